@@ -0,0 +1,497 @@
+//! Module containing the command line interface, used to analyze network traffic without opening any window
+//! (e.g. on servers accessed through SSH).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use pcap::Device;
+
+use crate::enums::byte_counting::ByteCounting;
+use crate::enums::capture_source::CaptureSource;
+use crate::enums::traffic_type::TrafficType;
+use crate::structs::capture_settings::CaptureSettings;
+use crate::structs::config::Config;
+use crate::structs::filters::Filters;
+use crate::structs::mmdb_readers::MmdbReaders;
+use crate::structs::recording::Recording;
+use crate::structs::report_settings::ReportSettings;
+use crate::thread_parse_packets::parse_packets_loop;
+use crate::utility::get_formatted_strings::get_formatted_bytes_string;
+use crate::utility::manage_packets::get_captures_result;
+use crate::{
+    sleep_and_write_report_loop, AppProtocol, InfoTraffic, IpVersion, Status, TransProtocol,
+};
+
+/// Usage message printed when the command line arguments are not valid
+pub const CLI_USAGE: &str = "\
+Usage: sniffnet --headless [OPTIONS]
+
+Options:
+  -a, --adapter <NAMES>     Comma separated network adapters to sniff (default: the system default adapter)
+  -f, --file <PATH>         Analyze the given pcap/pcapng file instead of sniffing an adapter
+      --ip <VERSION>        IP version filter: ipv4 | ipv6
+      --transport <PROTO>   Transport protocol filter: tcp | udp | icmp | arp
+      --app <PROTO>         Application protocol filter (e.g. http, dns, ...)
+      --bpf <EXPR>          BPF filter expression (e.g. \"net 10.0.0.0/8 and port 443\")
+      --addresses <LIST>    Comma separated IP addresses or networks (e.g. 10.0.0.0/8,fe80::/10)
+      --ports <LIST>        Comma separated ports or port ranges (e.g. 53,8000-8100)
+      --countries <LIST>    Comma separated country codes of the remote addresses (e.g. IT,US)
+      --traffic <LIST>      Comma separated traffic types: incoming | outgoing | multicast | broadcast | other
+      --names <LIST>        Comma separated (parts of) server or domain names (e.g. google,github.com)
+      --orgs <LIST>         Comma separated AS numbers or (parts of) organization names (e.g. AS13335,amazon)
+      --country-db <PATH>   Country database (MMDB file) used instead of the embedded one
+      --city-db <PATH>      City database (MMDB file) used to find the cities and coordinates of the remote addresses
+      --asn-db <PATH>       ASN database (MMDB file) used to find the organizations of the remote addresses
+      --snaplen <BYTES>     Bytes captured for each packet of the adapter (default: 1024)
+      --bytes <MODE>        Bytes counted for each packet: frame | network | payload (default: frame)
+  -d, --duration <SECS>     Stop after the given number of seconds
+  -n, --packets <N>         Stop after the given number of observed packets
+  -o, --output <DIR>        Directory where the report is written (default: ~/sniffnet_report)
+      --history <N>         Number of session reports kept in the directory (default: 10)
+      --flows               Show each conversation as a single connection, with both directions
+      --csv                 Also export the report as CSV (report.csv)
+      --json                Also export the report as JSON lines (report.jsonl)
+  -i, --interval <SECS>     Seconds between two printed totals (default: 1)
+  -h, --help                Print this message";
+
+/// Options of a headless analysis, as specified on the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliArgs {
+    /// Names of the network adapters to sniff (empty means the system default one)
+    pub adapters: Vec<String>,
+    /// Path of the capture file to analyze instead of sniffing an adapter
+    pub file: Option<String>,
+    /// Filters applied to the analyzed traffic
+    pub filters: Filters,
+    /// Configuration used to open the network adapter
+    pub capture_settings: CaptureSettings,
+    /// Number of seconds after which the analysis is stopped
+    pub duration: Option<u64>,
+    /// Number of observed packets after which the analysis is stopped
+    pub max_packets: Option<u128>,
+    /// Report directory, history size and machine-readable formats in which the report is also exported
+    pub report_settings: ReportSettings,
+    /// Seconds between two printed totals
+    pub interval: u64,
+    /// Path of the country database (if not given, the one set in the GUI is used)
+    pub country_database: Option<String>,
+    /// Path of the city database (if not given, the one set in the GUI is used)
+    pub city_database: Option<String>,
+    /// Path of the ASN database (if not given, the one set in the GUI is used)
+    pub asn_database: Option<String>,
+    /// Flag indicating that only the usage message has to be printed
+    pub help: bool,
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        CliArgs {
+            adapters: Vec::new(),
+            file: None,
+            filters: Filters::default(),
+            capture_settings: CaptureSettings::default(),
+            duration: None,
+            max_packets: None,
+            report_settings: ReportSettings::default(),
+            interval: 1,
+            country_database: None,
+            city_database: None,
+            asn_database: None,
+            help: false,
+        }
+    }
+}
+
+/// Parses the command line arguments (program name excluded).
+///
+/// Returns `Ok(None)` if no argument is given (i.e. the GUI has to be launched)
+/// and `Err` with a message describing the problem if the arguments are not valid.
+pub fn parse_cli_args(args: &[String]) -> Result<Option<CliArgs>, String> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+    // the usage is printed even if the headless flag is missing
+    if args.iter().any(|arg| arg.eq("-h") || arg.eq("--help")) {
+        return Ok(Some(CliArgs {
+            help: true,
+            ..CliArgs::default()
+        }));
+    }
+    if !args.iter().any(|arg| arg.eq("--headless")) {
+        return Err(format!("missing --headless flag\n\n{CLI_USAGE}"));
+    }
+
+    let mut cli_args = CliArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--headless" => {}
+            "-a" | "--adapter" => {
+                for adapter in value()?.split(',') {
+                    cli_args.adapters.push(adapter.trim().to_string());
+                }
+            }
+            "-f" | "--file" => cli_args.file = Some(value()?),
+            "--ip" => {
+                cli_args.filters.ip = parse_choice(&value()?, &IpVersion::ALL, "IP version")?;
+            }
+            "--transport" => {
+                cli_args.filters.transport =
+                    parse_choice(&value()?, &TransProtocol::ALL, "transport protocol")?;
+            }
+            "--app" => {
+                cli_args.filters.application =
+                    parse_choice(&value()?, &AppProtocol::ALL, "application protocol")?;
+            }
+            "--bpf" => cli_args.filters.bpf = value()?,
+            "--addresses" => cli_args.filters.addresses = value()?,
+            "--ports" => cli_args.filters.ports = value()?,
+            "--countries" => cli_args.filters.countries = value()?,
+            "--traffic" => {
+                for traffic_type in value()?.split(',') {
+                    cli_args.filters.traffic_types.push(parse_choice(
+                        traffic_type.trim(),
+                        &TrafficType::ALL,
+                        "traffic type",
+                    )?);
+                }
+            }
+            "--names" => cli_args.filters.server_names = value()?,
+            "--orgs" => cli_args.filters.organizations = value()?,
+            "--country-db" => cli_args.country_database = Some(value()?),
+            "--city-db" => cli_args.city_database = Some(value()?),
+            "--asn-db" => cli_args.asn_database = Some(value()?),
+            "--snaplen" => cli_args.capture_settings.snaplen = parse_number(&value()?)?,
+            "--bytes" => {
+                cli_args.capture_settings.byte_counting =
+                    parse_choice(&value()?, &ByteCounting::ALL, "byte counting mode")?;
+            }
+            "-d" | "--duration" => cli_args.duration = Some(parse_number(&value()?)?),
+            "-n" | "--packets" => cli_args.max_packets = Some(parse_number(&value()?)?),
+            "-o" | "--output" => cli_args.report_settings.directory = value()?,
+            "--history" => cli_args.report_settings.max_sessions = parse_number(&value()?)?,
+            "--flows" => cli_args.capture_settings.bidirectional_flows = true,
+            "--csv" => cli_args.report_settings.csv = true,
+            "--json" => cli_args.report_settings.json = true,
+            "-i" | "--interval" => cli_args.interval = parse_number::<u64>(&value()?)?.max(1),
+            _ => return Err(format!("unknown argument {arg}\n\n{CLI_USAGE}")),
+        }
+    }
+    cli_args.filters.validate()?;
+    Ok(Some(cli_args))
+}
+
+/// Finds the value whose displayed name matches (case insensitively) the given string
+fn parse_choice<T: ToString + Clone>(value: &str, choices: &[T], what: &str) -> Result<T, String> {
+    choices
+        .iter()
+        .find(|choice| choice.to_string().eq_ignore_ascii_case(value))
+        .cloned()
+        .ok_or_else(|| format!("invalid {what}: {value}"))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid number: {value}"))
+}
+
+/// Runs an analysis without GUI, printing periodic totals to stdout.
+///
+/// The analysis stops on Ctrl-C, when the specified duration or number of packets is reached,
+/// or when the whole capture file has been parsed.
+pub fn run_headless(cli_args: CliArgs) -> Result<(), String> {
+    let capture_source = if cli_args.file.is_some() {
+        CaptureSource::File
+    } else {
+        CaptureSource::Adapter
+    };
+    // no adapter is needed to read a capture file
+    let mut devices = Vec::new();
+    if capture_source.eq(&CaptureSource::Adapter) {
+        if cli_args.adapters.is_empty() {
+            devices.push(
+                Device::lookup()
+                    .map_err(|err| err.to_string())?
+                    .ok_or("no network adapter found")?,
+            );
+        } else {
+            let device_list = Device::list().map_err(|err| err.to_string())?;
+            for name in &cli_args.adapters {
+                devices.push(
+                    device_list
+                        .iter()
+                        .find(|dev| dev.name.eq(name))
+                        .cloned()
+                        .ok_or(format!("network adapter {name} not found"))?,
+                );
+            }
+        }
+    }
+    let capture_file = cli_args.file.clone().unwrap_or_default();
+    // the port mappings and the databases defined in the GUI settings are applied also to headless analyses
+    let config = confy::load::<Config>("sniffnet", None).unwrap_or_default();
+    let mut mmdb_settings = config.mmdb;
+    if let Some(country_database) = &cli_args.country_database {
+        mmdb_settings.country = country_database.clone();
+    }
+    if let Some(city_database) = &cli_args.city_database {
+        mmdb_settings.city = city_database.clone();
+    }
+    if let Some(asn_database) = &cli_args.asn_database {
+        mmdb_settings.asn = asn_database.clone();
+    }
+    let mmdb_readers = Arc::new(MmdbReaders::new(&mmdb_settings, &cli_args.filters)?);
+    let captures = get_captures_result(
+        &devices,
+        capture_source,
+        &capture_file,
+        &cli_args.filters.bpf,
+        cli_args.capture_settings.get_snaplen(),
+    )?;
+
+    let current_capture_id = Arc::new(Mutex::new(0));
+    let info_traffic_mutex = Arc::new(Mutex::new(InfoTraffic {
+        byte_counting: cli_args.capture_settings.byte_counting,
+        bidirectional_flows: cli_args.capture_settings.bidirectional_flows,
+        ..InfoTraffic::new()
+    }));
+    let status_pair = Arc::new((Mutex::new(Status::Running), Condvar::new()));
+    let recording_mutex = Arc::new(Mutex::new(Recording::default()));
+    let filters = cli_args.filters.clone();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_handler = stop.clone();
+    ctrlc::set_handler(move || stop_handler.store(true, Ordering::SeqCst))
+        .map_err(|err| err.to_string())?;
+
+    let current_capture_id2 = current_capture_id.clone();
+    let info_traffic_mutex2 = info_traffic_mutex.clone();
+    let report_settings_mutex = Arc::new(Mutex::new(cli_args.report_settings.clone()));
+    let report_stop = Arc::new(AtomicBool::new(false));
+    let report_stop2 = report_stop.clone();
+    let report_thread = thread::Builder::new()
+        .name("thread_write_report".to_string())
+        .spawn(move || {
+            sleep_and_write_report_loop(
+                &current_capture_id2,
+                &info_traffic_mutex2,
+                &status_pair,
+                &report_settings_mutex,
+                &report_stop2,
+            );
+        })
+        .unwrap();
+
+    let recording_directory = cli_args.report_settings.directory.clone();
+    let port_mappings = config.port_mappings;
+    // a thread for each adapter, all of them inserting packets in the same map
+    let mut parse_threads = Vec::new();
+    for (device, cap) in captures {
+        let current_capture_id = current_capture_id.clone();
+        let filters = filters.clone();
+        let info_traffic_mutex2 = info_traffic_mutex.clone();
+        let recording_mutex = recording_mutex.clone();
+        let recording_directory = recording_directory.clone();
+        let port_mappings = port_mappings.clone();
+        let mmdb_readers = mmdb_readers.clone();
+        parse_threads.push(
+            thread::Builder::new()
+                .name("thread_parse_packets".to_string())
+                .spawn(move || {
+                    parse_packets_loop(
+                        &current_capture_id,
+                        device,
+                        cap,
+                        capture_source,
+                        &filters,
+                        &info_traffic_mutex2,
+                        &recording_mutex,
+                        recording_directory,
+                        &port_mappings,
+                        &mmdb_readers,
+                    );
+                })
+                .unwrap(),
+        );
+    }
+
+    let start = Instant::now();
+    let mut last_print = Instant::now();
+    loop {
+        thread::sleep(Duration::from_millis(100));
+        let all_packets = {
+            let mut info_traffic = info_traffic_mutex.lock().unwrap();
+            // there are no charts to be updated
+            info_traffic.file_intervals.clear();
+            info_traffic.all_packets
+        };
+        let finished = stop.load(Ordering::SeqCst)
+            || parse_threads.iter().all(thread::JoinHandle::is_finished)
+            || cli_args
+                .duration
+                .is_some_and(|secs| start.elapsed() >= Duration::from_secs(secs))
+            || cli_args.max_packets.is_some_and(|n| all_packets >= n);
+        if finished {
+            break;
+        }
+        if last_print.elapsed() >= Duration::from_secs(cli_args.interval) {
+            last_print = Instant::now();
+            print_totals(&info_traffic_mutex.lock().unwrap());
+        }
+    }
+
+    // the report thread writes the last interval before ending
+    report_stop.store(true, Ordering::SeqCst);
+    report_thread
+        .join()
+        .map_err(|_| "error writing the report".to_string())?;
+    let info_traffic = info_traffic_mutex.lock().unwrap();
+    print_totals(&info_traffic);
+    match &info_traffic.report_path {
+        None => println!("No traffic matching the filters: no report was saved"),
+        Some(report_path) => println!("Report saved in {report_path}"),
+    }
+    if let Some(error) = &info_traffic.capture_error {
+        return Err(format!("error reading {capture_file}: {error}"));
+    }
+    match &info_traffic.report_error {
+        None => Ok(()),
+        Some(error) => Err(format!("error writing the report: {error}")),
+    }
+}
+
+/// Prints to stdout the current totals of the analyzed traffic
+fn print_totals(info_traffic: &InfoTraffic) {
+    let filtered_packets = info_traffic.tot_sent_packets + info_traffic.tot_received_packets;
+    let filtered_bytes = info_traffic.tot_sent_bytes + info_traffic.tot_received_bytes;
+    println!(
+        "[{}] packets: {} filtered / {} observed | bytes: {}B filtered / {}B observed | connections: {}",
+        Local::now().format("%H:%M:%S"),
+        filtered_packets,
+        info_traffic.all_packets,
+        get_formatted_bytes_string(filtered_bytes).trim(),
+        get_formatted_bytes_string(info_traffic.all_bytes).trim(),
+        info_traffic.map.len(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn no_args_launches_gui() {
+        assert_eq!(parse_cli_args(&[]), Ok(None));
+    }
+
+    #[test]
+    fn headless_flag_is_required() {
+        assert!(parse_cli_args(&to_args(&["-d", "10"])).is_err());
+    }
+
+    #[test]
+    fn help_is_not_an_error() {
+        let help = Ok(Some(CliArgs {
+            help: true,
+            ..CliArgs::default()
+        }));
+        assert_eq!(parse_cli_args(&to_args(&["--help"])), help);
+        assert_eq!(
+            parse_cli_args(&to_args(&["--headless", "-d", "10", "-h"])),
+            help
+        );
+    }
+
+    #[test]
+    fn parse_all_options() {
+        let args = to_args(&[
+            "--headless",
+            "-a",
+            "eth0, wlan0",
+            "--ip",
+            "ipv4",
+            "--transport",
+            "TCP",
+            "--app",
+            "https",
+            "--bpf",
+            "port 443",
+            "--ports",
+            "443,8000-8100",
+            "--traffic",
+            "incoming, outgoing",
+            "--names",
+            "example.com",
+            "--orgs",
+            "AS13335",
+            "--city-db",
+            "/tmp/city.mmdb",
+            "--asn-db",
+            "/tmp/asn.mmdb",
+            "--snaplen",
+            "2048",
+            "--bytes",
+            "payload",
+            "--flows",
+            "-d",
+            "30",
+            "-n",
+            "1000",
+            "-o",
+            "/tmp/out",
+        ]);
+        assert_eq!(
+            parse_cli_args(&args),
+            Ok(Some(CliArgs {
+                adapters: vec!["eth0".to_string(), "wlan0".to_string()],
+                filters: Filters {
+                    ip: IpVersion::IPv4,
+                    transport: TransProtocol::TCP,
+                    application: AppProtocol::HTTPS,
+                    bpf: "port 443".to_string(),
+                    ports: "443,8000-8100".to_string(),
+                    traffic_types: vec![TrafficType::Incoming, TrafficType::Outgoing],
+                    server_names: "example.com".to_string(),
+                    organizations: "AS13335".to_string(),
+                    ..Filters::default()
+                },
+                capture_settings: CaptureSettings {
+                    snaplen: 2048,
+                    byte_counting: ByteCounting::Payload,
+                    bidirectional_flows: true,
+                },
+                duration: Some(30),
+                max_packets: Some(1000),
+                report_settings: ReportSettings {
+                    directory: "/tmp/out".to_string(),
+                    ..ReportSettings::default()
+                },
+                city_database: Some("/tmp/city.mmdb".to_string()),
+                asn_database: Some("/tmp/asn.mmdb".to_string()),
+                ..CliArgs::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        assert!(parse_cli_args(&to_args(&["--headless", "--ip", "ipv5"])).is_err());
+        assert!(parse_cli_args(&to_args(&["--headless", "-d", "ten"])).is_err());
+        assert!(parse_cli_args(&to_args(&["--headless", "-o"])).is_err());
+        assert!(parse_cli_args(&to_args(&["--headless", "--unknown"])).is_err());
+        assert!(parse_cli_args(&to_args(&["--headless", "--ports", "80-"])).is_err());
+    }
+}
@@ -1,8 +1,12 @@
 #![allow(clippy::upper_case_acronyms)]
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Enum representing the possible observed values of application layer protocol.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// It's (de)serialized as its label, so that user defined protocols can be stored in the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum AppProtocol {
     /// File Transfer Protocol
     FTP,
@@ -53,6 +57,82 @@ pub enum AppProtocol {
     SSDP,
     /// Extensible Messaging and Presence Protocol |
     XMPP,
+    /// Kerberos authentication protocol
+    Kerberos,
+    /// Network News Transfer Protocol
+    NNTP,
+    /// Server Message Block
+    SMB,
+    /// Simple Mail Transfer Protocol over TLS/SSL
+    SMTPS,
+    /// System Logging Protocol
+    Syslog,
+    /// Dynamic Host Configuration Protocol for IPv6
+    DHCPv6,
+    /// Apple Filing Protocol
+    AFP,
+    /// Real Time Streaming Protocol
+    RTSP,
+    /// Internet Printing Protocol
+    IPP,
+    /// Remote file synchronization protocol
+    Rsync,
+    /// Socket Secure proxy protocol
+    SOCKS,
+    /// OpenVPN
+    OpenVPN,
+    /// Microsoft SQL Server
+    MSSQL,
+    /// Layer 2 Tunneling Protocol
+    L2TP,
+    /// Point-to-Point Tunneling Protocol
+    PPTP,
+    /// Remote Authentication Dial-In User Service
+    RADIUS,
+    /// Message Queuing Telemetry Transport
+    MQTT,
+    /// Network File System
+    NFS,
+    /// MySQL database
+    MySQL,
+    /// Remote Desktop Protocol
+    RDP,
+    /// Session Traversal Utilities for NAT
+    STUN,
+    /// Web Services Dynamic Discovery
+    WSD,
+    /// Real-time Transport Protocol
+    RTP,
+    /// Session Initiation Protocol
+    SIP,
+    /// Link-Local Multicast Name Resolution
+    LLMNR,
+    /// PostgreSQL database
+    PostgreSQL,
+    /// Advanced Message Queuing Protocol
+    AMQP,
+    /// Virtual Network Computing
+    VNC,
+    /// Redis database
+    Redis,
+    /// Internet Relay Chat
+    IRC,
+    /// Git protocol
+    Git,
+    /// Memcached
+    Memcached,
+    /// MongoDB database
+    MongoDB,
+    /// WireGuard
+    WireGuard,
+    /// Transport Layer Security (detected on ports not associated to a protocol over TLS)
+    TLS,
+    /// Quick UDP Internet Connections
+    QUIC,
+    /// BitTorrent peer-to-peer file sharing
+    BitTorrent,
+    /// Protocol defined by the user, with the given label
+    Custom(String),
     /// not identified
     Other,
 }
@@ -89,7 +169,9 @@ pub fn from_port_to_application_protocol(port: u16) -> AppProtocol {
         67..=68 => AppProtocol::DHCP,
         69 => AppProtocol::TFTP,
         80 | 8080 => AppProtocol::HTTP,
+        88 => AppProtocol::Kerberos,
         109..=110 => AppProtocol::POP,
+        119 => AppProtocol::NNTP,
         123 => AppProtocol::NTP,
         137..=139 => AppProtocol::NetBIOS,
         143 | 220 => AppProtocol::IMAP,
@@ -97,27 +179,86 @@ pub fn from_port_to_application_protocol(port: u16) -> AppProtocol {
         179 => AppProtocol::BGP,
         389 => AppProtocol::LDAP,
         443 => AppProtocol::HTTPS,
+        445 => AppProtocol::SMB,
+        465 => AppProtocol::SMTPS,
+        514 => AppProtocol::Syslog,
+        546..=547 => AppProtocol::DHCPv6,
+        548 => AppProtocol::AFP,
+        554 => AppProtocol::RTSP,
+        631 => AppProtocol::IPP,
         636 => AppProtocol::LDAPS,
+        873 => AppProtocol::Rsync,
         989..=990 => AppProtocol::FTPS,
         993 => AppProtocol::IMAPS,
         995 => AppProtocol::POP3S,
+        1080 => AppProtocol::SOCKS,
+        1194 => AppProtocol::OpenVPN,
+        1433..=1434 => AppProtocol::MSSQL,
+        1701 => AppProtocol::L2TP,
+        1723 => AppProtocol::PPTP,
+        1812..=1813 => AppProtocol::RADIUS,
+        1883 | 8883 => AppProtocol::MQTT,
         1900 => AppProtocol::SSDP,
+        2049 => AppProtocol::NFS,
+        3306 => AppProtocol::MySQL,
+        3389 => AppProtocol::RDP,
+        3478 => AppProtocol::STUN,
+        3702 => AppProtocol::WSD,
+        5004..=5005 => AppProtocol::RTP,
+        5060..=5061 => AppProtocol::SIP,
         5222 => AppProtocol::XMPP,
         5353 => AppProtocol::mDNS,
+        5355 => AppProtocol::LLMNR,
+        5432 => AppProtocol::PostgreSQL,
+        5672 => AppProtocol::AMQP,
+        5900 => AppProtocol::VNC,
+        6379 => AppProtocol::Redis,
+        6667 => AppProtocol::IRC,
+        9418 => AppProtocol::Git,
+        11211 => AppProtocol::Memcached,
+        27017 => AppProtocol::MongoDB,
+        51820 => AppProtocol::WireGuard,
         _ => AppProtocol::Other,
     }
 }
 
 impl fmt::Display for AppProtocol {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.eq(&AppProtocol::Other) {
-            write!(f, "All protocols")
-        } else {
-            write!(f, "{self:?}")
+        match self {
+            AppProtocol::Other => write!(f, "All protocols"),
+            AppProtocol::Custom(label) => write!(f, "{label}"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+impl From<AppProtocol> for String {
+    fn from(app_protocol: AppProtocol) -> Self {
+        match app_protocol {
+            AppProtocol::Custom(label) => label,
+            _ => format!("{app_protocol:?}"),
         }
     }
 }
 
+impl From<String> for AppProtocol {
+    /// Returns the protocol with the given label (case insensitive);
+    /// unknown labels correspond to user defined protocols.
+    fn from(label: String) -> Self {
+        let label = label.trim();
+        if label.eq_ignore_ascii_case("Other") {
+            return AppProtocol::Other;
+        }
+        if let Some(app_protocol) = AppProtocol::ALL
+            .iter()
+            .find(|app_protocol| format!("{app_protocol:?}").eq_ignore_ascii_case(label))
+        {
+            return app_protocol.clone();
+        }
+        AppProtocol::Custom(label.to_string())
+    }
+}
+
 /// Defines a constant to be used in the picklist in gui initial page
 impl AppProtocol {
     // pub fn get_picklist_label(&self, language: Language) -> String {
@@ -128,31 +269,68 @@ impl AppProtocol {
     //     }
     // }
 
-    pub(crate) const ALL: [AppProtocol; 25] = [
+    pub(crate) const ALL: [AppProtocol; 62] = [
         AppProtocol::Other,
+        AppProtocol::AFP,
+        AppProtocol::AMQP,
         AppProtocol::BGP,
+        AppProtocol::BitTorrent,
         AppProtocol::DHCP,
+        AppProtocol::DHCPv6,
         AppProtocol::DNS,
         AppProtocol::FTP,
         AppProtocol::FTPS,
+        AppProtocol::Git,
         AppProtocol::HTTP,
         AppProtocol::HTTPS,
         AppProtocol::IMAP,
         AppProtocol::IMAPS,
+        AppProtocol::IPP,
+        AppProtocol::IRC,
+        AppProtocol::Kerberos,
+        AppProtocol::L2TP,
         AppProtocol::LDAP,
         AppProtocol::LDAPS,
+        AppProtocol::LLMNR,
         AppProtocol::mDNS,
+        AppProtocol::Memcached,
+        AppProtocol::MongoDB,
+        AppProtocol::MQTT,
+        AppProtocol::MSSQL,
+        AppProtocol::MySQL,
         AppProtocol::NetBIOS,
+        AppProtocol::NFS,
+        AppProtocol::NNTP,
         AppProtocol::NTP,
+        AppProtocol::OpenVPN,
         AppProtocol::POP,
         AppProtocol::POP3S,
+        AppProtocol::PostgreSQL,
+        AppProtocol::PPTP,
+        AppProtocol::QUIC,
+        AppProtocol::RADIUS,
+        AppProtocol::RDP,
+        AppProtocol::Redis,
+        AppProtocol::Rsync,
+        AppProtocol::RTP,
+        AppProtocol::RTSP,
+        AppProtocol::SIP,
+        AppProtocol::SMB,
         AppProtocol::SMTP,
+        AppProtocol::SMTPS,
         AppProtocol::SNMP,
+        AppProtocol::SOCKS,
         AppProtocol::SSDP,
         AppProtocol::SSH,
+        AppProtocol::STUN,
+        AppProtocol::Syslog,
         AppProtocol::TACACS,
         AppProtocol::Telnet,
         AppProtocol::TFTP,
+        AppProtocol::TLS,
+        AppProtocol::VNC,
+        AppProtocol::WireGuard,
+        AppProtocol::WSD,
         AppProtocol::XMPP,
     ];
 }
@@ -181,6 +359,197 @@ mod tests {
         assert_eq!(AppProtocol::Other, result);
     }
 
+    #[test]
+    fn from_port_to_application_protocol_kerberos() {
+        assert_eq!(AppProtocol::Kerberos, from_port_to_application_protocol(88));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_nntp() {
+        assert_eq!(AppProtocol::NNTP, from_port_to_application_protocol(119));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_smb() {
+        assert_eq!(AppProtocol::SMB, from_port_to_application_protocol(445));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_smtps() {
+        assert_eq!(AppProtocol::SMTPS, from_port_to_application_protocol(465));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_syslog() {
+        assert_eq!(AppProtocol::Syslog, from_port_to_application_protocol(514));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_dhcpv6() {
+        assert_eq!(AppProtocol::DHCPv6, from_port_to_application_protocol(546));
+        assert_eq!(AppProtocol::DHCPv6, from_port_to_application_protocol(547));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_afp() {
+        assert_eq!(AppProtocol::AFP, from_port_to_application_protocol(548));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_rtsp() {
+        assert_eq!(AppProtocol::RTSP, from_port_to_application_protocol(554));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_ipp() {
+        assert_eq!(AppProtocol::IPP, from_port_to_application_protocol(631));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_rsync() {
+        assert_eq!(AppProtocol::Rsync, from_port_to_application_protocol(873));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_socks() {
+        assert_eq!(AppProtocol::SOCKS, from_port_to_application_protocol(1080));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_openvpn() {
+        assert_eq!(
+            AppProtocol::OpenVPN,
+            from_port_to_application_protocol(1194)
+        );
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_mssql() {
+        assert_eq!(AppProtocol::MSSQL, from_port_to_application_protocol(1433));
+        assert_eq!(AppProtocol::MSSQL, from_port_to_application_protocol(1434));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_l2tp() {
+        assert_eq!(AppProtocol::L2TP, from_port_to_application_protocol(1701));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_pptp() {
+        assert_eq!(AppProtocol::PPTP, from_port_to_application_protocol(1723));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_radius() {
+        assert_eq!(AppProtocol::RADIUS, from_port_to_application_protocol(1812));
+        assert_eq!(AppProtocol::RADIUS, from_port_to_application_protocol(1813));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_mqtt() {
+        assert_eq!(AppProtocol::MQTT, from_port_to_application_protocol(1883));
+        assert_eq!(AppProtocol::MQTT, from_port_to_application_protocol(8883));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_nfs() {
+        assert_eq!(AppProtocol::NFS, from_port_to_application_protocol(2049));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_mysql() {
+        assert_eq!(AppProtocol::MySQL, from_port_to_application_protocol(3306));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_rdp() {
+        assert_eq!(AppProtocol::RDP, from_port_to_application_protocol(3389));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_stun() {
+        assert_eq!(AppProtocol::STUN, from_port_to_application_protocol(3478));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_wsd() {
+        assert_eq!(AppProtocol::WSD, from_port_to_application_protocol(3702));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_rtp() {
+        assert_eq!(AppProtocol::RTP, from_port_to_application_protocol(5004));
+        assert_eq!(AppProtocol::RTP, from_port_to_application_protocol(5005));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_sip() {
+        assert_eq!(AppProtocol::SIP, from_port_to_application_protocol(5060));
+        assert_eq!(AppProtocol::SIP, from_port_to_application_protocol(5061));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_llmnr() {
+        assert_eq!(AppProtocol::LLMNR, from_port_to_application_protocol(5355));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_postgresql() {
+        assert_eq!(
+            AppProtocol::PostgreSQL,
+            from_port_to_application_protocol(5432)
+        );
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_amqp() {
+        assert_eq!(AppProtocol::AMQP, from_port_to_application_protocol(5672));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_vnc() {
+        assert_eq!(AppProtocol::VNC, from_port_to_application_protocol(5900));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_redis() {
+        assert_eq!(AppProtocol::Redis, from_port_to_application_protocol(6379));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_irc() {
+        assert_eq!(AppProtocol::IRC, from_port_to_application_protocol(6667));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_git() {
+        assert_eq!(AppProtocol::Git, from_port_to_application_protocol(9418));
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_memcached() {
+        assert_eq!(
+            AppProtocol::Memcached,
+            from_port_to_application_protocol(11211)
+        );
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_mongodb() {
+        assert_eq!(
+            AppProtocol::MongoDB,
+            from_port_to_application_protocol(27017)
+        );
+    }
+
+    #[test]
+    fn from_port_to_application_protocol_wireguard() {
+        assert_eq!(
+            AppProtocol::WireGuard,
+            from_port_to_application_protocol(51820)
+        );
+    }
+
     #[test]
     fn app_protocol_display_ftp() {
         let test_str = AppProtocol::FTP.to_string();
@@ -192,4 +561,31 @@ mod tests {
         let test_str = AppProtocol::Other.to_string();
         assert_eq!(test_str, "All protocols");
     }
+
+    #[test]
+    fn app_protocol_from_label() {
+        assert_eq!(AppProtocol::from("https".to_string()), AppProtocol::HTTPS);
+        assert_eq!(AppProtocol::from("Other".to_string()), AppProtocol::Other);
+        let custom = AppProtocol::from(" Backup ".to_string());
+        assert_eq!(custom, AppProtocol::Custom("Backup".to_string()));
+        assert_eq!(custom.to_string(), "Backup");
+        assert_eq!(String::from(custom), "Backup");
+        assert_eq!(String::from(AppProtocol::mDNS), "mDNS");
+    }
+
+    #[test]
+    fn custom_protocols_are_deserialized() {
+        let protocols: Vec<AppProtocol> = serde_json::from_str(r#"["Intranet", "ssh"]"#).unwrap();
+        assert_eq!(
+            protocols,
+            vec![
+                AppProtocol::Custom("Intranet".to_string()),
+                AppProtocol::SSH
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&protocols).unwrap(),
+            r#"["Intranet","SSH"]"#
+        );
+    }
 }
@@ -0,0 +1,40 @@
+use crate::utility::translations::{
+    frame_counting_translation, network_counting_translation, payload_counting_translation,
+};
+use crate::Language;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Enum representing the possible ways of counting the bytes of each packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum ByteCounting {
+    /// Length of the whole frame on the wire, as reported by pcap (link layer)
+    #[default]
+    Frame,
+    /// Total length of the IP packet, headers included (network layer)
+    Network,
+    /// Length of the transport layer payload
+    Payload,
+}
+
+impl fmt::Display for ByteCounting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl ByteCounting {
+    pub(crate) const ALL: [ByteCounting; 3] = [
+        ByteCounting::Frame,
+        ByteCounting::Network,
+        ByteCounting::Payload,
+    ];
+
+    pub fn get_radio_label(&self, language: Language) -> &str {
+        match self {
+            ByteCounting::Frame => frame_counting_translation(language),
+            ByteCounting::Network => network_counting_translation(language),
+            ByteCounting::Payload => payload_counting_translation(language),
+        }
+    }
+}
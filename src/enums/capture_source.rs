@@ -0,0 +1,22 @@
+use crate::utility::translations::{capture_file_translation, network_adapter_translation};
+use crate::Language;
+
+/// Enum representing the possible sources of the analyzed packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaptureSource {
+    /// Packets are sniffed live from a network adapter
+    Adapter,
+    /// Packets are read from a pcap/pcapng capture file
+    File,
+}
+
+impl CaptureSource {
+    pub(crate) const ALL: [CaptureSource; 2] = [CaptureSource::Adapter, CaptureSource::File];
+
+    pub fn get_radio_label(&self, language: Language) -> &str {
+        match self {
+            CaptureSource::Adapter => network_adapter_translation(language),
+            CaptureSource::File => capture_file_translation(language),
+        }
+    }
+}
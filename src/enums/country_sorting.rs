@@ -0,0 +1,32 @@
+use crate::utility::translations::{
+    bytes_report_translation, connections_report_translation, hosts_report_translation,
+    packets_report_translation,
+};
+use crate::Language;
+
+/// Enum representing the possible orderings of the countries table (the first countries have the highest values).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CountrySorting {
+    Bytes,
+    Packets,
+    Connections,
+    Hosts,
+}
+
+impl CountrySorting {
+    pub(crate) const ALL: [CountrySorting; 4] = [
+        CountrySorting::Bytes,
+        CountrySorting::Packets,
+        CountrySorting::Connections,
+        CountrySorting::Hosts,
+    ];
+
+    pub fn get_radio_label(&self, language: Language) -> &str {
+        match self {
+            CountrySorting::Bytes => bytes_report_translation(language),
+            CountrySorting::Packets => packets_report_translation(language),
+            CountrySorting::Connections => connections_report_translation(language),
+            CountrySorting::Hosts => hosts_report_translation(language),
+        }
+    }
+}
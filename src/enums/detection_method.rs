@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Enum representing the way the application layer protocol of a connection was detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DetectionMethod {
+    /// Heuristic: guessed from the well-known transport layer ports
+    Port,
+    /// Confirmed: a signature of the protocol was found in the payload of the first packet
+    Signature,
+}
+
+impl fmt::Display for DetectionMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
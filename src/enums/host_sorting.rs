@@ -0,0 +1,32 @@
+use crate::utility::translations::{
+    bytes_report_translation, connections_report_translation, packets_report_translation,
+    recent_report_translation,
+};
+use crate::Language;
+
+/// Enum representing the possible orderings of the remote hosts table (the first hosts have the highest values).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HostSorting {
+    Recent,
+    Packets,
+    Bytes,
+    Connections,
+}
+
+impl HostSorting {
+    pub(crate) const ALL: [HostSorting; 4] = [
+        HostSorting::Recent,
+        HostSorting::Packets,
+        HostSorting::Bytes,
+        HostSorting::Connections,
+    ];
+
+    pub fn get_radio_label(&self, language: Language) -> &str {
+        match self {
+            HostSorting::Recent => recent_report_translation(language),
+            HostSorting::Packets => packets_report_translation(language),
+            HostSorting::Bytes => bytes_report_translation(language),
+            HostSorting::Connections => connections_report_translation(language),
+        }
+    }
+}
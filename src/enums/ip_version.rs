@@ -1,9 +1,10 @@
 use crate::utility::translations::both_translation;
 use crate::Language;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Enum representing the possible observed values of IP protocol version.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IpVersion {
     /// Internet Protocol version 4
     IPv4,
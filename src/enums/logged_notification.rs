@@ -8,8 +8,8 @@ pub enum LoggedNotification {
     PacketsThresholdExceeded(PacketsThresholdExceeded),
     /// Byte threshold exceeded
     BytesThresholdExceeded(BytesThresholdExceeded),
-    /// Favorite connection exchanged data
-    FavoriteTransmitted(FavoriteTransmitted),
+    /// Favorite connection exchanged data (boxed, since connections carry many details)
+    FavoriteTransmitted(Box<FavoriteTransmitted>),
 }
 
 #[derive(Clone)]
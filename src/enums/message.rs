@@ -1,6 +1,18 @@
+use iced::keyboard::KeyCode;
+
+use crate::enums::capture_source::CaptureSource;
+use crate::enums::country_sorting::CountrySorting;
+use crate::enums::host_sorting::HostSorting;
 use crate::enums::overlay::MyOverlay;
+use crate::enums::report_column::ReportColumn;
 use crate::enums::running_page::RunningPage;
+use crate::enums::traffic_type::TrafficType;
+use crate::structs::capture_settings::CaptureSettings;
+use crate::structs::mmdb_settings::MmdbSettings;
 use crate::structs::notifications::{BytesNotification, FavoriteNotification, PacketsNotification};
+use crate::structs::port_mapping::PortMappingDraft;
+use crate::structs::recording::Recording;
+use crate::structs::report_settings::ReportSettings;
 use crate::{AppProtocol, ChartType, IpVersion, Language, ReportType, StyleType, TransProtocol};
 
 #[derive(Debug, Clone)]
@@ -10,18 +22,54 @@ pub enum Message {
     TickInit,
     /// Every 1 second
     TickRun,
-    /// Select adapter
-    AdapterSelection(String),
+    /// Select or deselect an adapter (more adapters can be sniffed at once)
+    AdapterSelection(String, bool),
+    /// Select the source of the packets (network adapter or capture file)
+    CaptureSourceSelection(CaptureSource),
+    /// Insert the path of the capture file to be analyzed
+    CaptureFileSelection(String),
     /// Select IP filter
     IpVersionSelection(IpVersion),
     /// Select transport filter
     TransportProtocolSelection(TransProtocol),
     /// Select application filter
     AppProtocolSelection(AppProtocol),
+    /// Insert the BPF filter expression
+    BpfFilterSelection(String),
+    /// Insert the IP addresses/networks filter
+    AddressesFilterSelection(String),
+    /// Insert the ports filter
+    PortsFilterSelection(String),
+    /// Insert the countries filter
+    CountriesFilterSelection(String),
+    /// Insert the server names filter
+    ServerNamesFilterSelection(String),
+    /// Insert the organizations filter
+    OrganizationsFilterSelection(String),
+    /// Select or deselect a traffic type filter
+    TrafficTypeSelection(TrafficType, bool),
     /// Select chart type to be displayed
     ChartSelection(ChartType),
     /// Select report type to be displayed
     ReportSelection(ReportType),
+    /// Sort the connections table by the given column (the order is reversed if it's already sorted by it)
+    ReportSortSelection(ReportColumn),
+    /// Search the given text in the connections table
+    ReportSearch(String),
+    /// Select the page of the connections table to be displayed
+    ReportPageSelection(usize),
+    /// Navigate the connections table with the keyboard
+    ReportKeyPressed(KeyCode),
+    /// Select the adapter whose connections are displayed in the report (unknown names mean all)
+    ReportInterfaceSelection(String),
+    /// Select the ordering of the remote hosts table
+    HostSortingSelection(HostSorting),
+    /// Select whether the remote hosts table is aggregated by organization
+    GroupByOrganizationSelection(bool),
+    /// Select the ordering of the countries table
+    CountrySortingSelection(CountrySorting),
+    /// Select the country whose connections are displayed in the report (None means all)
+    ReportCountrySelection(Option<String>),
     /// Saves the given connection into the favorites
     SaveConnection(usize),
     /// Un-saves the given connection into the favorites
@@ -56,4 +104,20 @@ pub enum Message {
     ClearAllNotifications,
     /// Set notifications volume
     ChangeVolume(u8),
+    /// Start or stop saving the sniffed packets to file
+    ToggleRecording,
+    /// Set packets recording configuration
+    UpdateRecording(Recording),
+    /// Update the report configuration
+    UpdateReportSettings(ReportSettings),
+    /// Update the configuration used to open the network adapters
+    UpdateCaptureSettings(CaptureSettings),
+    /// Update the paths of the geolocation databases
+    UpdateMmdbSettings(MmdbSettings),
+    /// Update the port mapping being inserted in the settings
+    UpdatePortMappingDraft(PortMappingDraft),
+    /// Validate the port mapping being inserted and add it to the user defined ones
+    AddPortMapping,
+    /// Remove the user defined port mapping with the given index
+    RemovePortMapping(usize),
 }
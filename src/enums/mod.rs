@@ -1,16 +1,24 @@
 pub mod app_protocol;
+pub mod byte_counting;
 pub mod byte_multiple;
+pub mod capture_source;
 pub mod chart_type;
+pub mod country_sorting;
+pub mod detection_method;
 pub mod element_type;
+pub mod host_sorting;
 pub mod ip_version;
 pub mod language;
 pub mod logged_notification;
 pub mod message;
 pub mod overlay;
+pub mod report_column;
 pub mod report_type;
+pub mod rtt_probe;
 pub mod running_page;
 pub mod sound;
 pub mod status;
 pub mod style_type;
+pub mod tcp_state;
 pub mod traffic_type;
 pub mod trans_protocol;
@@ -1,5 +1,6 @@
 use crate::utility::translations::{
-    language_translation, notifications_translation, style_translation,
+    advanced_translation, language_translation, notifications_translation, protocols_translation,
+    style_translation,
 };
 use crate::Language;
 
@@ -12,10 +13,16 @@ pub enum MyOverlay {
     SettingsAppearance,
     /// Settings Language page.
     SettingsLanguage,
+    /// Settings Advanced page.
+    SettingsAdvanced,
+    /// Settings Protocols page.
+    SettingsProtocols,
     /// Quit modal.
     Quit,
     /// Clear all modal.
     ClearAll,
+    /// Details of the connection with the given index.
+    ConnectionDetails(usize),
 }
 
 impl MyOverlay {
@@ -24,7 +31,9 @@ impl MyOverlay {
             MyOverlay::SettingsNotifications => notifications_translation(language),
             MyOverlay::SettingsAppearance => style_translation(language),
             MyOverlay::SettingsLanguage => language_translation(language),
-            MyOverlay::Quit | MyOverlay::ClearAll => "",
+            MyOverlay::SettingsAdvanced => advanced_translation(language),
+            MyOverlay::SettingsProtocols => protocols_translation(language),
+            MyOverlay::Quit | MyOverlay::ClearAll | MyOverlay::ConnectionDetails(_) => "",
         }
     }
 }
@@ -0,0 +1,147 @@
+use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::info_address_port_pair::InfoAddressPortPair;
+use crate::utility::get_formatted_strings::{get_formatted_bytes_string, get_formatted_rtt_string};
+use crate::AppProtocol;
+
+/// Enum representing the columns of the connections table, each of which can be used to sort it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportColumn {
+    SrcAddress,
+    SrcPort,
+    DstAddress,
+    DstPort,
+    TransProtocol,
+    AppProtocol,
+    Packets,
+    Bytes,
+    LastSeen,
+    Latency,
+    Country,
+}
+
+impl ReportColumn {
+    pub(crate) const ALL: [ReportColumn; 11] = [
+        ReportColumn::SrcAddress,
+        ReportColumn::SrcPort,
+        ReportColumn::DstAddress,
+        ReportColumn::DstPort,
+        ReportColumn::TransProtocol,
+        ReportColumn::AppProtocol,
+        ReportColumn::Packets,
+        ReportColumn::Bytes,
+        ReportColumn::LastSeen,
+        ReportColumn::Latency,
+        ReportColumn::Country,
+    ];
+
+    fn get_title(&self) -> &'static str {
+        match self {
+            ReportColumn::SrcAddress => "Src IP address",
+            ReportColumn::SrcPort => "Src port",
+            ReportColumn::DstAddress => "Dst IP address",
+            ReportColumn::DstPort => "Dst port",
+            ReportColumn::TransProtocol => "Layer4",
+            ReportColumn::AppProtocol => "Layer7",
+            ReportColumn::Packets => "Packets",
+            ReportColumn::Bytes => "Bytes",
+            ReportColumn::LastSeen => "Last seen",
+            ReportColumn::Latency => "RTT",
+            ReportColumn::Country => "Country",
+        }
+    }
+
+    /// Returns the number of characters of the column
+    /// (the address columns are wider if the displayed connections include long IPv6 addresses)
+    pub fn get_width(&self, long_addresses: bool) -> usize {
+        match self {
+            ReportColumn::SrcAddress | ReportColumn::DstAddress if long_addresses => 39,
+            ReportColumn::SrcAddress | ReportColumn::DstAddress => 25,
+            ReportColumn::SrcPort
+            | ReportColumn::DstPort
+            | ReportColumn::TransProtocol
+            | ReportColumn::AppProtocol => 10,
+            ReportColumn::Packets
+            | ReportColumn::Bytes
+            | ReportColumn::LastSeen
+            | ReportColumn::Latency
+            | ReportColumn::Country => 11,
+        }
+    }
+
+    /// Whether the column is sorted from the highest value when it's selected the first time
+    pub fn is_descending_by_default(&self) -> bool {
+        matches!(
+            self,
+            ReportColumn::Packets
+                | ReportColumn::Bytes
+                | ReportColumn::LastSeen
+                | ReportColumn::Latency
+        )
+    }
+
+    /// Returns the header of the column, with an arrow if the table is sorted by it
+    ///
+    /// # Arguments
+    ///
+    /// * `descending` - Order of the table if it's sorted by this column, `None` otherwise.
+    ///
+    /// * `long_addresses` - Whether the displayed connections include long IPv6 addresses.
+    pub fn get_header(&self, descending: Option<bool>, long_addresses: bool) -> String {
+        let title = match descending {
+            None => self.get_title().to_string(),
+            Some(true) => format!("{} v", self.get_title()),
+            Some(false) => format!("{} ^", self.get_title()),
+        };
+        format!("{:^width$}", title, width = self.get_width(long_addresses))
+    }
+
+    /// Returns the content of the column for the given connection, padded to the column width
+    pub fn get_cell(
+        &self,
+        key: &AddressPortPair,
+        info: &InfoAddressPortPair,
+        long_addresses: bool,
+    ) -> String {
+        let width = self.get_width(long_addresses);
+        match self {
+            ReportColumn::SrcAddress => format!("{:^width$}", key.address1),
+            ReportColumn::SrcPort => format!("{:>8}  ", key.port1),
+            ReportColumn::DstAddress => format!("{:^width$}", key.address2),
+            ReportColumn::DstPort => format!("{:>8}  ", key.port2),
+            ReportColumn::TransProtocol => format!("{:^width$}", key.trans_protocol.to_string()),
+            ReportColumn::AppProtocol => {
+                let app_string: String = match info.app_protocol {
+                    AppProtocol::Other => "Other".to_string(),
+                    // longer names would break the fixed width of the table rows
+                    _ => info.app_protocol.to_string().chars().take(9).collect(),
+                };
+                format!("{app_string:^width$}")
+            }
+            ReportColumn::Packets => format!("{:>9}  ", info.transmitted_packets),
+            ReportColumn::Bytes => format!(
+                "{:>9}  ",
+                get_formatted_bytes_string(info.transmitted_bytes).trim()
+            ),
+            ReportColumn::LastSeen => {
+                format!(
+                    "{:^width$}",
+                    info.final_timestamp.format("%H:%M:%S").to_string()
+                )
+            }
+            ReportColumn::Latency => format!(
+                "{:>9}  ",
+                info.rtt
+                    .map(|rtt| get_formatted_rtt_string(rtt.avg()))
+                    .unwrap_or_default()
+            ),
+            ReportColumn::Country => {
+                let country = if info.country.is_empty() {
+                    "?"
+                } else {
+                    &info.country
+                };
+                format!("{country:^width$}")
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+/// Enum representing the kinds of request/response exchanges used to estimate the round trip time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RttProbe {
+    /// TCP handshake: SYN answered by SYN-ACK
+    TcpHandshake,
+    /// DNS query answered by the response with the same transaction ID
+    DnsQuery(u16),
+}
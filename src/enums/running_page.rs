@@ -1,4 +1,6 @@
-use crate::utility::translations::{notifications_translation, overview_translation};
+use crate::utility::translations::{
+    countries_translation, hosts_translation, notifications_translation, overview_translation,
+};
 use crate::Language;
 
 /// This enum defines the current running page.
@@ -10,6 +12,10 @@ pub enum RunningPage {
     // Inspect,
     /// Notifications page.
     Notifications,
+    /// Remote hosts page.
+    Hosts,
+    /// Countries page.
+    Countries,
 }
 
 impl RunningPage {
@@ -18,6 +24,8 @@ impl RunningPage {
             RunningPage::Overview => overview_translation(language),
             // RunningPage::Inspect => inspect_translation(language),
             RunningPage::Notifications => notifications_translation(language),
+            RunningPage::Hosts => hosts_translation(language),
+            RunningPage::Countries => countries_translation(language),
         }
     }
 }
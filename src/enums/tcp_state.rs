@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::utility::translations::{
+    closed_translation, established_translation, handshake_not_seen_translation,
+    refused_translation, reset_translation, syn_sent_translation,
+};
+use crate::Language;
+
+/// Enum representing the state of a TCP connection, derived from the flags of its segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TcpState {
+    /// The client sent a SYN, but the handshake was not completed
+    SynSent,
+    /// The handshake was completed
+    Established,
+    /// The connection was closed with a FIN
+    Closed,
+    /// The connection was aborted with a RST
+    Reset,
+    /// The SYN was answered with a RST
+    Refused,
+    /// The handshake was not seen (the connection was already open when the capture started)
+    Unknown,
+}
+
+impl fmt::Display for TcpState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl TcpState {
+    pub fn get_label(&self, language: Language) -> &str {
+        match self {
+            TcpState::SynSent => syn_sent_translation(language),
+            TcpState::Established => established_translation(language),
+            TcpState::Closed => closed_translation(language),
+            TcpState::Reset => reset_translation(language),
+            TcpState::Refused => refused_translation(language),
+            TcpState::Unknown => handshake_not_seen_translation(language),
+        }
+    }
+}
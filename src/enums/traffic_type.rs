@@ -1,5 +1,14 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::utility::translations::{
+    broadcast_translation, incoming_translation, multicast_translation, outgoing_translation,
+    unknown_translation,
+};
+use crate::Language;
+
 /// Enum representing the possible traffic type (incoming, outgoing or multicast).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TrafficType {
     /// Incoming traffic (from remote address to local interface)
     Incoming,
@@ -12,3 +21,38 @@ pub enum TrafficType {
     /// Not identified
     Other,
 }
+
+impl fmt::Display for TrafficType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl TrafficType {
+    pub(crate) const ALL: [TrafficType; 5] = [
+        TrafficType::Incoming,
+        TrafficType::Outgoing,
+        TrafficType::Multicast,
+        TrafficType::Broadcast,
+        TrafficType::Other,
+    ];
+
+    pub fn get_radio_label(&self, language: Language) -> &str {
+        match self {
+            TrafficType::Incoming => incoming_translation(language),
+            TrafficType::Outgoing => outgoing_translation(language),
+            TrafficType::Multicast => multicast_translation(language),
+            TrafficType::Broadcast => broadcast_translation(language),
+            TrafficType::Other => unknown_translation(language),
+        }
+    }
+
+    /// Returns the traffic type of the packets travelling in the opposite direction
+    pub fn reversed(self) -> Self {
+        match self {
+            TrafficType::Incoming => TrafficType::Outgoing,
+            TrafficType::Outgoing => TrafficType::Incoming,
+            other => other,
+        }
+    }
+}
@@ -1,15 +1,22 @@
 #![allow(clippy::upper_case_acronyms)]
-use crate::utility::translations::both_translation;
+use crate::utility::translations::all_translation;
 use crate::Language;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Enum representing the possible observed values of transport layer protocol.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// ICMP and ARP packets, not carrying a transport layer protocol, are tracked as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TransProtocol {
     /// Transmission Control Protocol
     TCP,
     /// User Datagram Protocol
     UDP,
+    /// Internet Control Message Protocol (for both IPv4 and IPv6)
+    ICMP,
+    /// Address Resolution Protocol
+    ARP,
     /// Not identified
     Other,
 }
@@ -21,14 +28,25 @@ impl fmt::Display for TransProtocol {
 }
 
 impl TransProtocol {
-    pub(crate) const ALL: [TransProtocol; 3] =
+    pub(crate) const ALL: [TransProtocol; 5] = [
+        TransProtocol::TCP,
+        TransProtocol::UDP,
+        TransProtocol::ICMP,
+        TransProtocol::ARP,
+        TransProtocol::Other,
+    ];
+
+    /// Protocols carrying ports, to which the port mappings apply (Other means both TCP and UDP)
+    pub(crate) const WITH_PORTS: [TransProtocol; 3] =
         [TransProtocol::TCP, TransProtocol::UDP, TransProtocol::Other];
 
     pub fn get_radio_label(&self, language: Language) -> &str {
         match self {
             TransProtocol::TCP => "TCP",
             TransProtocol::UDP => "UDP",
-            TransProtocol::Other => both_translation(language),
+            TransProtocol::ICMP => "ICMP",
+            TransProtocol::ARP => "ARP",
+            TransProtocol::Other => all_translation(language),
         }
     }
 }
@@ -2,15 +2,20 @@
 //!
 //! It also is a wrapper of gui's main two pages: initial and run page.
 
+use iced::keyboard::KeyCode;
 use iced::widget::Column;
-use iced::{executor, Application, Command, Element, Subscription, Theme};
+use iced::{event, executor, keyboard, Application, Command, Element, Event, Subscription, Theme};
 use pcap::Device;
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+use crate::enums::capture_source::CaptureSource;
+use crate::enums::country_sorting::CountrySorting;
+use crate::enums::host_sorting::HostSorting;
 use crate::enums::message::Message;
 use crate::enums::overlay::MyOverlay;
 use crate::enums::running_page::RunningPage;
@@ -19,21 +24,33 @@ use crate::enums::status::Status;
 use crate::gui::components::footer::get_footer;
 use crate::gui::components::header::get_header;
 use crate::gui::components::modal::{get_clear_all_overlay, get_exit_overlay, Modal};
+use crate::gui::pages::connection_details_page::connection_details_page;
+use crate::gui::pages::countries_page::countries_page;
+use crate::gui::pages::hosts_page::hosts_page;
 use crate::gui::pages::initial_page::initial_page;
 // use crate::gui::pages::inspect_page::inspect_page;
 use crate::gui::pages::notifications_page::notifications_page;
 use crate::gui::pages::overview_page::overview_page;
 use crate::gui::pages::settings::{
-    settings_appearance_page, settings_language_page, settings_notifications_page,
+    settings_advanced_page, settings_appearance_page, settings_language_page,
+    settings_notifications_page, settings_protocols_page,
 };
 use crate::structs::config::Config;
+use crate::structs::mmdb_readers::MmdbReaders;
+use crate::structs::port_mapping::{PortMapping, PortMappingDraft};
+use crate::structs::recording::Recording;
+use crate::structs::report_table::ReportTable;
 use crate::structs::sniffer::Sniffer;
 use crate::structs::traffic_chart::TrafficChart;
+use crate::structs::world_map::WorldMap;
 use crate::thread_parse_packets::parse_packets_loop;
-use crate::utility::manage_charts_data::update_charts_data;
+use crate::utility::manage_charts_data::{update_charts_data, update_file_charts_data};
+use crate::utility::manage_filters::validate_bpf_filter;
 use crate::utility::manage_notifications::notify_and_log;
-use crate::utility::manage_packets::get_capture_result;
-use crate::utility::manage_report_data::update_report_data;
+use crate::utility::manage_packets::get_captures_result;
+use crate::utility::manage_report_data::{
+    update_countries_data, update_hosts_data, update_report_data,
+};
 use crate::utility::style_constants::get_font;
 use crate::{InfoTraffic, ReportType, RunTimeData};
 
@@ -62,6 +79,9 @@ impl Application for Sniffer {
             Message::TickInit => {}
             Message::TickRun => {
                 let mut info_traffic_lock = self.info_traffic.lock().unwrap();
+                if let Some(error) = info_traffic_lock.capture_error.take() {
+                    self.pcap_error = Some(error);
+                }
                 self.runtime_data.borrow_mut().all_packets = info_traffic_lock.all_packets;
                 if info_traffic_lock.tot_received_packets + info_traffic_lock.tot_sent_packets == 0
                 {
@@ -85,14 +105,38 @@ impl Application for Sniffer {
                         .favorite_featured_last_interval =
                         info_traffic_lock.favorite_featured_last_interval.clone();
                     info_traffic_lock.favorite_featured_last_interval = None;
+                    let file_intervals = std::mem::take(&mut info_traffic_lock.file_intervals);
                     drop(info_traffic_lock);
                     notify_and_log(self.runtime_data.borrow_mut(), self.notifications);
-                    update_charts_data(self.runtime_data.borrow_mut());
+                    if self.capture_source.eq(&CaptureSource::File) {
+                        update_file_charts_data(self.runtime_data.borrow_mut(), file_intervals);
+                    } else {
+                        update_charts_data(self.runtime_data.borrow_mut());
+                    }
                     update_report_data(
                         self.runtime_data.borrow_mut(),
                         &self.info_traffic,
                         self.report_type,
+                        self.report_interface.as_deref(),
+                        self.report_country.as_deref(),
+                        &mut self.report_table,
                     );
+                    // hosts are aggregated only when they're displayed
+                    if self.running_page.eq(&RunningPage::Hosts) {
+                        update_hosts_data(
+                            self.runtime_data.borrow_mut(),
+                            &self.info_traffic,
+                            self.host_sorting,
+                            self.group_by_organization,
+                        );
+                    }
+                    if self.running_page.eq(&RunningPage::Countries) {
+                        update_countries_data(
+                            self.runtime_data.borrow_mut(),
+                            &self.info_traffic,
+                            self.country_sorting,
+                        );
+                    }
                     // waiting notifications
                     if self.running_page.eq(&RunningPage::Notifications)
                         && self.runtime_data.borrow().logged_notifications.is_empty()
@@ -101,14 +145,25 @@ impl Application for Sniffer {
                     }
                 }
             }
-            Message::AdapterSelection(name) => {
-                for dev in Device::list().expect("Error retrieving device list\r\n") {
-                    if dev.name.eq(&name) {
-                        self.device = dev;
-                        break;
+            Message::AdapterSelection(name, selected) => {
+                if selected {
+                    for dev in Device::list().expect("Error retrieving device list\r\n") {
+                        if dev.name.eq(&name) && !self.devices.iter().any(|d| d.name.eq(&name)) {
+                            self.devices.push(dev);
+                            break;
+                        }
                     }
+                } else if self.devices.len() > 1 {
+                    // at least an adapter must stay selected
+                    self.devices.retain(|dev| dev.name.ne(&name));
                 }
             }
+            Message::CaptureSourceSelection(capture_source) => {
+                self.capture_source = capture_source;
+            }
+            Message::CaptureFileSelection(path) => {
+                self.capture_file = path;
+            }
             Message::IpVersionSelection(version) => {
                 self.filters.ip = version;
             }
@@ -118,34 +173,198 @@ impl Application for Sniffer {
             Message::AppProtocolSelection(protocol) => {
                 self.filters.application = protocol;
             }
+            Message::BpfFilterSelection(expression) => {
+                self.bpf_error = validate_bpf_filter(&expression).err();
+                self.filters.bpf = expression;
+            }
+            Message::AddressesFilterSelection(addresses) => {
+                self.filters.addresses = addresses;
+            }
+            Message::PortsFilterSelection(ports) => {
+                self.filters.ports = ports;
+            }
+            Message::CountriesFilterSelection(countries) => {
+                self.filters.countries = countries;
+            }
+            Message::ServerNamesFilterSelection(server_names) => {
+                self.filters.server_names = server_names;
+            }
+            Message::OrganizationsFilterSelection(organizations) => {
+                self.filters.organizations = organizations;
+            }
+            Message::TrafficTypeSelection(traffic_type, selected) => {
+                self.filters.traffic_types.retain(|t| t.ne(&traffic_type));
+                if selected {
+                    self.filters.traffic_types.push(traffic_type);
+                }
+            }
             Message::ChartSelection(what_to_display) => {
                 self.traffic_chart.change_kind(what_to_display);
             }
+            Message::HostSortingSelection(host_sorting) => {
+                self.host_sorting = host_sorting;
+                update_hosts_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.host_sorting,
+                    self.group_by_organization,
+                );
+            }
+            Message::GroupByOrganizationSelection(group_by_organization) => {
+                self.group_by_organization = group_by_organization;
+                update_hosts_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.host_sorting,
+                    self.group_by_organization,
+                );
+            }
+            Message::CountrySortingSelection(country_sorting) => {
+                self.country_sorting = country_sorting;
+                update_countries_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.country_sorting,
+                );
+            }
+            Message::ReportCountrySelection(country) => {
+                // the connections of the selected country are shown in the overview
+                self.report_country = country;
+                self.report_table.page = 0;
+                self.running_page = RunningPage::Overview;
+                update_report_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
+                );
+            }
             Message::ReportSelection(what_to_display) => {
                 if what_to_display.ne(&self.report_type) {
                     self.report_type = what_to_display;
+                    self.report_table.page = 0;
                     update_report_data(
                         self.runtime_data.borrow_mut(),
                         &self.info_traffic,
                         self.report_type,
+                        self.report_interface.as_deref(),
+                        self.report_country.as_deref(),
+                        &mut self.report_table,
                     );
                 }
             }
+            Message::ReportInterfaceSelection(name) => {
+                self.report_interface = self
+                    .devices
+                    .iter()
+                    .any(|dev| dev.name.eq(&name))
+                    .then_some(name);
+                self.report_table.page = 0;
+                update_report_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
+                );
+            }
+            Message::ReportSortSelection(column) => {
+                self.report_table.sort_by(column);
+                update_report_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
+                );
+            }
+            Message::ReportSearch(search) => {
+                self.report_table.search = search;
+                self.report_table.page = 0;
+                update_report_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
+                );
+            }
+            Message::ReportPageSelection(page) => {
+                // the page is moved back to the last one if it's beyond it
+                self.report_table.page = page;
+                update_report_data(
+                    self.runtime_data.borrow_mut(),
+                    &self.info_traffic,
+                    self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
+                );
+            }
+            Message::ReportKeyPressed(key_code) => {
+                // the table can be navigated only when it's visible
+                if self.running_page.eq(&RunningPage::Overview) && self.overlay.is_none() {
+                    let indexes: Vec<usize> = self
+                        .runtime_data
+                        .borrow()
+                        .report_vec
+                        .iter()
+                        .map(|(_, info)| info.index)
+                        .collect();
+                    match key_code {
+                        KeyCode::Down => self.report_table.select_next(&indexes),
+                        KeyCode::Up => self.report_table.select_previous(&indexes),
+                        KeyCode::PageDown => {
+                            self.update(Message::ReportPageSelection(self.report_table.page + 1));
+                        }
+                        KeyCode::PageUp => {
+                            self.update(Message::ReportPageSelection(
+                                self.report_table.page.saturating_sub(1),
+                            ));
+                        }
+                        KeyCode::Enter => {
+                            if let Some(index) = self.report_table.selected {
+                                self.update(Message::ShowModal(MyOverlay::ConnectionDetails(
+                                    index,
+                                )));
+                            }
+                        }
+                        KeyCode::Escape => self.report_table.selected = None,
+                        _ => {}
+                    }
+                }
+            }
             Message::OpenReport => {
+                // the report of the current capture may not exist yet: open its directory
+                let path = self
+                    .info_traffic
+                    .lock()
+                    .unwrap()
+                    .report_path
+                    .clone()
+                    .unwrap_or_else(|| self.report_settings.lock().unwrap().directory.clone());
                 #[cfg(target_os = "windows")]
                 std::process::Command::new("explorer")
-                    .arg(r".\sniffnet_report\report.txt")
+                    .arg(path)
                     .spawn()
                     .unwrap();
                 #[cfg(target_os = "macos")]
-                std::process::Command::new("open")
-                    .arg("-t")
-                    .arg("./sniffnet_report/report.txt")
-                    .spawn()
-                    .unwrap();
+                {
+                    let mut command = std::process::Command::new("open");
+                    if std::path::Path::new(&path).is_file() {
+                        // open the report with the default text editor
+                        command.arg("-t");
+                    }
+                    command.arg(path).spawn().unwrap();
+                }
                 #[cfg(target_os = "linux")]
                 std::process::Command::new("xdg-open")
-                    .arg("./sniffnet_report/report.txt")
+                    .arg(path)
                     .spawn()
                     .unwrap();
             }
@@ -166,47 +385,92 @@ impl Application for Sniffer {
                     .spawn()
                     .unwrap();
             }
+            // the analysis can't be started (e.g. by submitting a text filter) with an invalid BPF expression
+            Message::Start if self.bpf_error.is_some() => {}
             Message::Start => {
-                let device = self.device.clone();
-                let (pcap_error, cap) = get_capture_result(&device);
-                self.pcap_error = pcap_error.clone();
+                let capture_source = self.capture_source;
+                // the databases are opened again at each analysis, to apply the changes to their files
+                let captures_result = self
+                    .filters
+                    .validate()
+                    .and_then(|()| MmdbReaders::new(&self.mmdb_settings, &self.filters))
+                    .and_then(|mmdb_readers| {
+                        get_captures_result(
+                            &self.devices,
+                            capture_source,
+                            &self.capture_file,
+                            &self.filters.bpf,
+                            self.capture_settings.get_snaplen(),
+                        )
+                        .map(|captures| (captures, Arc::new(mmdb_readers)))
+                    });
+                // filters are persisted to be proposed again at the next launch
+                store_config(self);
+                self.pcap_error = captures_result.as_ref().err().cloned();
+                self.report_interface = None;
+                self.report_country = None;
+                *self.info_traffic.lock().unwrap() = InfoTraffic {
+                    byte_counting: self.capture_settings.byte_counting,
+                    bidirectional_flows: self.capture_settings.bidirectional_flows,
+                    ..InfoTraffic::new()
+                };
                 *self.status_pair.0.lock().unwrap() = Status::Running;
-                let info_traffic_mutex = self.info_traffic.clone();
-                *info_traffic_mutex.lock().unwrap() = InfoTraffic::new();
                 self.runtime_data = Rc::new(RefCell::new(RunTimeData::new()));
                 self.traffic_chart =
                     TrafficChart::new(self.runtime_data.clone(), self.style, self.language);
+                self.world_map = WorldMap::new(self.runtime_data.clone(), self.style);
 
-                if pcap_error.is_none() {
+                if let Ok((captures, mmdb_readers)) = captures_result {
                     // no pcap error
-                    let current_capture_id = self.current_capture_id.clone();
-                    let filters = self.filters.clone();
+                    let recording_directory =
+                        self.report_settings.lock().unwrap().directory.clone();
                     self.status_pair.1.notify_all();
-                    thread::Builder::new()
-                        .name("thread_parse_packets".to_string())
-                        .spawn(move || {
-                            parse_packets_loop(
-                                &current_capture_id,
-                                device.clone(),
-                                cap.unwrap(),
-                                &filters,
-                                &info_traffic_mutex,
-                            );
-                        })
-                        .unwrap();
+                    // a thread for each adapter, all of them inserting packets in the same map
+                    for (device, cap) in captures {
+                        let current_capture_id = self.current_capture_id.clone();
+                        let filters = self.filters.clone();
+                        let info_traffic_mutex = self.info_traffic.clone();
+                        let recording_mutex = self.recording.clone();
+                        let recording_directory = recording_directory.clone();
+                        let port_mappings = self.port_mappings.clone();
+                        let mmdb_readers = mmdb_readers.clone();
+                        thread::Builder::new()
+                            .name("thread_parse_packets".to_string())
+                            .spawn(move || {
+                                parse_packets_loop(
+                                    &current_capture_id,
+                                    device,
+                                    cap,
+                                    capture_source,
+                                    &filters,
+                                    &info_traffic_mutex,
+                                    &recording_mutex,
+                                    recording_directory,
+                                    &port_mappings,
+                                    &mmdb_readers,
+                                );
+                            })
+                            .unwrap();
+                    }
                 }
             }
             Message::Reset => {
                 *self.status_pair.0.lock().unwrap() = Status::Init;
                 self.running_page = RunningPage::Overview;
                 *self.current_capture_id.lock().unwrap() += 1; //change capture id to kill previous capture and to rewrite output file
+                self.recording.lock().unwrap().active = false;
                 self.pcap_error = None;
-                self.report_type = ReportType::MostRecent;
+                self.report_type = ReportType::AllConnections;
+                self.report_table = ReportTable::default();
+                self.host_sorting = HostSorting::Recent;
+                self.group_by_organization = false;
+                self.country_sorting = CountrySorting::Bytes;
                 self.update(Message::HideModal(false));
             }
             Message::Style(style) => {
                 self.style = style;
                 self.traffic_chart.change_colors(self.style);
+                self.world_map.change_colors(self.style);
             }
             Message::Waiting => {
                 if self.waiting.len() > 2 {
@@ -224,6 +488,9 @@ impl Application for Sniffer {
                     self.runtime_data.borrow_mut(),
                     &self.info_traffic,
                     self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
                 );
             }
             Message::UnSaveConnection(index) => {
@@ -236,6 +503,9 @@ impl Application for Sniffer {
                     self.runtime_data.borrow_mut(),
                     &self.info_traffic,
                     self.report_type,
+                    self.report_interface.as_deref(),
+                    self.report_country.as_deref(),
+                    &mut self.report_table,
                 );
             }
             Message::ShowModal(overlay) => {
@@ -244,16 +514,26 @@ impl Application for Sniffer {
             Message::HideModal(save_config) => {
                 self.overlay = None;
                 if save_config {
-                    let store = Config {
-                        style: self.style,
-                        notifications: self.notifications,
-                        language: self.language,
-                    };
-                    confy::store("sniffnet", None, store).unwrap();
+                    store_config(self);
                 }
             }
             Message::ChangeRunningPage(running_page) => {
                 self.running_page = running_page;
+                if running_page.eq(&RunningPage::Hosts) {
+                    update_hosts_data(
+                        self.runtime_data.borrow_mut(),
+                        &self.info_traffic,
+                        self.host_sorting,
+                        self.group_by_organization,
+                    );
+                }
+                if running_page.eq(&RunningPage::Countries) {
+                    update_countries_data(
+                        self.runtime_data.borrow_mut(),
+                        &self.info_traffic,
+                        self.country_sorting,
+                    );
+                }
             }
             Message::LanguageSelection(language) => {
                 self.language = language;
@@ -281,6 +561,44 @@ impl Application for Sniffer {
                 play_sound(Sound::Pop, volume);
                 self.notifications.volume = volume;
             }
+            Message::ToggleRecording => {
+                let mut recording = self.recording.lock().unwrap();
+                recording.active = !recording.active;
+            }
+            Message::UpdateRecording(value) => {
+                let mut recording = self.recording.lock().unwrap();
+                *recording = Recording {
+                    active: recording.active,
+                    ..value
+                };
+            }
+            Message::UpdateReportSettings(value) => {
+                *self.report_settings.lock().unwrap() = value;
+            }
+            Message::UpdateCaptureSettings(value) => {
+                self.capture_settings = value;
+            }
+            Message::UpdateMmdbSettings(value) => {
+                self.mmdb_settings = value;
+            }
+            Message::UpdatePortMappingDraft(draft) => {
+                self.port_mapping_draft = draft;
+            }
+            Message::AddPortMapping => {
+                let draft = &self.port_mapping_draft;
+                match PortMapping::new(&draft.ports, draft.transport, &draft.label) {
+                    Ok(port_mapping) => {
+                        self.port_mappings.push(port_mapping);
+                        self.port_mapping_draft = PortMappingDraft::default();
+                    }
+                    Err(error) => self.port_mapping_draft.error = Some(error),
+                }
+            }
+            Message::RemovePortMapping(index) => {
+                if index < self.port_mappings.len() {
+                    self.port_mappings.remove(index);
+                }
+            }
             Message::ClearAllNotifications => {
                 self.runtime_data.borrow_mut().logged_notifications = VecDeque::new();
                 self.update(Message::HideModal(false));
@@ -294,11 +612,12 @@ impl Application for Sniffer {
         let style = self.style;
 
         let header = match status {
-            Status::Init => get_header(style, false, 0, self.language),
+            Status::Init => get_header(style, false, 0, false, self.language),
             Status::Running => get_header(
                 style,
                 true,
                 self.info_traffic.lock().unwrap().all_packets,
+                self.recording.lock().unwrap().active,
                 self.language,
             ),
         };
@@ -309,6 +628,8 @@ impl Application for Sniffer {
                 RunningPage::Overview => overview_page(self),
                 // RunningPage::Inspect => inspect_page(self),
                 RunningPage::Notifications => notifications_page(self),
+                RunningPage::Hosts => hosts_page(self),
+                RunningPage::Countries => countries_page(self),
             },
         };
 
@@ -332,6 +653,11 @@ impl Application for Sniffer {
                 MyOverlay::SettingsNotifications => (settings_notifications_page(self), true),
                 MyOverlay::SettingsAppearance => (settings_appearance_page(self), true),
                 MyOverlay::SettingsLanguage => (settings_language_page(self), true),
+                MyOverlay::SettingsAdvanced => (settings_advanced_page(self), true),
+                MyOverlay::SettingsProtocols => (settings_protocols_page(self), true),
+                MyOverlay::ConnectionDetails(index) => {
+                    (connection_details_page(self, index), false)
+                }
             };
 
             Modal::new(content, overlay)
@@ -342,12 +668,50 @@ impl Application for Sniffer {
 
     fn subscription(&self) -> Subscription<Message> {
         match *self.status_pair.0.lock().unwrap() {
-            Status::Running => {
-                iced::time::every(Duration::from_millis(PERIOD_RUNNING)).map(|_| Message::TickRun)
-            }
+            Status::Running => Subscription::batch([
+                iced::time::every(Duration::from_millis(PERIOD_RUNNING)).map(|_| Message::TickRun),
+                iced::subscription::events_with(get_report_key_message),
+            ]),
             Status::Init => {
                 iced::time::every(Duration::from_millis(PERIOD_INIT)).map(|_| Message::TickInit)
             }
         }
     }
 }
+
+/// Returns the message corresponding to the keys used to navigate the connections table
+/// (keys already handled by a widget, like the search box, are ignored)
+fn get_report_key_message(event: Event, status: event::Status) -> Option<Message> {
+    match (event, status) {
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code:
+                    key_code @ (KeyCode::Up
+                    | KeyCode::Down
+                    | KeyCode::PageUp
+                    | KeyCode::PageDown
+                    | KeyCode::Enter
+                    | KeyCode::Escape),
+                modifiers,
+            }),
+            event::Status::Ignored,
+        ) if modifiers.is_empty() => Some(Message::ReportKeyPressed(key_code)),
+        _ => None,
+    }
+}
+
+/// Saves the current configuration of the application to file
+fn store_config(sniffer: &Sniffer) {
+    let store = Config {
+        style: sniffer.style,
+        notifications: sniffer.notifications,
+        language: sniffer.language,
+        recording: *sniffer.recording.lock().unwrap(),
+        filters: sniffer.filters.clone(),
+        report: sniffer.report_settings.lock().unwrap().clone(),
+        capture: sniffer.capture_settings,
+        mmdb: sniffer.mmdb_settings.clone(),
+        port_mappings: sniffer.port_mappings.clone(),
+    };
+    confy::store("sniffnet", None, store).unwrap();
+}
@@ -5,7 +5,10 @@ use crate::enums::message::Message;
 use crate::enums::overlay::MyOverlay;
 use crate::structs::style_tuple::StyleTuple;
 use crate::utility::style_constants::{get_font, HEIGHT_HEADER, ICONS};
-use crate::utility::translations::{quit_analysis_translation, settings_translation};
+use crate::utility::translations::{
+    quit_analysis_translation, settings_translation, start_recording_translation,
+    stop_recording_translation,
+};
 use crate::{Language, StyleType};
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::{button, horizontal_space, Container, Row, Text, Tooltip};
@@ -17,6 +20,7 @@ pub fn get_header(
     style: StyleType,
     back_button: bool,
     all_packets: u128,
+    recording: bool,
     language: Language,
 ) -> Container<'static, Message> {
     let logo = Text::new('A'.to_string())
@@ -30,9 +34,15 @@ pub fn get_header(
             .width(Length::Fill)
             .align_items(Alignment::Center)
             .push(if back_button {
-                Container::new(get_button_reset(style, all_packets, language))
-                    .width(FillPortion(1))
-                    .align_x(Horizontal::Center)
+                Container::new(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                        .push(get_button_reset(style, all_packets, language))
+                        .push(get_button_recording(style, recording, language)),
+                )
+                .width(FillPortion(1))
+                .align_x(Horizontal::Center)
             } else {
                 Container::new(Row::new())
                     .width(FillPortion(1))
@@ -98,6 +108,48 @@ pub fn get_button_reset(
     ))
 }
 
+pub fn get_button_recording(
+    style: StyleType,
+    recording: bool,
+    language: Language,
+) -> Tooltip<'static, Message> {
+    let content = button(
+        Text::new(if recording { "STOP" } else { "REC" })
+            .font(get_font(style))
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(10)
+    .height(Length::Units(40))
+    .width(Length::Units(60))
+    .style(
+        StyleTuple(
+            style,
+            if recording {
+                ElementType::Alert
+            } else {
+                ElementType::Standard
+            },
+        )
+        .into(),
+    )
+    .on_press(Message::ToggleRecording);
+
+    Tooltip::new(
+        content,
+        if recording {
+            stop_recording_translation(language)
+        } else {
+            start_recording_translation(language)
+        },
+        Position::Right,
+    )
+    .font(get_font(style))
+    .style(<StyleTuple as Into<iced::theme::Container>>::into(
+        StyleTuple(style, ElementType::Tooltip),
+    ))
+}
+
 pub fn get_button_settings(style: StyleType, language: Language) -> Tooltip<'static, Message> {
     let content = button(
         Text::new("a")
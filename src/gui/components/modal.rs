@@ -96,7 +96,7 @@ pub fn get_clear_all_overlay(
         ))
 }
 
-fn get_modal_header(
+pub fn get_modal_header(
     style: StyleType,
     language: Language,
     title: String,
@@ -1,4 +1,7 @@
+use crate::enums::capture_source::CaptureSource;
+use crate::enums::country_sorting::CountrySorting;
 use crate::enums::element_type::ElementType;
+use crate::enums::host_sorting::HostSorting;
 use crate::enums::message::Message;
 use crate::enums::sound::Sound;
 use crate::structs::notifications::{BytesNotification, FavoriteNotification, PacketsNotification};
@@ -6,11 +9,12 @@ use crate::structs::style_tuple::StyleTuple;
 use crate::utility::countries::get_flag;
 use crate::utility::style_constants::FONT_SIZE_SUBTITLE;
 use crate::utility::translations::{
-    ip_version_translation, relevant_connections_translation, sound_translation,
+    countries_title_translation, group_by_organization_translation, ip_version_translation,
+    relevant_connections_translation, remote_hosts_translation, sound_translation,
     traffic_rate_translation, transport_protocol_translation,
 };
 use crate::{ChartType, IpVersion, Language, ReportType, StyleType, TransProtocol};
-use iced::widget::{Column, Radio, Row, Text};
+use iced::widget::{Checkbox, Column, Radio, Row, Text};
 use iced::{Alignment, Font, Length};
 use iced_native::widget::horizontal_space;
 
@@ -277,3 +281,110 @@ pub fn report_radios(
     ret_val = ret_val.push(horizontal_space(Length::Units(120)));
     ret_val
 }
+
+pub fn host_sorting_radios(
+    active: HostSorting,
+    group_by_organization: bool,
+    font: Font,
+    style: StyleType,
+    language: Language,
+) -> Row<'static, Message> {
+    let mut ret_val = Row::new()
+        .padding(15)
+        .spacing(20)
+        .align_items(Alignment::Center)
+        .push(
+            remote_hosts_translation(language)
+                .font(font)
+                .size(FONT_SIZE_SUBTITLE),
+        );
+    for option in HostSorting::ALL {
+        ret_val = ret_val.push(
+            Radio::new(
+                option,
+                option.get_radio_label(language),
+                Some(active),
+                Message::HostSortingSelection,
+            )
+            .font(font)
+            .size(15)
+            .style(<StyleTuple as Into<iced::theme::Radio>>::into(StyleTuple(
+                style,
+                ElementType::Standard,
+            ))),
+        );
+    }
+    ret_val = ret_val.push(horizontal_space(Length::Units(20))).push(
+        Checkbox::new(
+            group_by_organization_translation(language),
+            group_by_organization,
+            Message::GroupByOrganizationSelection,
+        )
+        .size(15)
+        .font(font)
+        .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+            StyleTuple(style, ElementType::Standard),
+        )),
+    );
+    ret_val
+}
+
+pub fn country_sorting_radios(
+    active: CountrySorting,
+    font: Font,
+    style: StyleType,
+    language: Language,
+) -> Row<'static, Message> {
+    let mut ret_val = Row::new()
+        .padding(15)
+        .spacing(20)
+        .align_items(Alignment::Center)
+        .push(
+            countries_title_translation(language)
+                .font(font)
+                .size(FONT_SIZE_SUBTITLE),
+        );
+    for option in CountrySorting::ALL {
+        ret_val = ret_val.push(
+            Radio::new(
+                option,
+                option.get_radio_label(language),
+                Some(active),
+                Message::CountrySortingSelection,
+            )
+            .font(font)
+            .size(15)
+            .style(<StyleTuple as Into<iced::theme::Radio>>::into(StyleTuple(
+                style,
+                ElementType::Standard,
+            ))),
+        );
+    }
+    ret_val
+}
+
+pub fn capture_source_radios(
+    active: CaptureSource,
+    font: Font,
+    style: StyleType,
+    language: Language,
+) -> Row<'static, Message> {
+    let mut ret_val = Row::new().padding([0, 13]).spacing(20);
+    for option in CaptureSource::ALL {
+        ret_val = ret_val.push(
+            Radio::new(
+                option,
+                option.get_radio_label(language),
+                Some(active),
+                Message::CaptureSourceSelection,
+            )
+            .font(font)
+            .size(15)
+            .style(<StyleTuple as Into<iced::theme::Radio>>::into(StyleTuple(
+                style,
+                ElementType::Standard,
+            ))),
+        );
+    }
+    ret_val
+}
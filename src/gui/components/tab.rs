@@ -10,7 +10,7 @@ use iced::widget::{button, horizontal_space, Button, Row, Text};
 use iced::{alignment, Alignment, Length};
 
 pub fn get_settings_tabs(
-    labels: [MyOverlay; 3],
+    labels: &[MyOverlay],
     icons: &[&str],
     actions: &[Message],
     active: MyOverlay,
@@ -35,7 +35,7 @@ pub fn get_settings_tabs(
 }
 
 pub fn get_pages_tabs(
-    labels: [RunningPage; 2],
+    labels: &[RunningPage],
     icons: &[&str],
     actions: &[Message],
     active: RunningPage,
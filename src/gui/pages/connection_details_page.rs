@@ -0,0 +1,255 @@
+//! Module defining the connection details modal.
+//!
+//! It contains all the data collected about a single connection, including the names
+//! of the remote host and the types of the exchanged ICMP messages.
+
+use iced::widget::{vertical_space, Column, Container, Row, Scrollable, Text};
+use iced::{Font, Length};
+use thousands::Separable;
+
+use crate::enums::element_type::ElementType;
+use crate::enums::message::Message;
+use crate::gui::components::modal::get_modal_header;
+use crate::structs::style_tuple::StyleTuple;
+use crate::utility::get_formatted_strings::{get_formatted_bytes_string, get_formatted_rtt_string};
+use crate::utility::icmp_types::get_icmp_type_name;
+use crate::utility::style_constants::get_font;
+use crate::utility::translations::{
+    application_protocol_translation, autonomous_system_translation, bytes_translation,
+    city_translation, connection_details_translation, connection_not_found_translation,
+    coordinates_translation, country_translation, destination_translation, domain_translation,
+    duration_translation, first_packet_translation, host_name_translation,
+    icmp_messages_translation, last_packet_translation, network_adapters_translation,
+    out_of_order_translation, packets_translation, retransmissions_translation,
+    round_trip_time_translation, server_name_translation, source_translation,
+    tcp_flags_translation, tcp_state_translation, transport_protocol_translation,
+};
+use crate::{Sniffer, TransProtocol};
+
+/// Computes the content of the modal showing the details of the connection with the given index
+pub fn connection_details_page(sniffer: &Sniffer, index: usize) -> Container<'static, Message> {
+    let style = sniffer.style;
+    let language = sniffer.language;
+    let font = get_font(style);
+
+    let mut content = Column::new()
+        .width(Length::Fill)
+        .push(get_modal_header(
+            style,
+            language,
+            connection_details_translation(language),
+        ))
+        .push(vertical_space(Length::Units(10)));
+
+    let info_traffic = sniffer.info_traffic.lock().unwrap();
+    // the connections are cleared when a new analysis is started
+    let mut col_details = Column::new().spacing(5).padding(10);
+    if let Some((key, info)) = info_traffic.map.get_index(index) {
+        let has_ports = matches!(key.trans_protocol, TransProtocol::TCP | TransProtocol::UDP);
+        let endpoint = |address: &str, port: u16| {
+            if has_ports {
+                format!("{address}  {port}")
+            } else {
+                address.to_string()
+            }
+        };
+        col_details = col_details
+            .push(detail_row(
+                source_translation(language),
+                endpoint(&key.address1, key.port1),
+                font,
+            ))
+            .push(detail_row(
+                destination_translation(language),
+                endpoint(&key.address2, key.port2),
+                font,
+            ))
+            .push(detail_row(
+                transport_protocol_translation(language),
+                key.trans_protocol.to_string(),
+                font,
+            ));
+        if has_ports {
+            col_details = col_details.push(detail_row(
+                application_protocol_translation(language),
+                format!("{} ({})", info.app_protocol, info.detection_method),
+                font,
+            ));
+        }
+        for (label, name) in [
+            (server_name_translation(language), &info.server_name),
+            (domain_translation(language), &info.domain),
+            (host_name_translation(language), &info.hostname),
+        ] {
+            if let Some(name) = name {
+                col_details = col_details.push(detail_row(label, name.clone(), font));
+            }
+        }
+        if !info.interfaces.is_empty() {
+            col_details = col_details.push(detail_row(
+                network_adapters_translation(language),
+                info.interfaces.join("\n"),
+                font,
+            ));
+        }
+        if !info.country.is_empty() {
+            col_details = col_details.push(detail_row(
+                country_translation(language),
+                info.country.clone(),
+                font,
+            ));
+        }
+        if !info.city.is_empty() {
+            col_details = col_details.push(detail_row(
+                city_translation(language),
+                info.city.clone(),
+                font,
+            ));
+        }
+        if let Some((latitude, longitude)) = info.coordinates {
+            col_details = col_details.push(detail_row(
+                coordinates_translation(language),
+                format!("{latitude:.4}, {longitude:.4}"),
+                font,
+            ));
+        }
+        if let Some(asn) = &info.asn {
+            col_details = col_details.push(detail_row(
+                autonomous_system_translation(language),
+                asn.to_string(),
+                font,
+            ));
+        }
+        col_details = col_details
+            .push(detail_row(
+                packets_translation(language),
+                format!(
+                    "{}   (-> {}   <- {})",
+                    info.transmitted_packets.separate_with_spaces(),
+                    info.sent_packets.separate_with_spaces(),
+                    info.received_packets.separate_with_spaces()
+                ),
+                font,
+            ))
+            .push(detail_row(
+                bytes_translation(language),
+                format!(
+                    "{}   (-> {}   <- {})",
+                    get_formatted_bytes_string(info.transmitted_bytes).trim(),
+                    get_formatted_bytes_string(info.sent_bytes).trim(),
+                    get_formatted_bytes_string(info.received_bytes).trim()
+                ),
+                font,
+            ))
+            .push(detail_row(
+                first_packet_translation(language),
+                info.initial_timestamp
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                font,
+            ))
+            .push(detail_row(
+                last_packet_translation(language),
+                info.final_timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                font,
+            ))
+            .push(detail_row(
+                duration_translation(language),
+                format!(
+                    "{:.3} s",
+                    (info.final_timestamp - info.initial_timestamp).num_milliseconds() as f64
+                        / 1000.0
+                ),
+                font,
+            ));
+        if let Some(rtt) = info.rtt {
+            col_details = col_details.push(detail_row(
+                round_trip_time_translation(language),
+                format!(
+                    "min {}   avg {}   max {}   ({} x)",
+                    get_formatted_rtt_string(rtt.min),
+                    get_formatted_rtt_string(rtt.avg()),
+                    get_formatted_rtt_string(rtt.max),
+                    rtt.samples
+                ),
+                font,
+            ));
+        }
+        if let Some(tcp_info) = &info.tcp_info {
+            col_details = col_details
+                .push(detail_row(
+                    tcp_state_translation(language),
+                    tcp_info.get_state().get_label(language).to_string(),
+                    font,
+                ))
+                .push(detail_row(
+                    tcp_flags_translation(language),
+                    format!(
+                        "SYN {}   SYN-ACK {}   FIN {}   RST {}",
+                        tcp_info.syn, tcp_info.syn_ack, tcp_info.fin, tcp_info.rst
+                    ),
+                    font,
+                ))
+                .push(detail_row(
+                    retransmissions_translation(language),
+                    tcp_info.retransmissions.separate_with_spaces(),
+                    font,
+                ))
+                .push(detail_row(
+                    out_of_order_translation(language),
+                    tcp_info.out_of_order.separate_with_spaces(),
+                    font,
+                ));
+        }
+        if !info.icmp_types.is_empty() {
+            // ICMPv6 messages are carried by IPv6
+            let is_icmpv6 = key.address1.contains(':');
+            let mut icmp_types: Vec<(&(u8, u8), &u128)> = info.icmp_types.iter().collect();
+            icmp_types.sort();
+            let icmp_types_string = icmp_types
+                .iter()
+                .map(|((icmp_type, code), packets)| {
+                    format!(
+                        "{} ({icmp_type}/{code}): {packets}",
+                        get_icmp_type_name(is_icmpv6, *icmp_type)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+            col_details = col_details.push(detail_row(
+                icmp_messages_translation(language),
+                icmp_types_string,
+                font,
+            ));
+        }
+    } else {
+        col_details =
+            col_details.push(Text::new(connection_not_found_translation(language)).font(font));
+    }
+    drop(info_traffic);
+
+    content = content.push(Scrollable::new(col_details).style(<StyleTuple as Into<
+        iced::theme::Scrollable,
+    >>::into(StyleTuple(
+        style,
+        ElementType::Standard,
+    ))));
+
+    Container::new(content)
+        .height(Length::Units(400))
+        .width(Length::Units(650))
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(style, ElementType::Standard),
+        ))
+}
+
+fn detail_row(label: &str, value: String, font: Font) -> Row<'static, Message> {
+    Row::new()
+        .spacing(10)
+        .push(
+            Text::new(format!("{label}:"))
+                .font(font)
+                .width(Length::Units(180)),
+        )
+        .push(Text::new(value).font(font))
+}
@@ -0,0 +1,204 @@
+//! Module defining the countries page of the application.
+//!
+//! It contains a table with the traffic exchanged with each country and a world map
+//! where the countries are drawn as circles growing with their traffic.
+
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::{button, vertical_space, Column, Container, Row, Scrollable, Text, Tooltip};
+use iced::Length::FillPortion;
+use iced::{Alignment, Font, Length};
+use iced_native::widget::tooltip::Position;
+use thousands::Separable;
+
+use crate::enums::element_type::ElementType;
+use crate::enums::message::Message;
+use crate::gui::components::radio::country_sorting_radios;
+use crate::gui::components::tab::get_pages_tabs;
+use crate::structs::country_info::CountryInfo;
+use crate::structs::style_tuple::StyleTuple;
+use crate::utility::countries::{get_flag, FLAGS_WIDTH};
+use crate::utility::get_formatted_strings::get_formatted_bytes_string;
+use crate::utility::style_constants::{get_font, FONT_SIZE_FOOTER, HEIGHT_BODY, INCONSOLATA_BOLD};
+use crate::utility::translations::{
+    country_connections_translation, no_countries_translation, world_map_translation,
+};
+use crate::{Language, RunningPage, Sniffer, StyleType};
+
+/// Computes the body of gui countries page
+pub fn countries_page(sniffer: &Sniffer) -> Container<Message> {
+    let font = get_font(sniffer.style);
+
+    let mut tab_and_body = Column::new().height(FillPortion(HEIGHT_BODY));
+
+    let tabs = get_pages_tabs(
+        &[
+            RunningPage::Overview,
+            //RunningPage::Inspect,
+            RunningPage::Hosts,
+            RunningPage::Countries,
+            RunningPage::Notifications,
+        ],
+        &["d ", "5 ", "c ", "7 "],
+        &[
+            Message::ChangeRunningPage(RunningPage::Overview),
+            // Message::ChangeRunningPage(RunningPage::Inspect),
+            Message::ChangeRunningPage(RunningPage::Hosts),
+            Message::TickInit,
+            Message::ChangeRunningPage(RunningPage::Notifications),
+        ],
+        RunningPage::Countries,
+        sniffer.style,
+        sniffer.language,
+    );
+
+    tab_and_body = tab_and_body
+        .push(tabs)
+        .push(vertical_space(Length::Units(5)));
+
+    let mut col_countries =
+        Column::new()
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .push(country_sorting_radios(
+                sniffer.country_sorting,
+                font,
+                sniffer.style,
+                sniffer.language,
+            ));
+
+    let runtime_data = sniffer.runtime_data.borrow();
+    let countries = &runtime_data.countries_vec;
+    if countries.is_empty() {
+        col_countries = col_countries.push(
+            no_countries_translation(sniffer.language)
+                .font(font)
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        );
+    } else {
+        let mut scroll_countries = Column::new();
+        for country_info in countries {
+            scroll_countries = scroll_countries.push(country_row(
+                country_info,
+                font,
+                sniffer.style,
+                sniffer.language,
+            ));
+        }
+        let col_table = Column::new()
+            .width(FillPortion(1))
+            .push(
+                Text::new(format!(
+                    "   {:^4} {:>5} {:>6} {:>11} {:>10} {:>10}",
+                    "", "Hosts", "Conn.", "Packets", "Bytes in", "Bytes out"
+                ))
+                .font(font),
+            )
+            .push(Text::new("-".repeat(58)).font(font))
+            .push(
+                Scrollable::new(scroll_countries)
+                    .height(Length::Fill)
+                    .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
+                        StyleTuple(sniffer.style, ElementType::Standard),
+                    )),
+            );
+        let col_map = Column::new()
+            .width(FillPortion(1))
+            .spacing(5)
+            .push(
+                Container::new(sniffer.world_map.view())
+                    .height(Length::Fill)
+                    .width(Length::Fill),
+            )
+            .push(
+                world_map_translation(sniffer.language)
+                    .font(font)
+                    .size(FONT_SIZE_FOOTER),
+            );
+        col_countries = col_countries.push(
+            Row::new()
+                .spacing(15)
+                .height(Length::Fill)
+                .push(col_table)
+                .push(col_map),
+        );
+    }
+
+    let body = Container::new(col_countries)
+        .padding(5)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(sniffer.style, ElementType::BorderedRound),
+        ));
+
+    tab_and_body = tab_and_body.push(Container::new(body).padding(10).height(Length::Fill));
+
+    Container::new(tab_and_body)
+        .height(FillPortion(HEIGHT_BODY))
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(sniffer.style, ElementType::Standard),
+        ))
+}
+
+/// Computes a row of the countries table
+fn country_row(
+    country_info: &CountryInfo,
+    font: Font,
+    style: StyleType,
+    language: Language,
+) -> Row<'static, Message> {
+    let mut row = Row::new().align_items(Alignment::Center);
+    row = if country_info.country.is_empty() {
+        row.push(
+            Text::new("?")
+                .width(Length::Units(FLAGS_WIDTH))
+                .font(INCONSOLATA_BOLD),
+        )
+    } else {
+        row.push(get_flag(&country_info.country))
+    };
+    let country_label = if country_info.country.is_empty() {
+        "?"
+    } else {
+        &country_info.country
+    };
+    row.push(
+        Text::new(format!(
+            " {:^4} {:>5} {:>6} {:>11} {:>10} {:>10}   ",
+            country_label,
+            country_info.hosts,
+            country_info.connections,
+            (country_info.packets_in + country_info.packets_out).separate_with_spaces(),
+            get_formatted_bytes_string(country_info.bytes_in).trim(),
+            get_formatted_bytes_string(country_info.bytes_out).trim(),
+        ))
+        .font(INCONSOLATA_BOLD),
+    )
+    .push(
+        Tooltip::new(
+            button(
+                Text::new(">")
+                    .font(font)
+                    .size(14)
+                    .horizontal_alignment(Horizontal::Center)
+                    .vertical_alignment(Vertical::Center),
+            )
+            .padding(0)
+            .height(Length::Units(16))
+            .width(Length::Units(16))
+            .style(StyleTuple(style, ElementType::Standard).into())
+            .on_press(Message::ReportCountrySelection(Some(
+                country_info.country.clone(),
+            ))),
+            country_connections_translation(language, country_label),
+            Position::Left,
+        )
+        .font(font)
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(style, ElementType::Tooltip),
+        )),
+    )
+}
@@ -0,0 +1,179 @@
+//! Module defining the hosts page of the application.
+//!
+//! It contains a table with the traffic exchanged with each remote host, aggregated over all its connections.
+
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::scrollable::Properties;
+use iced::widget::{vertical_space, Column, Container, Row, Scrollable, Text};
+use iced::Length::FillPortion;
+use iced::{Alignment, Length};
+use thousands::Separable;
+
+use crate::enums::element_type::ElementType;
+use crate::enums::message::Message;
+use crate::gui::components::radio::host_sorting_radios;
+use crate::gui::components::tab::get_pages_tabs;
+use crate::structs::host_info::HostInfo;
+use crate::structs::style_tuple::StyleTuple;
+use crate::utility::countries::{get_flag, FLAGS_WIDTH};
+use crate::utility::get_formatted_strings::get_formatted_bytes_string;
+use crate::utility::style_constants::{get_font, FONT_SIZE_FOOTER, HEIGHT_BODY, INCONSOLATA_BOLD};
+use crate::utility::translations::{no_hosts_translation, shown_hosts_translation};
+use crate::{RunningPage, Sniffer};
+
+/// Maximum number of hosts displayed in the table
+const MAX_SHOWN_HOSTS: usize = 100;
+
+/// Computes the body of gui hosts page
+pub fn hosts_page(sniffer: &Sniffer) -> Container<Message> {
+    let font = get_font(sniffer.style);
+
+    let mut tab_and_body = Column::new().height(FillPortion(HEIGHT_BODY));
+
+    let tabs = get_pages_tabs(
+        &[
+            RunningPage::Overview,
+            //RunningPage::Inspect,
+            RunningPage::Hosts,
+            RunningPage::Countries,
+            RunningPage::Notifications,
+        ],
+        &["d ", "5 ", "c ", "7 "],
+        &[
+            Message::ChangeRunningPage(RunningPage::Overview),
+            // Message::ChangeRunningPage(RunningPage::Inspect),
+            Message::TickInit,
+            Message::ChangeRunningPage(RunningPage::Countries),
+            Message::ChangeRunningPage(RunningPage::Notifications),
+        ],
+        RunningPage::Hosts,
+        sniffer.style,
+        sniffer.language,
+    );
+
+    tab_and_body = tab_and_body
+        .push(tabs)
+        .push(vertical_space(Length::Units(5)));
+
+    let mut col_hosts =
+        Column::new()
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .push(host_sorting_radios(
+                sniffer.host_sorting,
+                sniffer.group_by_organization,
+                font,
+                sniffer.style,
+                sniffer.language,
+            ));
+
+    let runtime_data = sniffer.runtime_data.borrow();
+    let hosts = &runtime_data.hosts_vec;
+    if hosts.is_empty() {
+        col_hosts = col_hosts.push(
+            no_hosts_translation(sniffer.language)
+                .font(font)
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        );
+    } else {
+        col_hosts = col_hosts
+            .push(
+                Text::new(format!(
+                    "{:^39} {:>5} {:>5} {:>11} {:>11} {:>10} {:>10}  {:^10}  {:^10}  Country",
+                    "IP address",
+                    "Conn.",
+                    "Ports",
+                    "Packets in",
+                    "Packets out",
+                    "Bytes in",
+                    "Bytes out",
+                    "First seen",
+                    "Last seen"
+                ))
+                .font(font),
+            )
+            .push(Text::new("-".repeat(140)).font(font));
+        let mut scroll_hosts = Column::new();
+        for host_info in hosts.iter().take(MAX_SHOWN_HOSTS) {
+            scroll_hosts = scroll_hosts.push(host_row(host_info));
+        }
+        col_hosts = col_hosts.push(
+            Scrollable::new(scroll_hosts)
+                .height(Length::Fill)
+                .horizontal_scroll(Properties::new())
+                .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
+                    StyleTuple(sniffer.style, ElementType::Standard),
+                )),
+        );
+        if hosts.len() > MAX_SHOWN_HOSTS {
+            col_hosts = col_hosts.push(
+                Text::new(shown_hosts_translation(
+                    sniffer.language,
+                    MAX_SHOWN_HOSTS,
+                    hosts.len(),
+                ))
+                .font(font)
+                .size(FONT_SIZE_FOOTER),
+            );
+        }
+    }
+
+    let body = Container::new(col_hosts)
+        .padding(5)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(sniffer.style, ElementType::BorderedRound),
+        ));
+
+    tab_and_body = tab_and_body.push(Container::new(body).padding(10).height(Length::Fill));
+
+    Container::new(tab_and_body)
+        .height(FillPortion(HEIGHT_BODY))
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(sniffer.style, ElementType::Standard),
+        ))
+}
+
+/// Computes a row of the hosts table
+fn host_row(host_info: &HostInfo) -> Row<'static, Message> {
+    let mut row = Row::new().align_items(Alignment::Center).push(
+        Text::new(format!(
+            "{:^39} {:>5} {:>5} {:>11} {:>11} {:>10} {:>10}  {:^10}  {:^10}  ",
+            host_info.address,
+            host_info.connections,
+            host_info.ports.len(),
+            host_info.packets_in.separate_with_spaces(),
+            host_info.packets_out.separate_with_spaces(),
+            get_formatted_bytes_string(host_info.bytes_in).trim(),
+            get_formatted_bytes_string(host_info.bytes_out).trim(),
+            host_info.first_seen.format("%H:%M:%S").to_string(),
+            host_info.last_seen.format("%H:%M:%S").to_string(),
+        ))
+        .font(INCONSOLATA_BOLD),
+    );
+    row = if host_info.country.is_empty() {
+        row.push(
+            Text::new("?")
+                .width(Length::Units(FLAGS_WIDTH))
+                .font(INCONSOLATA_BOLD),
+        )
+    } else {
+        row.push(get_flag(&host_info.country))
+    };
+    let organization = match &host_info.asn {
+        Some(asn) => format!("   ({asn})"),
+        None => String::new(),
+    };
+    row.push(
+        Text::new(format!(
+            "   {}{}",
+            host_info.name.as_deref().unwrap_or(""),
+            organization
+        ))
+        .font(INCONSOLATA_BOLD),
+    )
+}
@@ -3,25 +3,32 @@
 //! It contains elements to select network adapter and traffic filters.
 
 use iced::widget::{
-    button, horizontal_space, vertical_space, Column, Container, PickList, Radio, Row, Scrollable,
-    Text, Tooltip,
+    button, horizontal_space, vertical_space, Checkbox, Column, Container, PickList, Row,
+    Scrollable, Text, TextInput, Tooltip,
 };
 use iced::Length::FillPortion;
 use iced::{alignment, Alignment, Font, Length};
 use iced_native::widget::tooltip::Position;
 use pcap::Device;
 
+use crate::enums::capture_source::CaptureSource;
 use crate::enums::element_type::ElementType;
 use crate::enums::message::Message;
-use crate::gui::components::radio::{ip_version_radios, transport_protocol_radios};
+use crate::enums::traffic_type::TrafficType;
+use crate::gui::components::radio::{
+    capture_source_radios, ip_version_radios, transport_protocol_radios,
+};
 use crate::structs::sniffer::Sniffer;
 use crate::structs::style_tuple::StyleTuple;
 use crate::utility::style_constants::{
     get_font, FONT_SIZE_SUBTITLE, FONT_SIZE_TITLE, HEIGHT_BODY, ICONS,
 };
 use crate::utility::translations::{
-    address_translation, addresses_translation, application_protocol_translation,
-    choose_adapters_translation, select_filters_translation, start_translation,
+    address_translation, addresses_filter_translation, addresses_translation,
+    application_protocol_translation, bpf_filter_translation, capture_file_path_translation,
+    choose_adapters_translation, countries_filter_translation, organizations_filter_translation,
+    ports_filter_translation, select_filters_translation, server_names_filter_translation,
+    start_translation, traffic_type_filter_translation,
 };
 use crate::{AppProtocol, Language, StyleType};
 
@@ -47,12 +54,23 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message> {
         .width(FillPortion(2))
         .push(col_transport_radio)
         .push(vertical_space(FillPortion(2)))
-        .push(get_button_start(sniffer.style, sniffer.language))
+        .push(get_button_start(
+            sniffer.style,
+            sniffer.language,
+            sniffer.bpf_error.as_deref(),
+        ))
         .push(vertical_space(FillPortion(1)));
 
-    let app_active = sniffer.filters.application;
+    let app_active = sniffer.filters.application.clone();
+    // the protocols defined by the user are selectable as well
+    let mut app_protocols = AppProtocol::ALL.to_vec();
+    for port_mapping in &sniffer.port_mappings {
+        if !app_protocols.contains(&port_mapping.app_protocol) {
+            app_protocols.push(port_mapping.app_protocol.clone());
+        }
+    }
     let picklist_app = PickList::new(
-        &AppProtocol::ALL[..],
+        app_protocols,
         Some(app_active),
         Message::AppProtocolSelection,
     )
@@ -70,6 +88,61 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message> {
         )
         .push(picklist_app);
 
+    let row_bpf = Row::new()
+        .spacing(10)
+        .push(get_col_text_filter(
+            bpf_filter_translation(sniffer.language),
+            "net 192.168.1.0/24 and port 443",
+            &sniffer.filters.bpf,
+            Message::BpfFilterSelection,
+            sniffer.style,
+        ))
+        .push(get_col_text_filter(
+            server_names_filter_translation(sniffer.language),
+            "google, github.com",
+            &sniffer.filters.server_names,
+            Message::ServerNamesFilterSelection,
+            sniffer.style,
+        ))
+        .push(get_col_text_filter(
+            organizations_filter_translation(sniffer.language),
+            "AS13335, amazon",
+            &sniffer.filters.organizations,
+            Message::OrganizationsFilterSelection,
+            sniffer.style,
+        ));
+
+    let row_text_filters = Row::new()
+        .spacing(10)
+        .push(get_col_text_filter(
+            addresses_filter_translation(sniffer.language),
+            "10.0.0.0/8, fe80::/10",
+            &sniffer.filters.addresses,
+            Message::AddressesFilterSelection,
+            sniffer.style,
+        ))
+        .push(get_col_text_filter(
+            ports_filter_translation(sniffer.language),
+            "53, 8000-8100",
+            &sniffer.filters.ports,
+            Message::PortsFilterSelection,
+            sniffer.style,
+        ))
+        .push(get_col_text_filter(
+            countries_filter_translation(sniffer.language),
+            "IT, US",
+            &sniffer.filters.countries,
+            Message::CountriesFilterSelection,
+            sniffer.style,
+        ));
+
+    let col_traffic_types = traffic_type_checkboxes(
+        &sniffer.filters.traffic_types,
+        font,
+        sniffer.style,
+        sniffer.language,
+    );
+
     let filters = Column::new()
         .width(FillPortion(6))
         .padding(10)
@@ -88,6 +161,14 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message> {
                 .push(col_ip)
                 .push(col_transport)
                 .push(col_app),
+        )
+        .push(
+            Column::new()
+                .spacing(10)
+                .height(FillPortion(3))
+                .push(row_bpf)
+                .push(row_text_filters)
+                .push(col_traffic_types),
         );
 
     let body = Column::new().push(vertical_space(Length::Units(5))).push(
@@ -104,8 +185,13 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message> {
         ))
 }
 
-pub fn get_button_start(style: StyleType, language: Language) -> Tooltip<'static, Message> {
-    let content = button(
+/// The button is disabled (and its tooltip shows the error) if the BPF expression is invalid
+pub fn get_button_start(
+    style: StyleType,
+    language: Language,
+    bpf_error: Option<&str>,
+) -> Tooltip<'static, Message> {
+    let mut content = button(
         Text::new("S")
             .font(ICONS)
             .size(FONT_SIZE_TITLE)
@@ -115,10 +201,13 @@ pub fn get_button_start(style: StyleType, language: Language) -> Tooltip<'static
     .padding(10)
     .height(Length::Units(80))
     .width(Length::Units(160))
-    .style(StyleTuple(style, ElementType::Standard).into())
-    .on_press(Message::Start);
+    .style(StyleTuple(style, ElementType::Standard).into());
+    if bpf_error.is_none() {
+        content = content.on_press(Message::Start);
+    }
 
-    Tooltip::new(content, start_translation(language), Position::Top)
+    let tooltip = bpf_error.map_or_else(|| start_translation(language).to_string(), str::to_string);
+    Tooltip::new(content, tooltip, Position::Top)
         .gap(5)
         .font(get_font(style))
         .style(<StyleTuple as Into<iced::theme::Container>>::into(
@@ -126,7 +215,93 @@ pub fn get_button_start(style: StyleType, language: Language) -> Tooltip<'static
         ))
 }
 
+fn traffic_type_checkboxes(
+    active: &[TrafficType],
+    font: Font,
+    style: StyleType,
+    language: Language,
+) -> Column<'static, Message> {
+    let mut row_checkboxes = Row::new().spacing(20);
+    for option in TrafficType::ALL {
+        row_checkboxes = row_checkboxes.push(
+            Checkbox::new(
+                option.get_radio_label(language),
+                active.contains(&option),
+                move |selected| Message::TrafficTypeSelection(option, selected),
+            )
+            .size(15)
+            .font(font)
+            .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+                StyleTuple(style, ElementType::Standard),
+            )),
+        );
+    }
+    Column::new()
+        .spacing(5)
+        .push(Text::new(traffic_type_filter_translation(language)).font(font))
+        .push(row_checkboxes)
+}
+
+fn get_col_text_filter(
+    label: &'static str,
+    placeholder: &str,
+    value: &str,
+    on_change: fn(String) -> Message,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+    Column::new()
+        .spacing(5)
+        .width(FillPortion(1))
+        .push(Text::new(label).font(font))
+        .push(
+            TextInput::new(placeholder, value, on_change)
+                .on_submit(Message::Start)
+                .padding(5)
+                .font(font)
+                .style(<StyleTuple as Into<iced::theme::TextInput>>::into(
+                    StyleTuple(style, ElementType::Standard),
+                )),
+        )
+}
+
 fn get_col_adapter(sniffer: &Sniffer, font: Font) -> Column<Message> {
+    let col_adapter = Column::new()
+        .padding(10)
+        .spacing(5)
+        .height(Length::Fill)
+        .width(FillPortion(4))
+        .push(
+            choose_adapters_translation(sniffer.language)
+                .font(font)
+                .size(FONT_SIZE_TITLE),
+        )
+        .push(capture_source_radios(
+            sniffer.capture_source,
+            font,
+            sniffer.style,
+            sniffer.language,
+        ));
+
+    if sniffer.capture_source.eq(&CaptureSource::File) {
+        return col_adapter.push(
+            Container::new(
+                TextInput::new(
+                    capture_file_path_translation(sniffer.language),
+                    &sniffer.capture_file,
+                    Message::CaptureFileSelection,
+                )
+                .on_submit(Message::Start)
+                .padding(5)
+                .font(font)
+                .style(<StyleTuple as Into<iced::theme::TextInput>>::into(
+                    StyleTuple(sniffer.style, ElementType::Standard),
+                )),
+            )
+            .padding(13),
+        );
+    }
+
     let mut dev_str_list = vec![];
     for dev in Device::list().expect("Error retrieving device list\r\n") {
         let mut dev_str = "\n".to_string();
@@ -160,46 +335,37 @@ fn get_col_adapter(sniffer: &Sniffer, font: Font) -> Column<Message> {
         dev_str_list.push((name, dev_str));
     }
 
-    Column::new()
-        .padding(10)
-        .spacing(5)
-        .height(Length::Fill)
-        .width(FillPortion(4))
-        .push(
-            choose_adapters_translation(sniffer.language)
-                .font(font)
-                .size(FONT_SIZE_TITLE),
-        )
-        .push(
-            Scrollable::new(dev_str_list.iter().fold(
-                Column::new().padding(13).spacing(5),
-                |scroll_adapters, adapter| {
-                    let name = &adapter.0;
-                    scroll_adapters.push(
-                        Container::new(
-                            Radio::new(name, &adapter.1, Some(&sniffer.device.name), |name| {
-                                Message::AdapterSelection(name.to_string())
-                            })
-                            .font(font)
-                            .size(15)
-                            .width(Length::Fill)
-                            .style(<StyleTuple as Into<
-                                iced::theme::Radio,
-                            >>::into(
-                                StyleTuple(sniffer.style, ElementType::Standard),
-                            )),
+    col_adapter.push(
+        Scrollable::new(dev_str_list.iter().fold(
+            Column::new().padding(13).spacing(5),
+            |scroll_adapters, adapter| {
+                let name = adapter.0.clone();
+                scroll_adapters.push(
+                    Container::new(
+                        // more adapters can be sniffed at once
+                        Checkbox::new(
+                            &adapter.1,
+                            sniffer.devices.iter().any(|dev| dev.name.eq(&name)),
+                            move |selected| Message::AdapterSelection(name.clone(), selected),
                         )
-                        .padding(10)
+                        .font(font)
+                        .size(15)
+                        .width(Length::Fill)
                         .style(<StyleTuple as Into<
-                            iced::theme::Container,
+                            iced::theme::Checkbox,
                         >>::into(
-                            StyleTuple(sniffer.style, ElementType::BorderedRound),
+                            StyleTuple(sniffer.style, ElementType::Standard),
                         )),
                     )
-                },
-            ))
-            .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
-                StyleTuple(sniffer.style, ElementType::Standard),
-            )),
-        )
+                    .padding(10)
+                    .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                        StyleTuple(sniffer.style, ElementType::BorderedRound),
+                    )),
+                )
+            },
+        ))
+        .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
+            StyleTuple(sniffer.style, ElementType::Standard),
+        )),
+    )
 }
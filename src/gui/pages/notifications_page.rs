@@ -41,15 +41,19 @@ pub fn notifications_page(sniffer: &Sniffer) -> Container<Message> {
         .height(FillPortion(HEIGHT_BODY));
 
     let tabs = get_pages_tabs(
-        [
+        &[
             RunningPage::Overview,
             //RunningPage::Inspect,
+            RunningPage::Hosts,
+            RunningPage::Countries,
             RunningPage::Notifications,
         ],
-        &["d ", "7 "],
+        &["d ", "5 ", "c ", "7 "],
         &[
             Message::ChangeRunningPage(RunningPage::Overview),
             // Message::ChangeRunningPage(RunningPage::Inspect),
+            Message::ChangeRunningPage(RunningPage::Hosts),
+            Message::ChangeRunningPage(RunningPage::Countries),
             Message::TickInit,
         ],
         RunningPage::Notifications,
@@ -129,7 +133,7 @@ pub fn notifications_page(sniffer: &Sniffer) -> Container<Message> {
                 }
                 LoggedNotification::FavoriteTransmitted(favorite_transmitted) => {
                     favorite_notification_log(
-                        favorite_transmitted.clone(),
+                        (**favorite_transmitted).clone(),
                         sniffer.language,
                         sniffer.style,
                     )
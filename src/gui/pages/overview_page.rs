@@ -5,17 +5,22 @@
 
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::scrollable::Properties;
-use iced::widget::{button, vertical_space, Column, Container, Row, Scrollable, Text, Tooltip};
+use iced::widget::{
+    button, vertical_space, Column, Container, PickList, Row, Scrollable, Text, TextInput, Tooltip,
+};
 use iced::Length::FillPortion;
 use iced::{alignment, Alignment, Length};
 use iced_native::widget::tooltip::Position;
 use thousands::Separable;
-//use dns_lookup::lookup_addr;
 
+use crate::enums::capture_source::CaptureSource;
 use crate::enums::element_type::ElementType;
 use crate::enums::message::Message;
+use crate::enums::overlay::MyOverlay;
+use crate::enums::report_column::ReportColumn;
 use crate::gui::components::radio::{chart_radios, report_radios};
 use crate::gui::components::tab::get_pages_tabs;
+use crate::structs::report_table::get_pages_number;
 use crate::structs::sniffer::Sniffer;
 use crate::structs::style_tuple::StyleTuple;
 use crate::utility::countries::{get_flag, FLAGS_WIDTH};
@@ -25,9 +30,12 @@ use crate::utility::get_formatted_strings::{
 };
 use crate::utility::style_constants::{get_font, HEIGHT_BODY, ICONS, INCONSOLATA_BOLD};
 use crate::utility::translations::{
+    all_translation, connections_page_translation, country_connections_translation,
     error_translation, filtered_application_translation, filtered_bytes_translation,
     filtered_packets_translation, no_addresses_translation, no_favorites_translation,
-    open_report_translation, some_observed_translation, waiting_translation,
+    no_search_results_translation, open_report_translation, report_error_translation,
+    search_connections_translation, show_details_translation, some_observed_translation,
+    waiting_file_translation, waiting_translation,
 };
 use crate::{AppProtocol, ReportType, RunningPage};
 
@@ -47,15 +55,19 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
         // NO pcap error detected
 
         let tabs = get_pages_tabs(
-            [
+            &[
                 RunningPage::Overview,
                 //RunningPage::Inspect,
+                RunningPage::Hosts,
+                RunningPage::Countries,
                 RunningPage::Notifications,
             ],
-            &["d ", "7 "],
+            &["d ", "5 ", "c ", "7 "],
             &[
                 Message::TickInit,
                 //Message::ChangeRunningPage(RunningPage::Inspect),
+                Message::ChangeRunningPage(RunningPage::Hosts),
+                Message::ChangeRunningPage(RunningPage::Countries),
                 Message::ChangeRunningPage(RunningPage::Notifications),
             ],
             RunningPage::Overview,
@@ -71,29 +83,53 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
             + sniffer.runtime_data.borrow().tot_received_bytes;
         let app_protocols = sniffer.runtime_data.borrow().app_protocols.clone();
         let filtered_bytes_string = get_formatted_bytes_string(filtered_bytes);
+        // the counting mode of the running analysis, which may differ from the one in the settings
+        let info_traffic = sniffer.info_traffic.lock().unwrap();
+        let byte_counting = info_traffic.byte_counting;
+        // the report button tells why the report was interrupted
+        let report_tooltip = match &info_traffic.report_error {
+            None => open_report_translation(sniffer.language).to_string(),
+            Some(error) => report_error_translation(sniffer.language, error),
+        };
+        drop(info_traffic);
 
         match (observed, filtered) {
             (0, 0) => {
                 //no packets observed at all
 
-                let adapter_name = sniffer.device.name.clone();
-                let (icon_text, nothing_to_see_text) = if sniffer.device.addresses.is_empty() {
-                    (
-                        Text::new('T'.to_string()).font(ICONS).size(60),
-                        no_addresses_translation(sniffer.language, &adapter_name)
-                            .horizontal_alignment(Horizontal::Center)
-                            .font(font),
-                    )
-                } else {
-                    (
-                        Text::new(sniffer.waiting.len().to_string())
-                            .font(ICONS)
-                            .size(60),
-                        waiting_translation(sniffer.language, &adapter_name)
-                            .horizontal_alignment(Horizontal::Center)
-                            .font(font),
-                    )
-                };
+                let adapter_name = sniffer
+                    .devices
+                    .iter()
+                    .map(|dev| dev.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                let (icon_text, nothing_to_see_text) =
+                    if sniffer.capture_source.eq(&CaptureSource::File) {
+                        (
+                            Text::new(sniffer.waiting.len().to_string())
+                                .font(ICONS)
+                                .size(60),
+                            waiting_file_translation(sniffer.language, &sniffer.capture_file)
+                                .horizontal_alignment(Horizontal::Center)
+                                .font(font),
+                        )
+                    } else if sniffer.devices.iter().all(|dev| dev.addresses.is_empty()) {
+                        (
+                            Text::new('T'.to_string()).font(ICONS).size(60),
+                            no_addresses_translation(sniffer.language, &adapter_name)
+                                .horizontal_alignment(Horizontal::Center)
+                                .font(font),
+                        )
+                    } else {
+                        (
+                            Text::new(sniffer.waiting.len().to_string())
+                                .font(ICONS)
+                                .size(60),
+                            waiting_translation(sniffer.language, &adapter_name)
+                                .horizontal_alignment(Horizontal::Center)
+                                .font(font),
+                        )
+                    };
                 body = body
                     .push(vertical_space(FillPortion(1)))
                     .push(icon_text)
@@ -170,6 +206,7 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
                             sniffer.language,
                             &filtered_bytes_string,
                             &get_percentage_string(observed_bytes, filtered_bytes),
+                            byte_counting.get_radio_label(sniffer.language),
                         )
                         .font(font),
                     );
@@ -191,19 +228,90 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
                 }
 
                 let active_radio_report = sniffer.report_type;
-                let row_radio_report =
-                    report_radios(active_radio_report, font, sniffer.style, sniffer.language);
+                let mut row_radio_report =
+                    report_radios(active_radio_report, font, sniffer.style, sniffer.language).push(
+                        TextInput::new(
+                            search_connections_translation(sniffer.language),
+                            &sniffer.report_table.search,
+                            Message::ReportSearch,
+                        )
+                        .padding(5)
+                        .font(font)
+                        .width(Length::Units(350))
+                        .style(<StyleTuple as Into<
+                            iced::theme::TextInput,
+                        >>::into(
+                            StyleTuple(sniffer.style, ElementType::Standard),
+                        )),
+                    );
+                if sniffer.capture_source.eq(&CaptureSource::Adapter) && sniffer.devices.len() > 1 {
+                    // the connections can be filtered by the adapter on which they were seen
+                    let all_interfaces = all_translation(sniffer.language).to_string();
+                    let mut interfaces = vec![all_interfaces.clone()];
+                    interfaces.extend(sniffer.devices.iter().map(|dev| dev.name.clone()));
+                    row_radio_report = row_radio_report.push(
+                        PickList::new(
+                            interfaces,
+                            Some(sniffer.report_interface.clone().unwrap_or(all_interfaces)),
+                            Message::ReportInterfaceSelection,
+                        )
+                        .font(font)
+                        .width(Length::Units(150))
+                        .style(<StyleTuple as Into<
+                            iced::theme::PickList,
+                        >>::into(
+                            StyleTuple(sniffer.style, ElementType::Standard),
+                        )),
+                    );
+                }
+
+                if let Some(country) = &sniffer.report_country {
+                    // the connections are filtered by the country selected in the countries page
+                    let country_label = if country.is_empty() { "?" } else { country };
+                    row_radio_report = row_radio_report.push(
+                        Row::new()
+                            .spacing(5)
+                            .align_items(Alignment::Center)
+                            .push(
+                                Text::new(country_connections_translation(
+                                    sniffer.language,
+                                    country_label,
+                                ))
+                                .font(font),
+                            )
+                            .push(
+                                button(
+                                    Text::new("x")
+                                        .font(font)
+                                        .size(14)
+                                        .horizontal_alignment(Horizontal::Center)
+                                        .vertical_alignment(Vertical::Center),
+                                )
+                                .padding(0)
+                                .height(Length::Units(16))
+                                .width(Length::Units(16))
+                                .style(StyleTuple(sniffer.style, ElementType::Standard).into())
+                                .on_press(Message::ReportCountrySelection(None)),
+                            ),
+                    );
+                }
 
                 let mut col_report = Column::new()
                     .height(Length::Fill)
                     .width(Length::Fill)
                     .push(row_radio_report);
 
-                if sniffer.report_type.eq(&ReportType::Favorites)
-                    && sniffer.runtime_data.borrow().report_vec.is_empty()
-                {
-                    col_report = col_report.push(
+                let runtime_data = sniffer.runtime_data.borrow();
+                if runtime_data.report_vec.is_empty() {
+                    let empty_report_text = if sniffer.report_type.eq(&ReportType::Favorites)
+                        && sniffer.report_table.search.trim().is_empty()
+                    {
                         no_favorites_translation(sniffer.language)
+                    } else {
+                        no_search_results_translation(sniffer.language)
+                    };
+                    col_report = col_report.push(
+                        empty_report_text
                             .font(font)
                             .height(Length::Fill)
                             .width(Length::Fill)
@@ -211,38 +319,37 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
                             .vertical_alignment(Vertical::Center),
                     );
                 } else {
-                    col_report = col_report
-                        .push(Text::new("     Src IP address       Src port      Dst IP address       Dst port  Layer4   Layer7     Packets      Bytes   Country").font(font))
-                        .push(Text::new("------------------------------------------------------------------------------------------------------------------------").font(font))
-                    ;
-                    let mut scroll_report = Column::new();
-                    for key_val in &sniffer.runtime_data.borrow().report_vec {
-                        let entry_color =
-                            get_connection_color(key_val.1.traffic_type, sniffer.style);
+                    // the address columns are widened only if needed by the displayed connections
+                    let long_addresses = runtime_data
+                        .report_vec
+                        .iter()
+                        .any(|(_, info)| info.very_long_address);
+                    let table_width: usize = ReportColumn::ALL
+                        .iter()
+                        .map(|column| column.get_width(long_addresses) + 1)
+                        .sum();
+                    let mut scroll_report = Column::new()
+                        .push(get_report_header(sniffer, long_addresses))
+                        .push(Text::new("-".repeat(table_width)).font(font));
+                    for (key, info) in &runtime_data.report_vec {
+                        let entry_color = get_connection_color(info.traffic_type, sniffer.style);
+                        let cells: Vec<String> = ReportColumn::ALL
+                            .iter()
+                            .map(|column| column.get_cell(key, info, long_addresses))
+                            .collect();
                         let mut entry_row = Row::new().align_items(Alignment::Center).push(
-                            Text::new(format!(
-                                "{}{}",
-                                key_val.0.print_gui(),
-                                key_val.1.print_gui()
-                            ))
-                            .style(iced::theme::Text::Color(entry_color))
-                            .font(INCONSOLATA_BOLD),
+                            Text::new(cells.join(" "))
+                                .style(iced::theme::Text::Color(entry_color))
+                                .font(INCONSOLATA_BOLD),
                         );
-                        if key_val.1.country.is_empty() {
-                            entry_row = entry_row
-                                .push(
-                                    Text::new("?")
-                                        .width(Length::Units(FLAGS_WIDTH))
-                                        .style(iced::theme::Text::Color(entry_color))
-                                        .font(INCONSOLATA_BOLD),
-                                )
-                                .push(Text::new("    "));
+                        // unknown countries leave an empty space in place of the flag
+                        entry_row = if info.country.is_empty() {
+                            entry_row.push(Text::new("").width(Length::Units(FLAGS_WIDTH)))
                         } else {
-                            entry_row = entry_row
-                                .push(get_flag(&key_val.1.country))
-                                .push(Text::new("  "));
-                        }
+                            entry_row.push(get_flag(&info.country))
+                        };
                         entry_row = entry_row
+                            .push(Text::new("  ").font(font))
                             .push(
                                 button(
                                     Text::new('X'.to_string())
@@ -257,7 +364,7 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
                                 .style(
                                     StyleTuple(
                                         sniffer.style,
-                                        if key_val.1.is_favorite {
+                                        if info.is_favorite {
                                             ElementType::Starred
                                         } else {
                                             ElementType::NotStarred
@@ -265,24 +372,72 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
                                     )
                                     .into(),
                                 )
-                                .on_press(
-                                    if key_val.1.is_favorite {
-                                        Message::UnSaveConnection(key_val.1.index)
-                                    } else {
-                                        Message::SaveConnection(key_val.1.index)
-                                    },
-                                ),
+                                .on_press(if info.is_favorite {
+                                    Message::UnSaveConnection(info.index)
+                                } else {
+                                    Message::SaveConnection(info.index)
+                                }),
+                            )
+                            .push(Text::new(" ").font(font))
+                            .push(
+                                Tooltip::new(
+                                    button(
+                                        Text::new("i")
+                                            .font(font)
+                                            .size(14)
+                                            .horizontal_alignment(Horizontal::Center)
+                                            .vertical_alignment(Vertical::Center),
+                                    )
+                                    .padding(0)
+                                    .height(Length::Units(16))
+                                    .width(Length::Units(16))
+                                    .style(StyleTuple(sniffer.style, ElementType::Standard).into())
+                                    .on_press(
+                                        Message::ShowModal(MyOverlay::ConnectionDetails(
+                                            info.index,
+                                        )),
+                                    ),
+                                    show_details_translation(sniffer.language),
+                                    Position::Right,
+                                )
+                                .font(font)
+                                .style(<StyleTuple as Into<
+                                    iced::theme::Container,
+                                >>::into(
+                                    StyleTuple(sniffer.style, ElementType::Tooltip),
+                                )),
                             )
                             .push(Text::new("   ").font(font));
-                        scroll_report = scroll_report.push(entry_row);
-                    }
-                    col_report = col_report.push(
-                        Scrollable::new(scroll_report)
-                            .horizontal_scroll(Properties::new())
-                            .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
-                                StyleTuple(sniffer.style, ElementType::Standard),
+                        if let Some(remote_name) = info.get_remote_name() {
+                            entry_row = entry_row.push(
+                                Text::new(remote_name.to_string())
+                                    .style(iced::theme::Text::Color(entry_color))
+                                    .font(INCONSOLATA_BOLD),
+                            );
+                        }
+                        // the connection selected with the keyboard is highlighted
+                        let element_type = if sniffer.report_table.selected.eq(&Some(info.index)) {
+                            ElementType::BorderedRound
+                        } else {
+                            ElementType::Standard
+                        };
+                        scroll_report = scroll_report.push(Container::new(entry_row).style(
+                            <StyleTuple as Into<iced::theme::Container>>::into(StyleTuple(
+                                sniffer.style,
+                                element_type,
                             )),
-                    );
+                        ));
+                    }
+                    col_report = col_report
+                        .push(
+                            Scrollable::new(scroll_report)
+                                .height(Length::Fill)
+                                .horizontal_scroll(Properties::new())
+                                .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
+                                    StyleTuple(sniffer.style, ElementType::Standard),
+                                )),
+                        )
+                        .push(get_report_pages(sniffer, runtime_data.report_len));
                 };
 
                 let row_report = Row::new().push(
@@ -334,7 +489,7 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
                                             StyleTuple(sniffer.style, ElementType::Standard).into(),
                                         )
                                         .on_press(Message::OpenReport),
-                                        open_report_translation(sniffer.language),
+                                        report_tooltip,
                                         Position::Top,
                                     )
                                     .gap(5)
@@ -374,3 +529,65 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message> {
             StyleTuple(sniffer.style, ElementType::Standard),
         ))
 }
+
+/// Computes the header of the connections table, made of buttons to sort it by each column
+fn get_report_header(sniffer: &Sniffer, long_addresses: bool) -> Row<'static, Message> {
+    let mut header = Row::new();
+    for column in ReportColumn::ALL {
+        let descending = sniffer
+            .report_table
+            .sort_column
+            .eq(&column)
+            .then_some(sniffer.report_table.descending);
+        header = header
+            .push(
+                button(
+                    Text::new(column.get_header(descending, long_addresses)).font(INCONSOLATA_BOLD),
+                )
+                .padding(0)
+                .style(StyleTuple(sniffer.style, ElementType::Standard).into())
+                .on_press(Message::ReportSortSelection(column)),
+            )
+            .push(Text::new(" ").font(INCONSOLATA_BOLD));
+    }
+    header
+}
+
+/// Computes the buttons to move between the pages of the connections table
+fn get_report_pages(sniffer: &Sniffer, connections: usize) -> Row<'static, Message> {
+    let font = get_font(sniffer.style);
+    let page = sniffer.report_table.page;
+    let pages = get_pages_number(connections);
+    let page_button = |label: &str, target: Option<usize>| {
+        let mut page_button = button(
+            Text::new(label.to_string())
+                .font(font)
+                .size(14)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .padding(0)
+        .height(Length::Units(20))
+        .width(Length::Units(20))
+        .style(StyleTuple(sniffer.style, ElementType::Standard).into());
+        if let Some(target) = target {
+            page_button = page_button.on_press(Message::ReportPageSelection(target));
+        }
+        page_button
+    };
+    Row::new()
+        .padding(5)
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(page_button("<", page.checked_sub(1)))
+        .push(
+            Text::new(connections_page_translation(
+                sniffer.language,
+                page + 1,
+                pages,
+                connections,
+            ))
+            .font(font),
+        )
+        .push(page_button(">", (page + 1 < pages).then_some(page + 1)))
+}
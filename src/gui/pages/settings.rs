@@ -1,3 +1,4 @@
+use crate::enums::byte_counting::ByteCounting;
 use crate::enums::byte_multiple::{from_char_to_multiple, ByteMultiple};
 use crate::enums::element_type::ElementType;
 use crate::enums::message::Message;
@@ -7,25 +8,40 @@ use crate::gui::components::radio::{
     sound_packets_threshold_radios,
 };
 use crate::gui::components::tab::get_settings_tabs;
+use crate::structs::capture_settings::{CaptureSettings, MAX_SNAPLEN, MIN_SNAPLEN};
+use crate::structs::mmdb_settings::MmdbSettings;
 use crate::structs::notifications::{BytesNotification, FavoriteNotification, PacketsNotification};
+use crate::structs::port_mapping::{PortMapping, PortMappingDraft};
+use crate::structs::recording::Recording;
+use crate::structs::report_settings::ReportSettings;
 use crate::structs::style_tuple::StyleTuple;
 use crate::utility::style_constants::{
     get_font, get_font_headers, DEEP_SEA, FONT_SIZE_FOOTER, FONT_SIZE_SUBTITLE, FONT_SIZE_TITLE,
     ICONS, MON_AMOUR, YETI_DAY, YETI_NIGHT,
 };
 use crate::utility::translations::{
-    appearance_title_translation, bytes_threshold_translation, deep_sea_translation,
-    favorite_notification_translation, hide_translation, languages_title_translation,
-    mon_amour_translation, notifications_title_translation, packets_threshold_translation,
-    per_second_translation, settings_translation, specify_multiples_translation,
-    threshold_translation, volume_translation, yeti_day_translation, yeti_night_translation,
+    add_translation, advanced_title_translation, appearance_title_translation,
+    asn_database_translation, bidirectional_flows_translation, both_translation,
+    byte_counting_translation, bytes_threshold_translation, capture_settings_translation,
+    city_database_translation, country_database_placeholder_translation,
+    country_database_translation, deep_sea_translation, export_csv_translation,
+    export_json_translation, favorite_notification_translation, hide_translation,
+    languages_title_translation, max_sessions_translation, mmdb_settings_translation,
+    mon_amour_translation, no_port_mappings_translation, notifications_title_translation,
+    packets_threshold_translation, per_second_translation, port_mapping_label_translation,
+    port_mapping_ports_translation, protocols_title_translation, record_max_duration_translation,
+    record_max_size_translation, record_only_filtered_translation, recording_translation,
+    remove_translation, report_directory_translation, report_settings_translation,
+    seconds_translation, sessions_translation, settings_translation, snaplen_hint_translation,
+    snaplen_translation, specify_multiples_translation, threshold_translation, volume_translation,
+    yeti_day_translation, yeti_night_translation,
 };
 use crate::StyleType::{Day, DeepSea, MonAmour, Night};
-use crate::{Language, Sniffer, StyleType};
+use crate::{get_colors, Language, Sniffer, StyleType, TransProtocol};
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::{
     button, horizontal_space, image::Handle, vertical_space, Button, Checkbox, Column, Container,
-    Image, Row, Scrollable, Text, TextInput, Tooltip,
+    Image, Radio, Row, Scrollable, Text, TextInput, Tooltip,
 };
 use iced::Length::Units;
 use iced::{Alignment, Length};
@@ -38,16 +54,20 @@ pub fn settings_notifications_page(sniffer: &Sniffer) -> Container<Message> {
         .width(Length::Fill)
         .push(get_settings_header(sniffer.style, sniffer.language))
         .push(get_settings_tabs(
-            [
+            &[
                 MyOverlay::SettingsNotifications,
                 MyOverlay::SettingsAppearance,
                 MyOverlay::SettingsLanguage,
+                MyOverlay::SettingsAdvanced,
+                MyOverlay::SettingsProtocols,
             ],
-            &["7 ", "K ", "c "],
+            &["7 ", "K ", "c ", "a ", "5 "],
             &[
                 Message::TickInit,
                 Message::ShowModal(MyOverlay::SettingsAppearance),
                 Message::ShowModal(MyOverlay::SettingsLanguage),
+                Message::ShowModal(MyOverlay::SettingsAdvanced),
+                Message::ShowModal(MyOverlay::SettingsProtocols),
             ],
             MyOverlay::SettingsNotifications,
             sniffer.style,
@@ -113,16 +133,20 @@ pub fn settings_appearance_page(sniffer: &Sniffer) -> Container<Message> {
         .width(Length::Fill)
         .push(get_settings_header(sniffer.style, sniffer.language))
         .push(get_settings_tabs(
-            [
+            &[
                 MyOverlay::SettingsNotifications,
                 MyOverlay::SettingsAppearance,
                 MyOverlay::SettingsLanguage,
+                MyOverlay::SettingsAdvanced,
+                MyOverlay::SettingsProtocols,
             ],
-            &["7 ", "K ", "c "],
+            &["7 ", "K ", "c ", "a ", "5 "],
             &[
                 Message::ShowModal(MyOverlay::SettingsNotifications),
                 Message::TickInit,
                 Message::ShowModal(MyOverlay::SettingsLanguage),
+                Message::ShowModal(MyOverlay::SettingsAdvanced),
+                Message::ShowModal(MyOverlay::SettingsProtocols),
             ],
             MyOverlay::SettingsAppearance,
             sniffer.style,
@@ -192,16 +216,20 @@ pub fn settings_language_page(sniffer: &Sniffer) -> Container<Message> {
         .width(Length::Fill)
         .push(get_settings_header(sniffer.style, sniffer.language))
         .push(get_settings_tabs(
-            [
+            &[
                 MyOverlay::SettingsNotifications,
                 MyOverlay::SettingsAppearance,
                 MyOverlay::SettingsLanguage,
+                MyOverlay::SettingsAdvanced,
+                MyOverlay::SettingsProtocols,
             ],
-            &["7 ", "K ", "c "],
+            &["7 ", "K ", "c ", "a ", "5 "],
             &[
                 Message::ShowModal(MyOverlay::SettingsNotifications),
                 Message::ShowModal(MyOverlay::SettingsAppearance),
                 Message::TickInit,
+                Message::ShowModal(MyOverlay::SettingsAdvanced),
+                Message::ShowModal(MyOverlay::SettingsProtocols),
             ],
             MyOverlay::SettingsLanguage,
             sniffer.style,
@@ -231,6 +259,138 @@ pub fn settings_language_page(sniffer: &Sniffer) -> Container<Message> {
         ))
 }
 
+pub fn settings_advanced_page(sniffer: &Sniffer) -> Container<Message> {
+    let font = get_font(sniffer.style);
+    let content = Column::new()
+        .align_items(Alignment::Center)
+        .width(Length::Fill)
+        .push(get_settings_header(sniffer.style, sniffer.language))
+        .push(get_settings_tabs(
+            &[
+                MyOverlay::SettingsNotifications,
+                MyOverlay::SettingsAppearance,
+                MyOverlay::SettingsLanguage,
+                MyOverlay::SettingsAdvanced,
+                MyOverlay::SettingsProtocols,
+            ],
+            &["7 ", "K ", "c ", "a ", "5 "],
+            &[
+                Message::ShowModal(MyOverlay::SettingsNotifications),
+                Message::ShowModal(MyOverlay::SettingsAppearance),
+                Message::ShowModal(MyOverlay::SettingsLanguage),
+                Message::TickInit,
+                Message::ShowModal(MyOverlay::SettingsProtocols),
+            ],
+            MyOverlay::SettingsAdvanced,
+            sniffer.style,
+            sniffer.language,
+        ))
+        .push(vertical_space(Units(15)))
+        .push(
+            advanced_title_translation(sniffer.language)
+                .font(font)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(vertical_space(Units(5)))
+        .push(
+            Scrollable::new(
+                Column::new()
+                    .width(Units(720))
+                    .push(get_recording_settings(
+                        *sniffer.recording.lock().unwrap(),
+                        sniffer.language,
+                        sniffer.style,
+                    ))
+                    .push(get_report_settings(
+                        sniffer.report_settings.lock().unwrap().clone(),
+                        sniffer.language,
+                        sniffer.style,
+                    ))
+                    .push(get_capture_settings(
+                        sniffer.capture_settings,
+                        sniffer.language,
+                        sniffer.style,
+                    ))
+                    .push(get_mmdb_settings(
+                        sniffer.mmdb_settings.clone(),
+                        sniffer.language,
+                        sniffer.style,
+                    )),
+            )
+            .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
+                StyleTuple(sniffer.style, ElementType::Standard),
+            )),
+        );
+
+    Container::new(content)
+        .height(Units(400))
+        .width(Units(800))
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(sniffer.style, ElementType::Standard),
+        ))
+}
+
+pub fn settings_protocols_page(sniffer: &Sniffer) -> Container<Message> {
+    let font = get_font(sniffer.style);
+    let content = Column::new()
+        .align_items(Alignment::Center)
+        .width(Length::Fill)
+        .push(get_settings_header(sniffer.style, sniffer.language))
+        .push(get_settings_tabs(
+            &[
+                MyOverlay::SettingsNotifications,
+                MyOverlay::SettingsAppearance,
+                MyOverlay::SettingsLanguage,
+                MyOverlay::SettingsAdvanced,
+                MyOverlay::SettingsProtocols,
+            ],
+            &["7 ", "K ", "c ", "a ", "5 "],
+            &[
+                Message::ShowModal(MyOverlay::SettingsNotifications),
+                Message::ShowModal(MyOverlay::SettingsAppearance),
+                Message::ShowModal(MyOverlay::SettingsLanguage),
+                Message::ShowModal(MyOverlay::SettingsAdvanced),
+                Message::TickInit,
+            ],
+            MyOverlay::SettingsProtocols,
+            sniffer.style,
+            sniffer.language,
+        ))
+        .push(vertical_space(Units(15)))
+        .push(
+            protocols_title_translation(sniffer.language)
+                .font(font)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(vertical_space(Units(5)))
+        .push(
+            Scrollable::new(
+                Column::new()
+                    .width(Units(720))
+                    .push(get_port_mapping_draft(
+                        &sniffer.port_mapping_draft,
+                        sniffer.language,
+                        sniffer.style,
+                    ))
+                    .push(get_port_mappings_list(
+                        &sniffer.port_mappings,
+                        sniffer.language,
+                        sniffer.style,
+                    )),
+            )
+            .style(<StyleTuple as Into<iced::theme::Scrollable>>::into(
+                StyleTuple(sniffer.style, ElementType::Standard),
+            )),
+        );
+
+    Container::new(content)
+        .height(Units(400))
+        .width(Units(800))
+        .style(<StyleTuple as Into<iced::theme::Container>>::into(
+            StyleTuple(sniffer.style, ElementType::Standard),
+        ))
+}
+
 fn get_packets_notify(
     packets_notification: PacketsNotification,
     language: Language,
@@ -445,6 +605,600 @@ fn get_favorite_notify(
     }
 }
 
+fn get_recording_settings(
+    recording: Recording,
+    language: Language,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+
+    let checkbox_only_filtered = Checkbox::new(
+        record_only_filtered_translation(language),
+        recording.only_filtered,
+        move |toggled| {
+            Message::UpdateRecording(Recording {
+                only_filtered: toggled,
+                ..recording
+            })
+        },
+    )
+    .size(18)
+    .font(font)
+    .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let checkbox_max_size = Checkbox::new(
+        record_max_size_translation(language),
+        recording.max_file_size.is_some(),
+        move |toggled| {
+            Message::UpdateRecording(Recording {
+                max_file_size: if toggled {
+                    Some(recording.previous_max_file_size)
+                } else {
+                    None
+                },
+                ..recording
+            })
+        },
+    )
+    .size(18)
+    .font(font)
+    .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let checkbox_max_duration = Checkbox::new(
+        record_max_duration_translation(language),
+        recording.max_file_duration.is_some(),
+        move |toggled| {
+            Message::UpdateRecording(Recording {
+                max_file_duration: if toggled {
+                    Some(recording.previous_max_file_duration)
+                } else {
+                    None
+                },
+                ..recording
+            })
+        },
+    )
+    .size(18)
+    .font(font)
+    .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let mut ret_val = Column::new()
+        .spacing(10)
+        .push(Text::new(recording_translation(language)).font(font))
+        .push(checkbox_only_filtered)
+        .push(checkbox_max_size);
+    if let Some(max_file_size) = recording.max_file_size {
+        ret_val = ret_val.push(
+            Row::new()
+                .push(horizontal_space(Units(50)))
+                .push(Text::new(threshold_translation(language)).font(font))
+                .push(input_group_number(
+                    max_file_size,
+                    "MB",
+                    move |value| {
+                        Message::UpdateRecording(Recording {
+                            max_file_size: Some(value),
+                            previous_max_file_size: value,
+                            ..recording
+                        })
+                    },
+                    style,
+                )),
+        );
+    }
+    ret_val = ret_val.push(checkbox_max_duration);
+    if let Some(max_file_duration) = recording.max_file_duration {
+        ret_val = ret_val.push(
+            Row::new()
+                .push(horizontal_space(Units(50)))
+                .push(Text::new(threshold_translation(language)).font(font))
+                .push(input_group_number(
+                    max_file_duration,
+                    seconds_translation(language),
+                    move |value| {
+                        Message::UpdateRecording(Recording {
+                            max_file_duration: Some(value),
+                            previous_max_file_duration: value,
+                            ..recording
+                        })
+                    },
+                    style,
+                )),
+        );
+    }
+
+    Column::new()
+        .padding(5)
+        .push(
+            Container::new(ret_val)
+                .padding(10)
+                .width(Units(700))
+                .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                    StyleTuple(style, ElementType::BorderedRound),
+                )),
+        )
+}
+
+fn get_report_settings(
+    report_settings: ReportSettings,
+    language: Language,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+
+    let settings = report_settings.clone();
+    let input_directory = TextInput::new("", &report_settings.directory, move |directory| {
+        Message::UpdateReportSettings(ReportSettings {
+            directory,
+            ..settings.clone()
+        })
+    })
+    .padding(5)
+    .font(font)
+    .width(Length::Units(500))
+    .style(<StyleTuple as Into<iced::theme::TextInput>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let settings = report_settings.clone();
+    let input_max_sessions = input_group_number(
+        report_settings.max_sessions,
+        sessions_translation(language),
+        move |max_sessions| {
+            Message::UpdateReportSettings(ReportSettings {
+                max_sessions,
+                ..settings.clone()
+            })
+        },
+        style,
+    );
+
+    let settings = report_settings.clone();
+    let checkbox_csv = Checkbox::new(
+        export_csv_translation(language),
+        report_settings.csv,
+        move |toggled| {
+            Message::UpdateReportSettings(ReportSettings {
+                csv: toggled,
+                ..settings.clone()
+            })
+        },
+    )
+    .size(18)
+    .font(font)
+    .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let settings = report_settings.clone();
+    let checkbox_json = Checkbox::new(
+        export_json_translation(language),
+        report_settings.json,
+        move |toggled| {
+            Message::UpdateReportSettings(ReportSettings {
+                json: toggled,
+                ..settings.clone()
+            })
+        },
+    )
+    .size(18)
+    .font(font)
+    .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let content = Column::new()
+        .spacing(10)
+        .push(Text::new(report_settings_translation(language)).font(font))
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(report_directory_translation(language)).font(font))
+                .push(input_directory),
+        )
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(max_sessions_translation(language)).font(font))
+                .push(input_max_sessions),
+        )
+        .push(checkbox_csv)
+        .push(checkbox_json);
+
+    Column::new()
+        .padding(5)
+        .push(
+            Container::new(content)
+                .padding(10)
+                .width(Units(700))
+                .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                    StyleTuple(style, ElementType::BorderedRound),
+                )),
+        )
+}
+
+fn get_capture_settings(
+    capture_settings: CaptureSettings,
+    language: Language,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+
+    let input_snaplen = input_group_number(
+        capture_settings.snaplen,
+        "B",
+        move |snaplen| {
+            Message::UpdateCaptureSettings(CaptureSettings {
+                snaplen,
+                ..capture_settings
+            })
+        },
+        style,
+    );
+
+    let mut row_byte_counting = Row::new()
+        .spacing(15)
+        .align_items(Alignment::Center)
+        .push(Text::new(byte_counting_translation(language)).font(font));
+    for option in ByteCounting::ALL {
+        row_byte_counting = row_byte_counting.push(
+            Radio::new(
+                option,
+                option.get_radio_label(language),
+                Some(capture_settings.byte_counting),
+                move |byte_counting| {
+                    Message::UpdateCaptureSettings(CaptureSettings {
+                        byte_counting,
+                        ..capture_settings
+                    })
+                },
+            )
+            .font(font)
+            .size(15)
+            .style(<StyleTuple as Into<iced::theme::Radio>>::into(StyleTuple(
+                style,
+                ElementType::Standard,
+            ))),
+        );
+    }
+
+    let checkbox_flows = Checkbox::new(
+        bidirectional_flows_translation(language),
+        capture_settings.bidirectional_flows,
+        move |toggled| {
+            Message::UpdateCaptureSettings(CaptureSettings {
+                bidirectional_flows: toggled,
+                ..capture_settings
+            })
+        },
+    )
+    .size(18)
+    .font(font)
+    .style(<StyleTuple as Into<iced::theme::Checkbox>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let content = Column::new()
+        .spacing(10)
+        .push(Text::new(capture_settings_translation(language)).font(font))
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(snaplen_translation(language)).font(font))
+                .push(input_snaplen),
+        )
+        .push(
+            Text::new(format!(
+                "{MIN_SNAPLEN} - {MAX_SNAPLEN} B, {}",
+                snaplen_hint_translation(language)
+            ))
+            .font(font)
+            .size(FONT_SIZE_FOOTER),
+        )
+        .push(row_byte_counting)
+        .push(checkbox_flows);
+
+    Column::new()
+        .padding(5)
+        .push(
+            Container::new(content)
+                .padding(10)
+                .width(Units(700))
+                .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                    StyleTuple(style, ElementType::BorderedRound),
+                )),
+        )
+}
+
+fn get_mmdb_settings(
+    mmdb_settings: MmdbSettings,
+    language: Language,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+
+    let settings = mmdb_settings.clone();
+    let input_country = TextInput::new(
+        country_database_placeholder_translation(language),
+        &mmdb_settings.country,
+        move |country| {
+            Message::UpdateMmdbSettings(MmdbSettings {
+                country,
+                ..settings.clone()
+            })
+        },
+    );
+
+    let settings = mmdb_settings.clone();
+    let input_city = TextInput::new("GeoLite2-City.mmdb", &mmdb_settings.city, move |city| {
+        Message::UpdateMmdbSettings(MmdbSettings {
+            city,
+            ..settings.clone()
+        })
+    });
+
+    let settings = mmdb_settings.clone();
+    let input_asn = TextInput::new("GeoLite2-ASN.mmdb", &mmdb_settings.asn, move |asn| {
+        Message::UpdateMmdbSettings(MmdbSettings {
+            asn,
+            ..settings.clone()
+        })
+    });
+
+    let mut content = Column::new()
+        .spacing(10)
+        .push(Text::new(mmdb_settings_translation(language)).font(font));
+    for (label, input) in [
+        (country_database_translation(language), input_country),
+        (city_database_translation(language), input_city),
+        (asn_database_translation(language), input_asn),
+    ] {
+        content = content.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(label).font(font).width(Length::Units(150)))
+                .push(input.padding(5).font(font).width(Length::Units(500)).style(
+                    <StyleTuple as Into<iced::theme::TextInput>>::into(StyleTuple(
+                        style,
+                        ElementType::Standard,
+                    )),
+                )),
+        );
+    }
+
+    Column::new()
+        .padding(5)
+        .push(
+            Container::new(content)
+                .padding(10)
+                .width(Units(700))
+                .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                    StyleTuple(style, ElementType::BorderedRound),
+                )),
+        )
+}
+
+fn get_port_mapping_draft(
+    draft: &PortMappingDraft,
+    language: Language,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+
+    let draft_ports = draft.clone();
+    let input_ports = TextInput::new("8000-8100", &draft.ports, move |ports| {
+        Message::UpdatePortMappingDraft(PortMappingDraft {
+            ports,
+            error: None,
+            ..draft_ports.clone()
+        })
+    })
+    .on_submit(Message::AddPortMapping)
+    .padding(5)
+    .font(font)
+    .width(Length::Units(110))
+    .style(<StyleTuple as Into<iced::theme::TextInput>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let draft_label = draft.clone();
+    let input_label = TextInput::new("Backup", &draft.label, move |label| {
+        Message::UpdatePortMappingDraft(PortMappingDraft {
+            label,
+            error: None,
+            ..draft_label.clone()
+        })
+    })
+    .on_submit(Message::AddPortMapping)
+    .padding(5)
+    .font(font)
+    .width(Length::Units(110))
+    .style(<StyleTuple as Into<iced::theme::TextInput>>::into(
+        StyleTuple(style, ElementType::Standard),
+    ));
+
+    let mut row_transport = Row::new().spacing(10);
+    for option in TransProtocol::WITH_PORTS {
+        let draft_transport = draft.clone();
+        let label = get_mapping_transport_label(option, language);
+        row_transport = row_transport.push(
+            Radio::new(option, label, Some(draft.transport), move |transport| {
+                Message::UpdatePortMappingDraft(PortMappingDraft {
+                    transport,
+                    error: None,
+                    ..draft_transport
+                })
+            })
+            .font(font)
+            .size(15)
+            .style(<StyleTuple as Into<iced::theme::Radio>>::into(StyleTuple(
+                style,
+                ElementType::Standard,
+            ))),
+        );
+    }
+
+    let button_add = button(
+        Text::new(add_translation(language))
+            .font(font)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(5)
+    .height(Units(30))
+    .width(Units(80))
+    .style(StyleTuple(style, ElementType::Standard).into())
+    .on_press(Message::AddPortMapping);
+
+    let mut content = Column::new()
+        .spacing(10)
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(port_mapping_ports_translation(language)).font(font))
+                .push(input_ports)
+                .push(Text::new(port_mapping_label_translation(language)).font(font))
+                .push(input_label)
+                .push(button_add),
+        )
+        .push(row_transport);
+    if let Some(error) = &draft.error {
+        content = content.push(
+            Text::new(error.clone())
+                .font(font)
+                .style(iced::theme::Text::Color(get_colors(style).secondary)),
+        );
+    }
+
+    Column::new()
+        .padding(5)
+        .push(
+            Container::new(content)
+                .padding(10)
+                .width(Units(700))
+                .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                    StyleTuple(style, ElementType::BorderedRound),
+                )),
+        )
+}
+
+fn get_port_mappings_list(
+    port_mappings: &[PortMapping],
+    language: Language,
+    style: StyleType,
+) -> Column<'static, Message> {
+    let font = get_font(style);
+
+    let mut content = Column::new().spacing(5);
+    if port_mappings.is_empty() {
+        content = content.push(Text::new(no_port_mappings_translation(language)).font(font));
+    }
+    for (index, port_mapping) in port_mappings.iter().enumerate() {
+        content = content.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(format!(
+                        "{:<11} {:<5} {}",
+                        port_mapping.get_ports_string(),
+                        get_mapping_transport_label(port_mapping.transport, language),
+                        port_mapping.app_protocol
+                    ))
+                    .font(font)
+                    .width(Length::Units(600)),
+                )
+                .push(
+                    Tooltip::new(
+                        button(
+                            Text::new("x")
+                                .font(font)
+                                .horizontal_alignment(Horizontal::Center)
+                                .size(15),
+                        )
+                        .padding(2)
+                        .height(Units(20))
+                        .width(Units(20))
+                        .style(StyleTuple(style, ElementType::Standard).into())
+                        .on_press(Message::RemovePortMapping(index)),
+                        remove_translation(language),
+                        Position::Left,
+                    )
+                    .font(font)
+                    .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                        StyleTuple(style, ElementType::Tooltip),
+                    )),
+                ),
+        );
+    }
+
+    Column::new()
+        .padding(5)
+        .push(
+            Container::new(content)
+                .padding(10)
+                .width(Units(700))
+                .style(<StyleTuple as Into<iced::theme::Container>>::into(
+                    StyleTuple(style, ElementType::BorderedRound),
+                )),
+        )
+}
+
+fn input_group_number(
+    value: u64,
+    unit: &'static str,
+    update: impl Fn(u64) -> Message + 'static,
+    style: StyleType,
+) -> Container<'static, Message> {
+    let curr_value_str = value.to_string();
+    let input_row = Row::new()
+        .spacing(10)
+        .push(
+            TextInput::new(
+                "0",
+                if curr_value_str == "0" {
+                    ""
+                } else {
+                    &curr_value_str
+                },
+                move |new_value| {
+                    let new_value = if new_value.is_empty() {
+                        0
+                    } else {
+                        new_value.parse().unwrap_or(value)
+                    };
+                    update(new_value)
+                },
+            )
+            .padding(1)
+            .font(get_font(style))
+            .width(Length::Units(100))
+            .style(<StyleTuple as Into<iced::theme::TextInput>>::into(
+                StyleTuple(style, ElementType::Standard),
+            )),
+        )
+        .push(
+            Text::new(unit)
+                .font(get_font(style))
+                .vertical_alignment(Vertical::Center)
+                .size(FONT_SIZE_FOOTER),
+        );
+    Container::new(input_row)
+        .align_x(Horizontal::Center)
+        .align_y(Vertical::Center)
+}
+
 fn input_group_packets(
     packets_notification: PacketsNotification,
     style: StyleType,
@@ -687,3 +1441,12 @@ fn get_settings_header(style: StyleType, language: Language) -> Container<'stati
         StyleTuple(style, ElementType::Headers),
     ))
 }
+
+/// Returns the label of the transport protocol of a port mapping (Other means both TCP and UDP)
+fn get_mapping_transport_label(transport: TransProtocol, language: Language) -> String {
+    if transport.eq(&TransProtocol::Other) {
+        both_translation(language).to_string()
+    } else {
+        transport.get_radio_label(language).to_string()
+    }
+}
@@ -2,8 +2,9 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Condvar, Mutex};
-use std::{panic, process, thread};
+use std::{env, panic, process, thread};
 
 use iced::window::Position;
 use iced::{window, Application, Settings};
@@ -11,9 +12,13 @@ use pcap::Device;
 
 use utility::style_constants::FONT_SIZE_BODY;
 
+use crate::cli::{parse_cli_args, run_headless, CLI_USAGE};
 use crate::enums::app_protocol::AppProtocol;
 use crate::enums::byte_multiple::ByteMultiple;
+use crate::enums::capture_source::CaptureSource;
 use crate::enums::chart_type::ChartType;
+use crate::enums::country_sorting::CountrySorting;
+use crate::enums::host_sorting::HostSorting;
 use crate::enums::ip_version::IpVersion;
 use crate::enums::language::Language;
 use crate::enums::report_type::ReportType;
@@ -22,24 +27,31 @@ use crate::enums::status::Status;
 use crate::enums::style_type::StyleType;
 use crate::enums::trans_protocol::TransProtocol;
 use crate::structs::config::Config;
-use crate::structs::filters::Filters;
 use crate::structs::info_traffic::InfoTraffic;
 use crate::structs::palette::get_colors;
+use crate::structs::port_mapping::PortMappingDraft;
+use crate::structs::report_table::ReportTable;
 use crate::structs::runtime_data::RunTimeData;
 use crate::structs::sniffer::Sniffer;
 use crate::structs::traffic_chart::TrafficChart;
+use crate::structs::world_map::WorldMap;
 use crate::thread_write_report::sleep_and_write_report_loop;
+use crate::utility::manage_filters::validate_bpf_filter;
 
+mod cli;
 mod enums;
 mod gui;
 mod structs;
 mod thread_parse_packets;
+mod thread_reverse_dns;
 mod thread_write_report;
 mod utility;
 
 /// Entry point of application execution
 ///
 /// It initialized shared variables and gui parameters
+///
+/// If command line arguments are given, the analysis is run without GUI
 pub fn main() -> iced::Result {
     let current_capture_id1 = Arc::new(Mutex::new(0));
     let current_capture_id2 = current_capture_id1.clone();
@@ -51,18 +63,11 @@ pub fn main() -> iced::Result {
     let status_pair1 = Arc::new((Mutex::new(Status::Init), Condvar::new()));
     let status_pair2 = status_pair1.clone();
 
-    let found_device = Device::lookup().unwrap().unwrap();
-
     let pcap_error = None; // None means no error
 
     let runtime_data1 = Rc::new(RefCell::new(RunTimeData::new()));
     let runtime_data2 = runtime_data1.clone();
-
-    let filters = Filters {
-        ip: IpVersion::Other,
-        transport: TransProtocol::Other,
-        application: AppProtocol::Other,
-    };
+    let runtime_data3 = runtime_data1.clone();
 
     // to kill the main thread as soon as a secondary thread panics
     let orig_hook = panic::take_hook();
@@ -72,12 +77,28 @@ pub fn main() -> iced::Result {
         process::exit(1);
     }));
 
-    thread::Builder::new()
-        .name("thread_write_report".to_string())
-        .spawn(move || {
-            sleep_and_write_report_loop(&current_capture_id2, &mutex_map2, &status_pair2);
-        })
-        .unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+    match parse_cli_args(&args) {
+        Ok(None) => {}
+        Ok(Some(cli_args)) if cli_args.help => {
+            println!("{CLI_USAGE}");
+            return Ok(());
+        }
+        Ok(Some(cli_args)) => {
+            if let Err(error) = run_headless(cli_args) {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            process::exit(2);
+        }
+    }
+
+    // the default adapter is looked up only for the GUI, the headless analyses select their own
+    let found_device = Device::lookup().unwrap().unwrap();
 
     let mut config_result = confy::load::<Config>("sniffnet", None);
     if config_result.is_err() {
@@ -89,6 +110,26 @@ pub fn main() -> iced::Result {
     let style = config.style;
     let notifications = config.notifications;
     let language = config.language;
+    let recording = Arc::new(Mutex::new(config.recording));
+    let filters = config.filters;
+    // the stored BPF expression could have been edited by hand
+    let bpf_error = validate_bpf_filter(&filters.bpf).err();
+    let report_settings1 = Arc::new(Mutex::new(config.report));
+    let report_settings2 = report_settings1.clone();
+
+    thread::Builder::new()
+        .name("thread_write_report".to_string())
+        .spawn(move || {
+            sleep_and_write_report_loop(
+                &current_capture_id2,
+                &mutex_map2,
+                &status_pair2,
+                &report_settings2,
+                // the GUI never stops the report thread
+                &AtomicBool::new(false),
+            );
+        })
+        .unwrap();
 
     Sniffer::run(Settings {
         id: None,
@@ -108,18 +149,34 @@ pub fn main() -> iced::Result {
             current_capture_id: current_capture_id1,
             info_traffic: mutex_map1,
             runtime_data: runtime_data1,
-            device: found_device,
+            devices: vec![found_device],
+            capture_source: CaptureSource::Adapter,
+            capture_file: String::new(),
             filters,
             status_pair: status_pair1,
             pcap_error,
+            bpf_error,
             style,
             waiting: ".".to_string(),
             traffic_chart: TrafficChart::new(runtime_data2, style, language),
-            report_type: ReportType::MostRecent,
+            report_type: ReportType::AllConnections,
+            report_table: ReportTable::default(),
+            report_interface: None,
+            host_sorting: HostSorting::Recent,
+            group_by_organization: false,
+            report_country: None,
+            country_sorting: CountrySorting::Bytes,
+            world_map: WorldMap::new(runtime_data3, style),
             overlay: None,
             notifications,
             running_page: RunningPage::Overview,
             language,
+            recording,
+            report_settings: report_settings1,
+            capture_settings: config.capture,
+            mmdb_settings: config.mmdb,
+            port_mappings: config.port_mappings,
+            port_mapping_draft: PortMappingDraft::default(),
         },
         default_font: Some(include_bytes!("../fonts/inconsolata-regular.ttf")),
         default_text_size: FONT_SIZE_BODY,
@@ -9,13 +9,13 @@ use crate::TransProtocol;
 pub struct AddressPortPair {
     /// Network layer IPv4 or IPv6 source address.
     pub address1: String,
-    /// Transport layer source port number (in the range 0..=65535).
+    /// Transport layer source port number (in the range 0..=65535, 0 for protocols without ports).
     pub port1: u16,
     /// Network layer IPv4 or IPv6 destination address.
     pub address2: String,
-    /// Transport layer destination port number (in the range 0..=65535).
+    /// Transport layer destination port number (in the range 0..=65535, 0 for protocols without ports).
     pub port2: u16,
-    ///  Transport layer protocol carried through the associate address:port pair (TCP or UPD).
+    ///  Transport layer protocol carried through the associate address:port pair (TCP, UDP, ICMP, ARP or Other).
     pub trans_protocol: TransProtocol,
 }
 
@@ -43,24 +43,40 @@ impl AddressPortPair {
         }
     }
 
-    pub fn print_gui(&self) -> String {
-        self.to_string().replace('|', "")
+    /// Returns the address:port pair of the packets travelling in the opposite direction
+    pub fn reversed(&self) -> Self {
+        AddressPortPair::new(
+            self.address2.clone(),
+            self.port2,
+            self.address1.clone(),
+            self.port1,
+            self.trans_protocol,
+        )
     }
 }
 
 impl fmt::Display for AddressPortPair {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the protocol is converted to string to be padded to the column width
         if self.address1.len() > 25 || self.address2.len() > 25 {
             write!(
                 f,
-                "|{:^45}|{:>8}  |{:^45}|{:>8}  |   {}   |",
-                self.address1, self.port1, self.address2, self.port2, self.trans_protocol
+                "|{:^45}|{:>8}  |{:^45}|{:>8}  |  {:^5}  |",
+                self.address1,
+                self.port1,
+                self.address2,
+                self.port2,
+                self.trans_protocol.to_string()
             )
         } else {
             write!(
                 f,
-                "|{:^25}|{:>8}  |{:^25}|{:>8}  |   {}   |",
-                self.address1, self.port1, self.address2, self.port2, self.trans_protocol
+                "|{:^25}|{:>8}  |{:^25}|{:>8}  |  {:^5}  |",
+                self.address1,
+                self.port1,
+                self.address2,
+                self.port2,
+                self.trans_protocol.to_string()
             )
         }
     }
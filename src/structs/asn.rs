@@ -0,0 +1,18 @@
+//! Module defining the `Asn` struct, which identifies the autonomous system of a remote address.
+
+use std::fmt;
+
+/// Struct representing the autonomous system a remote address belongs to (e.g. a cloud provider or a CDN).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Asn {
+    /// Autonomous system number
+    pub number: u32,
+    /// Name of the organization managing the autonomous system
+    pub name: String,
+}
+
+impl fmt::Display for Asn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AS{} {}", self.number, self.name)
+    }
+}
@@ -0,0 +1,41 @@
+//! Module defining the `CaptureSettings` struct, which represents the configuration
+//! used to open the network adapters.
+
+use serde::{Deserialize, Serialize};
+
+use crate::enums::byte_counting::ByteCounting;
+
+/// Minimum snapshot length accepted, enough to contain the packet headers
+pub const MIN_SNAPLEN: u64 = 128;
+/// Maximum snapshot length accepted, enough to contain any packet
+pub const MAX_SNAPLEN: u64 = 65535;
+
+/// Used to contain the capture configuration set by the user
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct CaptureSettings {
+    /// Maximum number of bytes captured for each packet (higher values permit to inspect more of
+    /// the payloads, e.g. to find server names, but fewer packets fit in the buffer)
+    pub snaplen: u64,
+    /// How the bytes of each packet are counted in totals, charts, notifications and reports
+    pub byte_counting: ByteCounting,
+    /// Flag to aggregate the packets of both directions of a conversation in a single connection
+    pub bidirectional_flows: bool,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        CaptureSettings {
+            snaplen: 1024,
+            byte_counting: ByteCounting::default(),
+            bidirectional_flows: false,
+        }
+    }
+}
+
+impl CaptureSettings {
+    /// Returns the snapshot length to be used by pcap, limited to the accepted range
+    pub fn get_snaplen(&self) -> i32 {
+        i32::try_from(self.snaplen.clamp(MIN_SNAPLEN, MAX_SNAPLEN)).unwrap()
+    }
+}
@@ -2,13 +2,49 @@
 //! the application default configuration.
 
 use crate::enums::language::Language;
+use crate::structs::capture_settings::CaptureSettings;
+use crate::structs::filters::Filters;
+use crate::structs::mmdb_settings::MmdbSettings;
 use crate::structs::notifications::Notifications;
+use crate::structs::port_mapping::PortMapping;
+use crate::structs::recording::Recording;
+use crate::structs::report_settings::ReportSettings;
 use crate::StyleType;
 use serde::{Deserialize, Serialize};
 
+/// Missing fields take their default value, so that a configuration file
+/// stored by a previous version of the application can still be loaded.
 #[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct Config {
     pub style: StyleType,
     pub language: Language,
     pub notifications: Notifications,
+    pub recording: Recording,
+    pub filters: Filters,
+    pub report: ReportSettings,
+    pub capture: CaptureSettings,
+    pub mmdb: MmdbSettings,
+    pub port_mappings: Vec<PortMapping>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_configuration_is_loaded() {
+        let config: Config =
+            serde_json::from_str(r#"{"language": "IT", "filters": {"ports": "53"}}"#).unwrap();
+        assert_eq!(config.language, Language::IT);
+        assert_eq!(
+            config.filters,
+            Filters {
+                ports: "53".to_string(),
+                ..Filters::default()
+            }
+        );
+        assert_eq!(config.report, ReportSettings::default());
+        assert!(config.port_mappings.is_empty());
+    }
 }
@@ -0,0 +1,54 @@
+//! Module defining the `CountryInfo` struct, which aggregates the traffic exchanged with the hosts of a country.
+
+use crate::structs::host_info::HostInfo;
+
+/// Struct representing the traffic exchanged with all the remote hosts of a country.
+#[derive(Clone, Debug)]
+pub struct CountryInfo {
+    /// ISO code of the country (empty if unknown)
+    pub country: String,
+    /// Amount of bytes received from the country
+    pub bytes_in: u128,
+    /// Amount of bytes sent to the country
+    pub bytes_out: u128,
+    /// Amount of packets received from the country
+    pub packets_in: u128,
+    /// Amount of packets sent to the country
+    pub packets_out: u128,
+    /// Number of connections with the hosts of the country
+    pub connections: usize,
+    /// Number of remote hosts of the country
+    pub hosts: usize,
+}
+
+impl CountryInfo {
+    /// Constructs a new `CountryInfo` with the given remote host
+    pub fn new(host_info: &HostInfo) -> Self {
+        let mut country_info = CountryInfo {
+            country: host_info.country.clone(),
+            bytes_in: 0,
+            bytes_out: 0,
+            packets_in: 0,
+            packets_out: 0,
+            connections: 0,
+            hosts: 0,
+        };
+        country_info.add_host(host_info);
+        country_info
+    }
+
+    /// Adds the traffic of a remote host of this country
+    pub fn add_host(&mut self, host_info: &HostInfo) {
+        self.bytes_in += host_info.bytes_in;
+        self.bytes_out += host_info.bytes_out;
+        self.packets_in += host_info.packets_in;
+        self.packets_out += host_info.packets_out;
+        self.connections += host_info.connections;
+        self.hosts += 1;
+    }
+
+    /// Returns the total amount of bytes exchanged with the country
+    pub fn get_bytes(&self) -> u128 {
+        self.bytes_in + self.bytes_out
+    }
+}
@@ -0,0 +1,74 @@
+//! Module defining the `DnsTable` struct, which stores the domain names learned
+//! from the sniffed DNS responses.
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+
+/// Maximum number of domain names remembered from the sniffed DNS responses
+pub const MAX_DNS_TABLE_LEN: usize = 10_000;
+
+/// Struct mapping the resolved addresses to their domain names,
+/// forgetting the least recently answered ones when full.
+#[derive(Default)]
+pub struct DnsTable {
+    /// Domain name of each address, with the number of the answer that stored it
+    domains: HashMap<IpAddr, (String, u64)>,
+    /// Answered addresses, from the least to the most recent
+    /// (an address answered again is also found earlier, with an outdated number)
+    answers: VecDeque<(IpAddr, u64)>,
+    /// Number of answers stored so far
+    answers_count: u64,
+}
+
+impl DnsTable {
+    /// Returns the domain name of the given address, if it was answered
+    pub fn get(&self, address: &IpAddr) -> Option<&String> {
+        self.domains.get(address).map(|(domain, _)| domain)
+    }
+
+    /// Stores the domain name of an address, which becomes the most recently answered one
+    pub fn insert(&mut self, address: IpAddr, domain: String) {
+        self.answers_count += 1;
+        self.domains.insert(address, (domain, self.answers_count));
+        self.answers.push_back((address, self.answers_count));
+        if self.domains.len() > MAX_DNS_TABLE_LEN {
+            // outdated answers are skipped until the least recent address is found
+            while let Some((address, number)) = self.answers.pop_front() {
+                if self.domains.get(&address).map(|(_, n)| *n) == Some(number) {
+                    self.domains.remove(&address);
+                    break;
+                }
+            }
+        }
+        if self.answers.len() > 2 * MAX_DNS_TABLE_LEN {
+            // addresses answered many times would fill the queue: outdated answers are dropped
+            let domains = &self.domains;
+            self.answers
+                .retain(|(address, number)| domains.get(address).map(|(_, n)| n) == Some(number));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn address(n: u32) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::from(n))
+    }
+
+    #[test]
+    fn outdated_answers_are_dropped() {
+        let mut dns_table = DnsTable::default();
+        for n in 0..=2 * MAX_DNS_TABLE_LEN {
+            dns_table.insert(address(7), format!("host{n}.example.com"));
+        }
+        assert!(dns_table.answers.len() <= 2 * MAX_DNS_TABLE_LEN);
+        assert_eq!(
+            dns_table.get(&address(7)).map(String::as_str),
+            Some(format!("host{}.example.com", 2 * MAX_DNS_TABLE_LEN).as_str())
+        );
+    }
+}
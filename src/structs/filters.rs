@@ -1,9 +1,16 @@
 //! Module defining the `Filters` struct, which represents the possible filters applicable on network traffic.
 
+use serde::{Deserialize, Serialize};
+
+use crate::enums::traffic_type::TrafficType;
+use crate::utility::manage_filters::{
+    parse_address_filter, parse_country_filter, parse_port_filter, validate_bpf_filter,
+};
 use crate::{AppProtocol, IpVersion, TransProtocol};
 
 /// Possible filters applicable to network traffic
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(default)]
 pub struct Filters {
     /// Internet Protocol version
     pub ip: IpVersion,
@@ -11,4 +18,48 @@ pub struct Filters {
     pub transport: TransProtocol,
     /// Application layer protocol
     pub application: AppProtocol,
+    /// Berkeley Packet Filter expression, applied by pcap before packets are parsed (empty means no filter)
+    pub bpf: String,
+    /// Comma separated IP addresses or networks in CIDR notation (empty means no filter)
+    pub addresses: String,
+    /// Comma separated ports or port ranges (empty means no filter)
+    pub ports: String,
+    /// Comma separated country codes of the remote addresses (empty means no filter)
+    pub countries: String,
+    /// Comma separated (parts of) server or domain names of the connections (empty means no filter);
+    /// the packets exchanged before a matching name is known are not considered
+    pub server_names: String,
+    /// Comma separated AS numbers or (parts of) organization names of the remote addresses
+    /// (empty means no filter); an ASN database is needed
+    pub organizations: String,
+    /// Accepted traffic types (empty means no filter)
+    pub traffic_types: Vec<TrafficType>,
+}
+
+impl Default for Filters {
+    fn default() -> Self {
+        Filters {
+            ip: IpVersion::Other,
+            transport: TransProtocol::Other,
+            application: AppProtocol::Other,
+            bpf: String::new(),
+            addresses: String::new(),
+            ports: String::new(),
+            countries: String::new(),
+            server_names: String::new(),
+            organizations: String::new(),
+            traffic_types: Vec::new(),
+        }
+    }
+}
+
+impl Filters {
+    /// Checks that the textual filters are well formed, returning the first error found
+    pub fn validate(&self) -> Result<(), String> {
+        parse_address_filter(&self.addresses)?;
+        parse_port_filter(&self.ports)?;
+        parse_country_filter(&self.countries)?;
+        validate_bpf_filter(&self.bpf)?;
+        Ok(())
+    }
 }
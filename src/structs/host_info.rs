@@ -0,0 +1,107 @@
+//! Module defining the `HostInfo` struct, which aggregates the traffic exchanged with a remote host.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Local};
+
+use crate::enums::traffic_type::TrafficType;
+use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::asn::Asn;
+use crate::structs::info_address_port_pair::InfoAddressPortPair;
+use crate::utility::manage_packets::get_remote_address;
+
+/// Struct representing the traffic exchanged with a remote host, over all its connections.
+#[derive(Clone, Debug)]
+pub struct HostInfo {
+    /// Network layer IPv4 or IPv6 address of the remote host
+    pub address: String,
+    /// Name of the remote host (server name, queried domain or resolved host name), if known
+    pub name: Option<String>,
+    /// Country of the remote host
+    pub country: String,
+    /// Autonomous system of the remote host, if an ASN database is given
+    pub asn: Option<Asn>,
+    /// Amount of bytes received from the host
+    pub bytes_in: u128,
+    /// Amount of bytes sent to the host
+    pub bytes_out: u128,
+    /// Amount of packets received from the host
+    pub packets_in: u128,
+    /// Amount of packets sent to the host
+    pub packets_out: u128,
+    /// Number of connections with the host
+    pub connections: usize,
+    /// Ports of the host involved in the connections
+    pub ports: BTreeSet<u16>,
+    /// First packet exchanged with the host
+    pub first_seen: DateTime<Local>,
+    /// Last packet exchanged with the host
+    pub last_seen: DateTime<Local>,
+}
+
+impl HostInfo {
+    /// Constructs a new `HostInfo` with the remote host of the given connection
+    pub fn new(key: &AddressPortPair, info: &InfoAddressPortPair) -> Self {
+        let mut host_info = HostInfo {
+            address: get_remote_address(info.traffic_type, key).to_string(),
+            name: None,
+            country: String::new(),
+            asn: None,
+            bytes_in: 0,
+            bytes_out: 0,
+            packets_in: 0,
+            packets_out: 0,
+            connections: 0,
+            ports: BTreeSet::new(),
+            first_seen: info.initial_timestamp,
+            last_seen: info.final_timestamp,
+        };
+        host_info.add_connection(key, info);
+        host_info
+    }
+
+    /// Adds the traffic of a connection with this host
+    pub fn add_connection(&mut self, key: &AddressPortPair, info: &InfoAddressPortPair) {
+        // the sent counters refer to the first address of the key, the local one only for outgoing traffic
+        if info.traffic_type.eq(&TrafficType::Outgoing) {
+            self.bytes_out += info.sent_bytes;
+            self.packets_out += info.sent_packets;
+            self.bytes_in += info.received_bytes;
+            self.packets_in += info.received_packets;
+            self.ports.insert(key.port2);
+        } else {
+            self.bytes_in += info.sent_bytes;
+            self.packets_in += info.sent_packets;
+            self.bytes_out += info.received_bytes;
+            self.packets_out += info.received_packets;
+            self.ports.insert(key.port1);
+        }
+        self.connections += 1;
+        self.first_seen = self.first_seen.min(info.initial_timestamp);
+        self.last_seen = self.last_seen.max(info.final_timestamp);
+        if self.country.is_empty() {
+            self.country = info.country.clone();
+        }
+        if self.name.is_none() {
+            self.name = info.get_remote_name().map(ToString::to_string);
+        }
+        if self.asn.is_none() {
+            self.asn = info.asn.clone();
+        }
+    }
+
+    /// Adds the traffic of another remote host (used to aggregate the hosts of an organization)
+    pub fn add_host(&mut self, other: &HostInfo) {
+        self.bytes_in += other.bytes_in;
+        self.bytes_out += other.bytes_out;
+        self.packets_in += other.packets_in;
+        self.packets_out += other.packets_out;
+        self.connections += other.connections;
+        self.ports.extend(&other.ports);
+        self.first_seen = self.first_seen.min(other.first_seen);
+        self.last_seen = self.last_seen.max(other.last_seen);
+        if self.country.is_empty() {
+            self.country = other.country.clone();
+        }
+    }
+}
@@ -2,10 +2,14 @@
 //! to keep track of statistics about the sniffed traffic.
 
 use chrono::{DateTime, Local};
+use std::collections::HashMap;
 use std::fmt;
-use std::ops::Add;
 
+use crate::enums::detection_method::DetectionMethod;
 use crate::enums::traffic_type::TrafficType;
+use crate::structs::asn::Asn;
+use crate::structs::rtt_stats::RttStats;
+use crate::structs::tcp_info::TcpInfo;
 use crate::utility::get_formatted_strings::get_formatted_bytes_string;
 use crate::AppProtocol;
 
@@ -18,18 +22,48 @@ pub struct InfoAddressPortPair {
     pub transmitted_bytes: u128,
     /// Amount of packets transmitted between the pair.
     pub transmitted_packets: u128,
+    /// Amount of bytes sent from the first address to the second one.
+    pub sent_bytes: u128,
+    /// Amount of packets sent from the first address to the second one.
+    pub sent_packets: u128,
+    /// Amount of bytes sent from the second address to the first one (only for bidirectional flows).
+    pub received_bytes: u128,
+    /// Amount of packets sent from the second address to the first one (only for bidirectional flows).
+    pub received_packets: u128,
     /// First occurrence of information exchange featuring the associate address:port pair as a source or destination.
     pub initial_timestamp: DateTime<Local>,
     /// Last occurrence of information exchange featuring the associate address:port pair as a source or destination.
     pub final_timestamp: DateTime<Local>,
     /// Set of application layer protocols carried by the associated address:port pair.
     pub app_protocol: AppProtocol,
+    /// Method used to detect the application layer protocol
+    pub detection_method: DetectionMethod,
     /// Check if source or destination is an IPv6 address longer than 25 bytes (used for Display
     pub very_long_address: bool,
     /// Flag to determine which of the address is that of the sniffed adapter or remote
     pub traffic_type: TrafficType,
     /// Country of the remote IP address
     pub country: String,
+    /// Autonomous system of the remote IP address, if an ASN database is given
+    pub asn: Option<Asn>,
+    /// City of the remote IP address, if a City database is given (empty if unknown)
+    pub city: String,
+    /// Latitude and longitude of the remote IP address, if a City database is given
+    pub coordinates: Option<(f64, f64)>,
+    /// Host name of the remote IP address, if resolved
+    pub hostname: Option<String>,
+    /// Domain name queried by the local host to reach the remote IP address, if seen in DNS traffic
+    pub domain: Option<String>,
+    /// Server name requested by the client (TLS Server Name Indication or HTTP Host header), if seen
+    pub server_name: Option<String>,
+    /// ICMP message types and codes exchanged (only for ICMP connections), with their packet count
+    pub icmp_types: HashMap<(u8, u8), u128>,
+    /// Network adapters on which the connection was seen (empty for capture files)
+    pub interfaces: Vec<String>,
+    /// Flags and sequence numbers statistics (only for TCP connections)
+    pub tcp_info: Option<TcpInfo>,
+    /// Round trip times measured from TCP handshakes and DNS queries, if any
+    pub rtt: Option<RttStats>,
     /// Integer corresponding to the index inside the connections map
     pub index: usize,
     /// Flag that indicates if this connection is marked as favourite
@@ -37,13 +71,51 @@ pub struct InfoAddressPortPair {
 }
 
 impl InfoAddressPortPair {
-    pub fn print_gui(&self) -> String {
-        self.to_string()
-            .get(0..37)
-            .unwrap()
-            .to_string()
-            .replace('|', "")
-            .add(&*format!(" {} ", &self.country))
+    /// Returns the name of the remote host: the requested server name if known,
+    /// otherwise the queried domain or the resolved host name
+    pub fn get_remote_name(&self) -> Option<&str> {
+        self.server_name
+            .as_deref()
+            .or(self.domain.as_deref())
+            .or(self.hostname.as_deref())
+    }
+}
+
+#[cfg(test)]
+impl InfoAddressPortPair {
+    /// Constructs a connection which hasn't exchanged any packet yet, to be customized by each test
+    pub fn new_for_tests(
+        app_protocol: AppProtocol,
+        traffic_type: TrafficType,
+        index: usize,
+    ) -> Self {
+        InfoAddressPortPair {
+            transmitted_bytes: 0,
+            transmitted_packets: 0,
+            sent_bytes: 0,
+            sent_packets: 0,
+            received_bytes: 0,
+            received_packets: 0,
+            initial_timestamp: Local::now(),
+            final_timestamp: Local::now(),
+            app_protocol,
+            detection_method: DetectionMethod::Port,
+            very_long_address: false,
+            traffic_type,
+            country: String::new(),
+            asn: None,
+            city: String::new(),
+            coordinates: None,
+            hostname: None,
+            domain: None,
+            server_name: None,
+            icmp_types: HashMap::new(),
+            interfaces: Vec::new(),
+            tcp_info: None,
+            rtt: None,
+            index,
+            is_favorite: false,
+        }
     }
 }
 
@@ -55,11 +127,13 @@ impl fmt::Display for InfoAddressPortPair {
             AppProtocol::Other => "Other".to_string(),
             _ => self.app_protocol.to_string(),
         };
+        // longer names would break the fixed width of the report rows
+        let app_string = fit_to_bytes(&app_string, 9, fmt::Alignment::Center);
 
         if self.very_long_address {
             write!(
                 f,
-                "{:^9}|{:>10}  |{:>10}  | {} | {} |",
+                "{}|{:>10}  |{:>10}  | {} | {} |",
                 app_string,
                 self.transmitted_packets,
                 bytes_string,
@@ -69,14 +143,77 @@ impl fmt::Display for InfoAddressPortPair {
         } else {
             write!(
                 f,
-                "{:^9}|{:>10}  |{:>10}  | {} | {} |{}",
+                "{}|{:>10}  |{:>10}  | {} | {} | {}",
                 app_string,
                 self.transmitted_packets,
                 bytes_string,
                 self.initial_timestamp.to_string().get(0..19).unwrap(),
                 self.final_timestamp.to_string().get(0..19).unwrap(),
-                " ".repeat(40)
+                // the space left by short addresses is filled with the remote host name
+                fit_to_bytes(
+                    self.get_remote_name().unwrap_or(""),
+                    39,
+                    fmt::Alignment::Left
+                )
             )
         }
     }
 }
+
+/// Truncates the string on a char boundary and pads it with spaces, so that it's exactly `width` bytes long.
+///
+/// The report file is updated seeking to byte offsets, thus columns can't be measured in chars.
+fn fit_to_bytes(string: &str, width: usize, alignment: fmt::Alignment) -> String {
+    let mut end = string.len().min(width);
+    while !string.is_char_boundary(end) {
+        end -= 1;
+    }
+    let padding = width - end;
+    let left_padding = match alignment {
+        fmt::Alignment::Left => 0,
+        fmt::Alignment::Center => padding / 2,
+        fmt::Alignment::Right => padding,
+    };
+    format!(
+        "{}{}{}",
+        " ".repeat(left_padding),
+        &string[..end],
+        " ".repeat(padding - left_padding)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_rows_have_a_fixed_byte_length() {
+        let ascii = InfoAddressPortPair {
+            server_name: Some("example.com".to_string()),
+            ..InfoAddressPortPair::new_for_tests(AppProtocol::HTTPS, TrafficType::Outgoing, 0)
+        };
+        let unicode = InfoAddressPortPair {
+            // 2-byte chars crossing the column limit
+            server_name: Some("ñ".repeat(30)),
+            initial_timestamp: ascii.initial_timestamp,
+            final_timestamp: ascii.final_timestamp,
+            ..InfoAddressPortPair::new_for_tests(
+                AppProtocol::Custom("Überwachung".to_string()),
+                TrafficType::Outgoing,
+                0,
+            )
+        };
+        let ascii_row = ascii.to_string();
+        let unicode_row = unicode.to_string();
+        assert_eq!(ascii_row.len(), unicode_row.len());
+        assert!(unicode_row.starts_with("Überwach|"));
+        assert!(unicode_row.ends_with(&format!(" {} ", "ñ".repeat(19))));
+    }
+
+    #[test]
+    fn strings_are_fit_to_bytes() {
+        assert_eq!(fit_to_bytes("DNS", 9, fmt::Alignment::Center), "   DNS   ");
+        assert_eq!(fit_to_bytes("ab", 5, fmt::Alignment::Right), "   ab");
+        assert_eq!(fit_to_bytes("aé", 2, fmt::Alignment::Left), "a ");
+    }
+}
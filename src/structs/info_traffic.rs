@@ -1,12 +1,16 @@
 //! Module defining the `ReportInfo` struct, useful to format the output report file and
 //! to keep track of statistics about the sniffed traffic.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::IpAddr;
 
 use indexmap::IndexMap;
 
+use crate::enums::byte_counting::ByteCounting;
 use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::dns_table::DnsTable;
 use crate::structs::info_address_port_pair::InfoAddressPortPair;
+use crate::structs::traffic_totals::TrafficTotals;
 use crate::AppProtocol;
 
 /// Struct to be shared between the threads in charge of parsing packets and update reports.
@@ -33,6 +37,20 @@ pub struct InfoTraffic {
     pub favorite_connections: HashSet<usize>,
     /// Flag to determine if data were exchanged from favorites in the last interval of time
     pub favorite_featured_last_interval: Option<(AddressPortPair, InfoAddressPortPair)>,
+    /// Path of the textual report of the current capture (None until it's created)
+    pub report_path: Option<String>,
+    /// Domain names learned from the sniffed DNS responses, for each resolved address
+    pub dns_table: DnsTable,
+    /// How the bytes of the packets are counted in the current capture
+    pub byte_counting: ByteCounting,
+    /// Flag indicating if the packets of both directions of a conversation share a single connection
+    pub bidirectional_flows: bool,
+    /// Chart intervals of a capture file not yet displayed, each with its second on the file's timeline
+    pub file_intervals: VecDeque<(u32, TrafficTotals)>,
+    /// Error that interrupted the reading of a capture file
+    pub capture_error: Option<String>,
+    /// Error that stopped the writing of the report of the current capture
+    pub report_error: Option<String>,
 }
 
 impl InfoTraffic {
@@ -50,6 +68,65 @@ impl InfoTraffic {
             app_protocols: HashMap::new(),
             favorite_connections: HashSet::new(),
             favorite_featured_last_interval: None,
+            report_path: None,
+            dns_table: DnsTable::default(),
+            byte_counting: ByteCounting::default(),
+            bidirectional_flows: false,
+            file_intervals: VecDeque::new(),
+            capture_error: None,
+            report_error: None,
         }
     }
+
+    /// Stores the domain names learned from a DNS response, forgetting the least recently answered
+    /// ones when the table is full (long captures see a lot of DNS traffic)
+    pub fn add_dns_answers(&mut self, answers: Vec<(IpAddr, String)>) {
+        for (address, domain) in answers {
+            self.dns_table.insert(address, domain);
+        }
+    }
+
+    /// Returns the current totals of the filtered traffic
+    pub fn get_totals(&self) -> TrafficTotals {
+        TrafficTotals {
+            sent_bytes: self.tot_sent_bytes,
+            received_bytes: self.tot_received_bytes,
+            sent_packets: self.tot_sent_packets,
+            received_packets: self.tot_received_packets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+    use crate::structs::dns_table::MAX_DNS_TABLE_LEN;
+
+    #[test]
+    fn dns_table_forgets_the_oldest_answers() {
+        let mut info_traffic = InfoTraffic::new();
+        let address = |n: u32| IpAddr::V4(Ipv4Addr::from(n));
+        info_traffic.add_dns_answers(
+            (0..=u32::try_from(MAX_DNS_TABLE_LEN).unwrap())
+                .map(|n| (address(n), format!("host{n}.example.com")))
+                .collect(),
+        );
+        assert_eq!(
+            (0..=u32::try_from(MAX_DNS_TABLE_LEN).unwrap())
+                .filter(|n| info_traffic.dns_table.get(&address(*n)).is_some())
+                .count(),
+            MAX_DNS_TABLE_LEN
+        );
+        assert!(info_traffic.dns_table.get(&address(0)).is_none());
+        // the answer is renewed, so the next eviction removes another address
+        info_traffic.add_dns_answers(vec![(address(1), "renewed.example.com".to_string())]);
+        info_traffic.add_dns_answers(vec![(address(u32::MAX), "new.example.com".to_string())]);
+        assert_eq!(
+            info_traffic.dns_table.get(&address(1)).map(String::as_str),
+            Some("renewed.example.com")
+        );
+        assert!(info_traffic.dns_table.get(&address(2)).is_none());
+    }
 }
@@ -0,0 +1,55 @@
+//! Module defining the `MmdbReaders` struct, which contains the geolocation databases
+//! opened for an analysis.
+
+use std::borrow::Cow;
+
+use maxminddb::Reader;
+
+use crate::structs::filters::Filters;
+use crate::structs::mmdb_settings::MmdbSettings;
+use crate::utility::countries::COUNTRY_MMDB;
+
+/// Reader of a MMDB file, either embedded in the executable or read from disk
+pub type MmdbReader = Reader<Cow<'static, [u8]>>;
+
+/// Struct containing the readers of the MMDB files, opened when an analysis is started
+/// (so that the files can be replaced between two analyses).
+pub struct MmdbReaders {
+    /// Reader of the country database (the embedded one, if no file is given)
+    pub country: MmdbReader,
+    /// Reader of the cities database, if given
+    pub city: Option<MmdbReader>,
+    /// Reader of the autonomous systems database, if given
+    pub asn: Option<MmdbReader>,
+}
+
+impl MmdbReaders {
+    /// Opens the databases specified in the settings.
+    ///
+    /// Returns an error if a database cannot be read, or if the filters need a database that is not given.
+    pub fn new(mmdb_settings: &MmdbSettings, filters: &Filters) -> Result<Self, String> {
+        let country = match open_mmdb(&mmdb_settings.country)? {
+            Some(reader) => reader,
+            None => Reader::from_source(Cow::Borrowed(COUNTRY_MMDB)).unwrap(),
+        };
+        let city = open_mmdb(&mmdb_settings.city)?;
+        let asn = open_mmdb(&mmdb_settings.asn)?;
+        if asn.is_none() && !filters.organizations.trim().is_empty() {
+            return Err("an ASN database is needed to filter by organization".to_string());
+        }
+        Ok(MmdbReaders { country, city, asn })
+    }
+}
+
+/// Opens the MMDB file at the given path (no file is opened if the path is empty)
+fn open_mmdb(path: &str) -> Result<Option<MmdbReader>, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Ok(None);
+    }
+    std::fs::read(path)
+        .map_err(|err| err.to_string())
+        .and_then(|buf| Reader::from_source(Cow::Owned(buf)).map_err(|err| err.to_string()))
+        .map(Some)
+        .map_err(|err| format!("cannot read the database {path}: {err}"))
+}
@@ -0,0 +1,19 @@
+//! Module defining the `MmdbSettings` struct, which represents the geolocation databases
+//! used to enrich the remote addresses.
+
+use serde::{Deserialize, Serialize};
+
+/// Used to contain the paths of the MMDB files set by the user
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct MmdbSettings {
+    /// Path of the database mapping addresses to countries, e.g. GeoLite2-Country
+    /// (empty means the database embedded in the executable)
+    pub country: String,
+    /// Path of the database mapping addresses to cities and coordinates, e.g. GeoLite2-City
+    /// (empty means no lookup)
+    pub city: String,
+    /// Path of the database mapping addresses to autonomous systems, e.g. GeoLite2-ASN
+    /// (empty means no lookup)
+    pub asn: String,
+}
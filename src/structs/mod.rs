@@ -1,11 +1,29 @@
 pub mod address_port_pair;
+pub mod asn;
+pub mod capture_settings;
 pub mod config;
+pub mod country_info;
+pub mod dns_table;
 pub mod filters;
+pub mod host_info;
 pub mod info_address_port_pair;
 pub mod info_traffic;
+pub mod mmdb_readers;
+pub mod mmdb_settings;
 pub mod notifications;
+pub mod packets_recorder;
 pub mod palette;
+pub mod port_mapping;
+pub mod recording;
+pub mod report_entry;
+pub mod report_settings;
+pub mod report_table;
+pub mod rtt_stats;
+pub mod rtt_tracker;
 pub mod runtime_data;
 pub mod sniffer;
 pub mod style_tuple;
+pub mod tcp_info;
 pub mod traffic_chart;
+pub mod traffic_totals;
+pub mod world_map;
@@ -0,0 +1,133 @@
+//! Module defining the `PacketsRecorder` struct, used by the thread parsing packets
+//! to save them to file while sniffing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Local;
+use pcap::{Capture, Linktype, Packet, Savefile};
+
+use crate::structs::recording::Recording;
+
+/// Size in bytes of the global header of a pcap file
+const PCAP_GLOBAL_HEADER_LEN: u64 = 24;
+/// Size in bytes of the header preceding each packet in a pcap file
+const PCAP_PACKET_HEADER_LEN: u64 = 16;
+
+/// Struct in charge of writing sniffed packets to pcap files, rotating them when they become too big or too old.
+///
+/// Files are written in the classic pcap format (libpcap can't write pcapng files).
+pub struct PacketsRecorder {
+    /// Data link type of the packets to be saved
+    linktype: Linktype,
+    /// Directory where files are saved
+    directory: String,
+    /// Name of the sniffed adapter, included in the file names (None for capture files)
+    interface: Option<String>,
+    /// File currently being written, if any
+    savefile: Option<Savefile>,
+    /// Instant at which the current file has been created
+    file_start: Instant,
+    /// Number of bytes written to the current file
+    file_size: u64,
+    /// Number of files created, included in the file names to keep them distinct
+    files_count: u32,
+}
+
+impl PacketsRecorder {
+    /// Constructs a new `PacketsRecorder` saving packets of the given data link type in the given directory.
+    pub fn new(linktype: Linktype, directory: String, interface: Option<&str>) -> Self {
+        PacketsRecorder {
+            linktype,
+            directory,
+            // adapter names can contain characters not allowed in file names
+            interface: interface.map(|name| {
+                name.chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect()
+            }),
+            savefile: None,
+            file_start: Instant::now(),
+            file_size: 0,
+            files_count: 0,
+        }
+    }
+
+    /// Saves the given packet to file, creating a new file if necessary.
+    pub fn record(&mut self, packet: &Packet, recording: Recording) {
+        let exceeded_size = recording
+            .get_max_file_bytes()
+            .is_some_and(|max_bytes| self.file_size >= max_bytes);
+        let exceeded_duration = recording
+            .get_max_file_duration()
+            .is_some_and(|max_duration| self.file_start.elapsed() >= max_duration);
+        if self.savefile.is_none() || exceeded_size || exceeded_duration {
+            self.stop();
+            fs::create_dir_all(&self.directory).unwrap_or(());
+            let path = get_record_path(
+                &self.directory,
+                self.interface.as_deref(),
+                &mut self.files_count,
+            );
+            self.savefile = Capture::dead(self.linktype)
+                .and_then(|dead_cap| dead_cap.savefile(path))
+                .ok();
+            self.file_start = Instant::now();
+            self.file_size = PCAP_GLOBAL_HEADER_LEN;
+        }
+        if let Some(savefile) = self.savefile.as_mut() {
+            savefile.write(packet);
+            self.file_size += PCAP_PACKET_HEADER_LEN + u64::from(packet.header.caplen);
+        }
+    }
+
+    /// Flushes and closes the file currently being written, if any.
+    pub fn stop(&mut self) {
+        if let Some(mut savefile) = self.savefile.take() {
+            savefile.flush().unwrap_or(());
+        }
+    }
+}
+
+/// Returns the path of a new file where packets will be saved, placed next to the reports
+///
+/// The name of the adapter distinguishes the files of the adapters sniffed at once,
+/// while the files count distinguishes the ones created in the same instant.
+fn get_record_path(directory: &str, interface: Option<&str>, files_count: &mut u32) -> PathBuf {
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S_%3f");
+    loop {
+        *files_count += 1;
+        let path = Path::new(directory).join(match interface {
+            Some(interface) => format!("capture_{interface}_{timestamp}_{files_count}.pcap"),
+            None => format!("capture_{timestamp}_{files_count}.pcap"),
+        });
+        // files of previous analyses are never overwritten
+        if !path.exists() {
+            return path;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_paths_are_distinct() {
+        let directory = std::env::temp_dir().join("sniffnet_record_paths_test");
+        fs::create_dir_all(&directory).unwrap();
+        let directory = directory.to_str().unwrap();
+        let mut files_count = 0;
+        let first = get_record_path(directory, Some("eth0"), &mut files_count);
+        let second = get_record_path(directory, Some("eth0"), &mut files_count);
+        assert_ne!(first, second);
+        assert!(first.to_str().unwrap().ends_with("_1.pcap"));
+        // an existing file is skipped, even if created by another analysis
+        fs::write(&first, []).unwrap();
+        let mut files_count = 0;
+        let third = get_record_path(directory, Some("eth0"), &mut files_count);
+        assert_ne!(first, third);
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+//! Module defining the `PortMapping` struct, which represents an association between
+//! transport layer ports and an application layer protocol defined by the user.
+
+use serde::{Deserialize, Serialize};
+
+use crate::utility::manage_filters::parse_port_filter;
+use crate::{AppProtocol, TransProtocol};
+
+/// Maximum length of the labels of the user defined protocols
+pub const MAX_LABEL_LEN: usize = 9;
+
+/// Association between a port range and an application layer protocol, defined by the user
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct PortMapping {
+    /// Lowest port of the range
+    pub lowest_port: u16,
+    /// Highest port of the range
+    pub highest_port: u16,
+    /// Transport layer protocol to which the mapping applies (Other means both TCP and UDP)
+    pub transport: TransProtocol,
+    /// Application layer protocol, parsed from the label inserted by the user
+    #[serde(rename = "label")]
+    pub app_protocol: AppProtocol,
+}
+
+impl PortMapping {
+    /// Constructs a new `PortMapping`, validating the port (or port range) and the label inserted by the user.
+    pub fn new(ports: &str, transport: TransProtocol, label: &str) -> Result<Self, String> {
+        let range = match parse_port_filter(ports)?.as_slice() {
+            [range] => range.clone(),
+            _ => return Err(format!("invalid port range: {ports}")),
+        };
+        let label = label.trim();
+        if label.is_empty()
+            || label.len() > MAX_LABEL_LEN
+            || !label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        {
+            return Err(format!(
+                "invalid label: {label} (up to {MAX_LABEL_LEN} letters, digits or -_.)"
+            ));
+        }
+        if label.eq_ignore_ascii_case("Other") {
+            return Err(format!("reserved label: {label}"));
+        }
+        Ok(PortMapping {
+            lowest_port: *range.start(),
+            highest_port: *range.end(),
+            transport,
+            app_protocol: AppProtocol::from(label.to_string()),
+        })
+    }
+
+    /// Returns the protocol of the mapping, if it applies to the given port and transport protocol
+    pub fn get_app_protocol(&self, port: u16, transport: TransProtocol) -> Option<AppProtocol> {
+        let transport_matches =
+            self.transport.eq(&TransProtocol::Other) || self.transport.eq(&transport);
+        if transport_matches && (self.lowest_port..=self.highest_port).contains(&port) {
+            Some(self.app_protocol.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the port range of the mapping, formatted as inserted by the user
+    pub fn get_ports_string(&self) -> String {
+        if self.lowest_port == self.highest_port {
+            self.lowest_port.to_string()
+        } else {
+            format!("{}-{}", self.lowest_port, self.highest_port)
+        }
+    }
+}
+
+/// Port mapping being inserted by the user in the settings, not yet validated
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PortMappingDraft {
+    /// Port or port range
+    pub ports: String,
+    /// Transport layer protocol
+    pub transport: TransProtocol,
+    /// Label of the application layer protocol
+    pub label: String,
+    /// Error found in the last attempt to add the mapping
+    pub error: Option<String>,
+}
+
+impl Default for PortMappingDraft {
+    fn default() -> Self {
+        PortMappingDraft {
+            ports: String::new(),
+            transport: TransProtocol::Other,
+            label: String::new(),
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_mappings() {
+        let mapping = PortMapping::new(" 9000-9010", TransProtocol::TCP, "Backup ").unwrap();
+        assert_eq!(mapping.get_ports_string(), "9000-9010");
+        assert_eq!(
+            mapping.get_app_protocol(9005, TransProtocol::TCP),
+            Some(AppProtocol::Custom("Backup".to_string()))
+        );
+        assert_eq!(mapping.get_app_protocol(9005, TransProtocol::UDP), None);
+        assert_eq!(mapping.get_app_protocol(9011, TransProtocol::TCP), None);
+
+        // known labels map to the corresponding protocols
+        let mapping = PortMapping::new("2222", TransProtocol::Other, "ssh").unwrap();
+        assert_eq!(
+            mapping.get_app_protocol(2222, TransProtocol::UDP),
+            Some(AppProtocol::SSH)
+        );
+    }
+
+    #[test]
+    fn invalid_mappings() {
+        assert!(PortMapping::new("", TransProtocol::TCP, "Backup").is_err());
+        assert!(PortMapping::new("80,81", TransProtocol::TCP, "Backup").is_err());
+        assert!(PortMapping::new("9000", TransProtocol::TCP, " ").is_err());
+        assert!(PortMapping::new("9000", TransProtocol::TCP, "my,label").is_err());
+        assert!(PortMapping::new("9000", TransProtocol::TCP, "VeryLongLabel").is_err());
+        assert!(PortMapping::new("9000", TransProtocol::TCP, "other").is_err());
+    }
+
+    #[test]
+    fn mappings_are_stored_with_their_label() {
+        let mapping = PortMapping::new("9000", TransProtocol::TCP, "Backup").unwrap();
+        let serialized = serde_json::to_string(&mapping).unwrap();
+        assert!(serialized.contains("\"label\":\"Backup\""));
+        assert_eq!(
+            serde_json::from_str::<PortMapping>(&serialized).unwrap(),
+            mapping
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Module defining the `Recording` struct, which represents the configuration used to save
+//! the sniffed packets to file.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Used to contain the packets recording configuration set by the user
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy)]
+#[serde(default)]
+pub struct Recording {
+    /// Flag to determine if packets are currently being saved to file (never stored in the config file)
+    #[serde(skip)]
+    pub active: bool,
+    /// Flag to determine if only the packets selected by the filters have to be saved
+    pub only_filtered: bool,
+    /// Maximum size in megabytes of a file; if exceeded, a new file is created (0 means no limit)
+    pub max_file_size: Option<u64>,
+    /// The last used Some value for the max_file_size field
+    pub previous_max_file_size: u64,
+    /// Maximum duration in seconds of a file; if exceeded, a new file is created (0 means no limit)
+    pub max_file_duration: Option<u64>,
+    /// The last used Some value for the max_file_duration field
+    pub previous_max_file_duration: u64,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Recording {
+            active: false,
+            only_filtered: false,
+            max_file_size: Some(100),
+            previous_max_file_size: 100,
+            max_file_duration: None,
+            previous_max_file_duration: 3600,
+        }
+    }
+}
+
+impl Recording {
+    /// Returns the maximum size in bytes of a file, if any
+    pub fn get_max_file_bytes(&self) -> Option<u64> {
+        self.max_file_size
+            .filter(|max_mb| *max_mb > 0)
+            .map(|max_mb| max_mb.saturating_mul(1_000_000))
+    }
+
+    /// Returns the maximum duration of a file, if any
+    pub fn get_max_file_duration(&self) -> Option<Duration> {
+        self.max_file_duration
+            .filter(|max_secs| *max_secs > 0)
+            .map(Duration::from_secs)
+    }
+}
@@ -0,0 +1,302 @@
+//! Module defining the `ReportEntry` struct, which represents a connection of the
+//! machine-readable reports (CSV and JSON lines).
+
+use std::net::IpAddr;
+
+use serde::Serialize;
+
+use crate::enums::detection_method::DetectionMethod;
+use crate::enums::tcp_state::TcpState;
+use crate::enums::traffic_type::TrafficType;
+use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::info_address_port_pair::InfoAddressPortPair;
+use crate::structs::tcp_info::TcpInfo;
+use crate::{AppProtocol, IpVersion, TransProtocol};
+
+/// Header line of the CSV report, listing the fields of a `ReportEntry`
+pub const CSV_HEADER: &str = "src_address,src_port,dst_address,dst_port,ip_version,\
+transport_protocol,app_protocol,detection_method,country,hostname,domain,server_name,traffic_type,\
+packets,bytes,initial_timestamp,final_timestamp,favorite,interfaces,sent_packets,sent_bytes,\
+received_packets,received_bytes,duration_ms,tcp_state,retransmissions,out_of_order,\
+rtt_min_ms,rtt_avg_ms,rtt_max_ms,asn,organization,city,latitude,longitude";
+
+/// Struct representing a connection of the machine-readable reports.
+#[derive(Serialize)]
+pub struct ReportEntry {
+    /// Network layer source address
+    pub src_address: String,
+    /// Transport layer source port
+    pub src_port: u16,
+    /// Network layer destination address
+    pub dst_address: String,
+    /// Transport layer destination port
+    pub dst_port: u16,
+    /// Internet Protocol version (Other if the addresses can't be parsed)
+    pub ip_version: IpVersion,
+    /// Transport layer protocol
+    pub transport_protocol: TransProtocol,
+    /// Application layer protocol
+    pub app_protocol: AppProtocol,
+    /// Method used to detect the application layer protocol
+    pub detection_method: DetectionMethod,
+    /// Country code of the remote address (empty if not available)
+    pub country: String,
+    /// Host name of the remote address (empty if not resolved)
+    pub hostname: String,
+    /// Domain name queried to reach the remote address (empty if not seen in DNS traffic)
+    pub domain: String,
+    /// Server name requested by the client (empty if not seen in TLS or HTTP traffic)
+    pub server_name: String,
+    /// Traffic type of the connection
+    pub traffic_type: TrafficType,
+    /// Amount of packets exchanged
+    pub packets: u128,
+    /// Amount of bytes exchanged
+    pub bytes: u128,
+    /// RFC 3339 timestamp of the first exchanged packet
+    pub initial_timestamp: String,
+    /// RFC 3339 timestamp of the last exchanged packet
+    pub final_timestamp: String,
+    /// Flag that indicates if the connection is marked as favorite
+    pub favorite: bool,
+    /// Network adapters on which the connection was seen, separated by semicolons
+    /// (empty for capture files)
+    pub interfaces: String,
+    /// Amount of packets sent from the source to the destination
+    pub sent_packets: u128,
+    /// Amount of bytes sent from the source to the destination
+    pub sent_bytes: u128,
+    /// Amount of packets sent back from the destination to the source (only for bidirectional flows)
+    pub received_packets: u128,
+    /// Amount of bytes sent back from the destination to the source (only for bidirectional flows)
+    pub received_bytes: u128,
+    /// Milliseconds elapsed between the first and the last exchanged packet
+    pub duration_ms: i64,
+    /// State of the connection (only for TCP)
+    pub tcp_state: Option<TcpState>,
+    /// Amount of retransmitted TCP segments
+    pub retransmissions: u128,
+    /// Amount of TCP segments arrived after a gap in the sequence numbers
+    pub out_of_order: u128,
+    /// Minimum round trip time in milliseconds (if measured)
+    pub rtt_min_ms: Option<f64>,
+    /// Average round trip time in milliseconds (if measured)
+    pub rtt_avg_ms: Option<f64>,
+    /// Maximum round trip time in milliseconds (if measured)
+    pub rtt_max_ms: Option<f64>,
+    /// Autonomous system number of the remote address (if an ASN database is given)
+    pub asn: Option<u32>,
+    /// Organization managing the autonomous system of the remote address (empty if not available)
+    pub organization: String,
+    /// City of the remote address (empty if not available)
+    pub city: String,
+    /// Latitude of the remote address (if a City database is given)
+    pub latitude: Option<f64>,
+    /// Longitude of the remote address (if a City database is given)
+    pub longitude: Option<f64>,
+}
+
+impl ReportEntry {
+    /// Constructs a new `ReportEntry` from an element of the connections map.
+    pub fn new(key: &AddressPortPair, val: &InfoAddressPortPair) -> Self {
+        ReportEntry {
+            src_address: key.address1.clone(),
+            src_port: key.port1,
+            dst_address: key.address2.clone(),
+            dst_port: key.port2,
+            ip_version: match key.address1.parse::<IpAddr>() {
+                Ok(IpAddr::V4(_)) => IpVersion::IPv4,
+                Ok(IpAddr::V6(_)) => IpVersion::IPv6,
+                Err(_) => IpVersion::Other,
+            },
+            transport_protocol: key.trans_protocol,
+            app_protocol: val.app_protocol.clone(),
+            detection_method: val.detection_method,
+            country: val.country.clone(),
+            hostname: val.hostname.clone().unwrap_or_default(),
+            domain: val.domain.clone().unwrap_or_default(),
+            server_name: val.server_name.clone().unwrap_or_default(),
+            traffic_type: val.traffic_type,
+            packets: val.transmitted_packets,
+            bytes: val.transmitted_bytes,
+            initial_timestamp: val.initial_timestamp.to_rfc3339(),
+            final_timestamp: val.final_timestamp.to_rfc3339(),
+            favorite: val.is_favorite,
+            interfaces: val.interfaces.join(";"),
+            sent_packets: val.sent_packets,
+            sent_bytes: val.sent_bytes,
+            received_packets: val.received_packets,
+            received_bytes: val.received_bytes,
+            duration_ms: (val.final_timestamp - val.initial_timestamp).num_milliseconds(),
+            tcp_state: val.tcp_info.as_ref().map(TcpInfo::get_state),
+            retransmissions: val
+                .tcp_info
+                .as_ref()
+                .map_or(0, |tcp_info| tcp_info.retransmissions),
+            out_of_order: val
+                .tcp_info
+                .as_ref()
+                .map_or(0, |tcp_info| tcp_info.out_of_order),
+            rtt_min_ms: val.rtt.map(|rtt| rtt.min.as_secs_f64() * 1000.0),
+            rtt_avg_ms: val.rtt.map(|rtt| rtt.avg().as_secs_f64() * 1000.0),
+            rtt_max_ms: val.rtt.map(|rtt| rtt.max.as_secs_f64() * 1000.0),
+            asn: val.asn.as_ref().map(|asn| asn.number),
+            organization: val
+                .asn
+                .as_ref()
+                .map(|asn| asn.name.clone())
+                .unwrap_or_default(),
+            city: val.city.clone(),
+            latitude: val.coordinates.map(|(latitude, _)| latitude),
+            longitude: val.coordinates.map(|(_, longitude)| longitude),
+        }
+    }
+
+    /// Returns the CSV line representing this entry (fields are in the same order of `CSV_HEADER`)
+    ///
+    /// Free-text fields are always quoted, since most of them come from the network or from the user.
+    pub fn to_csv_line(&self) -> String {
+        format!(
+            "{},{},{},{},{:?},{:?},{},{},{},{},{},{},{:?},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            quote_csv_field(&self.src_address),
+            self.src_port,
+            quote_csv_field(&self.dst_address),
+            self.dst_port,
+            self.ip_version,
+            self.transport_protocol,
+            quote_csv_field(&String::from(self.app_protocol.clone())),
+            self.detection_method,
+            quote_csv_field(&self.country),
+            quote_csv_field(&self.hostname),
+            quote_csv_field(&self.domain),
+            quote_csv_field(&self.server_name),
+            self.traffic_type,
+            self.packets,
+            self.bytes,
+            quote_csv_field(&self.initial_timestamp),
+            quote_csv_field(&self.final_timestamp),
+            self.favorite,
+            quote_csv_field(&self.interfaces),
+            self.sent_packets,
+            self.sent_bytes,
+            self.received_packets,
+            self.received_bytes,
+            self.duration_ms,
+            self.tcp_state.map(|state| state.to_string()).unwrap_or_default(),
+            self.retransmissions,
+            self.out_of_order,
+            format_optional_ms(self.rtt_min_ms),
+            format_optional_ms(self.rtt_avg_ms),
+            format_optional_ms(self.rtt_max_ms),
+            self.asn.map(|asn| asn.to_string()).unwrap_or_default(),
+            quote_csv_field(&self.organization),
+            quote_csv_field(&self.city),
+            self.latitude.map(|lat| lat.to_string()).unwrap_or_default(),
+            self.longitude.map(|lon| lon.to_string()).unwrap_or_default(),
+        )
+    }
+}
+
+/// Encloses a CSV field in double quotes, escaping the double quotes it contains
+/// (commas and line breaks are allowed inside quoted fields)
+fn quote_csv_field(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+/// Formats a number of milliseconds for the CSV report (empty if not available)
+fn format_optional_ms(ms: Option<f64>) -> String {
+    ms.map(|ms| format!("{ms:.3}")).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn entry(address: &str, info: &InfoAddressPortPair) -> ReportEntry {
+        let key = AddressPortPair::new(
+            "192.168.1.2".to_string(),
+            50000,
+            address.to_string(),
+            443,
+            TransProtocol::TCP,
+        );
+        ReportEntry::new(&key, info)
+    }
+
+    fn entry_from(address: &str) -> ReportEntry {
+        let key = AddressPortPair::new(
+            address.to_string(),
+            50000,
+            address.to_string(),
+            443,
+            TransProtocol::TCP,
+        );
+        let info = InfoAddressPortPair::new_for_tests(AppProtocol::HTTPS, TrafficType::Outgoing, 0);
+        ReportEntry::new(&key, &info)
+    }
+
+    #[test]
+    fn ip_version_is_parsed_from_the_addresses() {
+        assert_eq!(entry_from("192.168.1.2").ip_version, IpVersion::IPv4);
+        assert_eq!(entry_from("2001:db8::1").ip_version, IpVersion::IPv6);
+        assert_eq!(entry_from("::ffff:10.0.0.1").ip_version, IpVersion::IPv6);
+        // addresses that can't be parsed are kept as they are
+        let entry = entry_from("fe80::1%eth0");
+        assert_eq!(entry.ip_version, IpVersion::Other);
+        assert!(entry
+            .to_csv_line()
+            .starts_with(r#""fe80::1%eth0",50000,"fe80::1%eth0",443,Other,"#));
+    }
+
+    #[test]
+    fn csv_free_text_is_quoted() {
+        let info = InfoAddressPortPair {
+            hostname: Some("host,name".to_string()),
+            server_name: Some(r#"say "hi""#.to_string()),
+            city: "New\nYork".to_string(),
+            ..InfoAddressPortPair::new_for_tests(
+                AppProtocol::Custom("My, app".to_string()),
+                TrafficType::Outgoing,
+                0,
+            )
+        };
+        let line = entry("1.1.1.1", &info).to_csv_line();
+        assert!(line.contains(r#","My, app",Port,"","host,name","","say ""hi""",Outgoing,"#));
+        assert!(line.contains(",\"New\nYork\","));
+        // commas outside quotes separate exactly the columns of the header
+        let mut quoted = false;
+        let separators = line
+            .chars()
+            .filter(|c| {
+                if *c == '"' {
+                    quoted = !quoted;
+                }
+                *c == ',' && !quoted
+            })
+            .count();
+        assert_eq!(separators + 1, CSV_HEADER.split(',').count());
+    }
+
+    #[test]
+    fn json_has_the_csv_fields() {
+        let info = InfoAddressPortPair {
+            hostname: Some("host,name".to_string()),
+            ..InfoAddressPortPair::new_for_tests(AppProtocol::HTTPS, TrafficType::Outgoing, 0)
+        };
+        let value = serde_json::to_value(entry("1.1.1.1", &info)).unwrap();
+        let keys: HashSet<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(keys, CSV_HEADER.split(',').collect());
+        assert_eq!(value["dst_address"], "1.1.1.1");
+        assert_eq!(value["app_protocol"], "HTTPS");
+        assert_eq!(value["hostname"], "host,name");
+        assert_eq!(value["domain"], "");
+        assert!(value["rtt_min_ms"].is_null());
+    }
+}
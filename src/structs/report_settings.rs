@@ -0,0 +1,44 @@
+//! Module defining the `ReportSettings` struct, which represents the configuration of the
+//! files written by the thread in charge of updating the output report.
+
+use std::env;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Used to contain the report configuration set by the user
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct ReportSettings {
+    /// Flag to determine if the report has also to be exported as CSV
+    pub csv: bool,
+    /// Flag to determine if the report has also to be exported as JSON lines
+    pub json: bool,
+    /// Directory where reports and recorded packets are saved
+    pub directory: String,
+    /// Maximum number of sessions whose reports are kept in the directory (older ones are deleted,
+    /// while packet recordings are always kept)
+    pub max_sessions: u64,
+}
+
+impl Default for ReportSettings {
+    fn default() -> Self {
+        ReportSettings {
+            csv: false,
+            json: false,
+            directory: get_default_report_directory(),
+            max_sessions: 10,
+        }
+    }
+}
+
+/// Returns the default report directory: `sniffnet_report` inside the user's home directory
+fn get_default_report_directory() -> String {
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string());
+    Path::new(&home)
+        .join("sniffnet_report")
+        .to_string_lossy()
+        .to_string()
+}
@@ -0,0 +1,126 @@
+//! Module defining the `ReportTable` struct, which represents the state of the connections table
+//! displayed in the overview.
+
+use crate::enums::report_column::ReportColumn;
+
+/// Number of connections displayed in each page of the table
+pub const CONNECTIONS_PER_PAGE: usize = 20;
+
+/// Struct containing the sorting, the search and the position of the connections table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportTable {
+    /// Column by which the connections are sorted
+    pub sort_column: ReportColumn,
+    /// Whether the connections are sorted from the highest value
+    pub descending: bool,
+    /// Text to be searched in the connections (empty means all the connections)
+    pub search: String,
+    /// Displayed page, starting from 0
+    pub page: usize,
+    /// Index of the connection selected with the keyboard, if any
+    pub selected: Option<usize>,
+}
+
+impl Default for ReportTable {
+    fn default() -> Self {
+        ReportTable {
+            sort_column: ReportColumn::LastSeen,
+            descending: true,
+            search: String::new(),
+            page: 0,
+            selected: None,
+        }
+    }
+}
+
+impl ReportTable {
+    /// Sorts the table by the given column, reversing the order if it was already sorted by it
+    pub fn sort_by(&mut self, column: ReportColumn) {
+        if self.sort_column.eq(&column) {
+            self.descending = !self.descending;
+        } else {
+            self.sort_column = column;
+            self.descending = column.is_descending_by_default();
+        }
+        self.page = 0;
+    }
+
+    /// Selects the connection following the selected one in the displayed page
+    /// (the first one if nothing is selected)
+    ///
+    /// # Arguments
+    ///
+    /// * `indexes` - Indexes of the connections displayed in the current page.
+    pub fn select_next(&mut self, indexes: &[usize]) {
+        let position = self.get_selected_position(indexes);
+        let next = position.map_or(0, |position| (position + 1).min(indexes.len() - 1));
+        self.selected = indexes.get(next).copied();
+    }
+
+    /// Selects the connection preceding the selected one in the displayed page
+    /// (the last one if nothing is selected)
+    ///
+    /// # Arguments
+    ///
+    /// * `indexes` - Indexes of the connections displayed in the current page.
+    pub fn select_previous(&mut self, indexes: &[usize]) {
+        let position = self.get_selected_position(indexes);
+        let previous = position.map_or(indexes.len().saturating_sub(1), |position| {
+            position.saturating_sub(1)
+        });
+        self.selected = indexes.get(previous).copied();
+    }
+
+    /// Returns the position of the selected connection in the displayed page, if it's there
+    fn get_selected_position(&self, indexes: &[usize]) -> Option<usize> {
+        let selected = self.selected?;
+        indexes.iter().position(|index| *index == selected)
+    }
+}
+
+/// Returns the number of pages needed to display the given number of connections (at least one)
+pub fn get_pages_number(connections: usize) -> usize {
+    connections.saturating_sub(1) / CONNECTIONS_PER_PAGE + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_by_columns() {
+        let mut table = ReportTable {
+            page: 3,
+            ..ReportTable::default()
+        };
+        table.sort_by(ReportColumn::LastSeen);
+        assert_eq!(table.sort_column, ReportColumn::LastSeen);
+        assert!(!table.descending);
+        assert_eq!(table.page, 0);
+        table.sort_by(ReportColumn::SrcAddress);
+        assert_eq!(table.sort_column, ReportColumn::SrcAddress);
+        assert!(!table.descending);
+        table.sort_by(ReportColumn::Bytes);
+        assert!(table.descending);
+    }
+
+    #[test]
+    fn keyboard_selection() {
+        let indexes = [7, 3, 12];
+        let mut table = ReportTable::default();
+        table.select_next(&indexes);
+        assert_eq!(table.selected, Some(7));
+        table.select_next(&indexes);
+        table.select_next(&indexes);
+        table.select_next(&indexes);
+        assert_eq!(table.selected, Some(12));
+        table.select_previous(&indexes);
+        assert_eq!(table.selected, Some(3));
+        // the selected connection is no longer displayed
+        table.selected = Some(100);
+        table.select_previous(&indexes);
+        assert_eq!(table.selected, Some(12));
+        table.select_next(&[]);
+        assert_eq!(table.selected, None);
+    }
+}
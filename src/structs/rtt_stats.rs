@@ -0,0 +1,41 @@
+//! Module defining the `RttStats` struct, which summarizes the round trip times measured on a connection.
+
+use std::time::Duration;
+
+/// Struct representing the round trip times measured on a connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RttStats {
+    /// Minimum round trip time
+    pub min: Duration,
+    /// Maximum round trip time
+    pub max: Duration,
+    /// Sum of the round trip times, used to compute the average
+    total: Duration,
+    /// Number of measurements
+    pub samples: u32,
+}
+
+impl RttStats {
+    /// Constructs a new `RttStats` with a first measurement
+    pub fn new(rtt: Duration) -> Self {
+        RttStats {
+            min: rtt,
+            max: rtt,
+            total: rtt,
+            samples: 1,
+        }
+    }
+
+    /// Adds a new measurement
+    pub fn add(&mut self, rtt: Duration) {
+        self.min = self.min.min(rtt);
+        self.max = self.max.max(rtt);
+        self.total += rtt;
+        self.samples += 1;
+    }
+
+    /// Returns the average round trip time
+    pub fn avg(&self) -> Duration {
+        self.total / self.samples
+    }
+}
@@ -0,0 +1,158 @@
+//! Module defining the `RttTracker` struct, which matches requests and responses
+//! to estimate the round trip time of the connections.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::enums::rtt_probe::RttProbe;
+use crate::structs::address_port_pair::AddressPortPair;
+
+/// Maximum number of requests waiting for a response
+const MAX_PENDING_REQUESTS: usize = 4096;
+/// Time after which a request without response is discarded
+const REQUEST_TIMEOUT_SECONDS: i64 = 10;
+
+/// Struct keeping track of the requests waiting for a response, for each sniffed adapter.
+#[derive(Default)]
+pub struct RttTracker {
+    /// Requests waiting for a response, with the timestamp of their packet
+    pending: HashMap<(AddressPortPair, RttProbe), DateTime<Local>>,
+    /// Requests in order of arrival, including those already answered
+    /// (which are just skipped when they reach the front)
+    arrivals: VecDeque<((AddressPortPair, RttProbe), DateTime<Local>)>,
+}
+
+impl RttTracker {
+    /// Stores a request, whose response is expected in the opposite direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The source and destination of the request packet.
+    ///
+    /// * `probe` - The kind of request.
+    ///
+    /// * `timestamp` - The timestamp of the request packet.
+    pub fn request(&mut self, key: &AddressPortPair, probe: RttProbe, timestamp: DateTime<Local>) {
+        // requests never answered are dropped, to keep memory usage bounded
+        // (the oldest ones go first if too many are recent)
+        while let Some((_, request_timestamp)) = self.arrivals.front() {
+            if self.arrivals.len() < MAX_PENDING_REQUESTS
+                && (timestamp - *request_timestamp).num_seconds() < REQUEST_TIMEOUT_SECONDS
+            {
+                break;
+            }
+            let (request, request_timestamp) = self.arrivals.pop_front().unwrap();
+            // the same request could have been sent again in the meanwhile
+            if self.pending.get(&request) == Some(&request_timestamp) {
+                self.pending.remove(&request);
+            }
+        }
+        self.pending.insert((key.clone(), probe), timestamp);
+        self.arrivals.push_back(((key.clone(), probe), timestamp));
+    }
+
+    /// Matches a response with the corresponding request, returning the round trip time if found.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The source and destination of the response packet.
+    ///
+    /// * `probe` - The kind of response.
+    ///
+    /// * `timestamp` - The timestamp of the response packet.
+    pub fn response(
+        &mut self,
+        key: &AddressPortPair,
+        probe: RttProbe,
+        timestamp: DateTime<Local>,
+    ) -> Option<Duration> {
+        let request_timestamp = self.pending.remove(&(key.reversed(), probe))?;
+        (timestamp - request_timestamp).to_std().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as ChronoDuration;
+
+    use super::*;
+    use crate::TransProtocol;
+
+    #[test]
+    fn responses_are_matched_with_requests() {
+        let query = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            50000,
+            "8.8.8.8".to_string(),
+            53,
+            TransProtocol::UDP,
+        );
+        let response = AddressPortPair::new(
+            "8.8.8.8".to_string(),
+            53,
+            "192.168.1.10".to_string(),
+            50000,
+            TransProtocol::UDP,
+        );
+        let start = Local::now();
+        let mut rtt_tracker = RttTracker::default();
+        rtt_tracker.request(&query, RttProbe::DnsQuery(7), start);
+        // different transaction ID
+        assert_eq!(
+            rtt_tracker.response(&response, RttProbe::DnsQuery(8), start),
+            None
+        );
+        assert_eq!(
+            rtt_tracker.response(
+                &response,
+                RttProbe::DnsQuery(7),
+                start + ChronoDuration::milliseconds(25)
+            ),
+            Some(Duration::from_millis(25))
+        );
+        // each request is matched only once
+        assert_eq!(
+            rtt_tracker.response(&response, RttProbe::DnsQuery(7), start),
+            None
+        );
+    }
+
+    #[test]
+    fn unanswered_requests_are_dropped() {
+        let query = |port: u16| {
+            AddressPortPair::new(
+                "192.168.1.10".to_string(),
+                port,
+                "8.8.8.8".to_string(),
+                53,
+                TransProtocol::UDP,
+            )
+        };
+        let start = Local::now();
+        let mut rtt_tracker = RttTracker::default();
+        for port in 0..u16::try_from(2 * MAX_PENDING_REQUESTS).unwrap() {
+            rtt_tracker.request(&query(port), RttProbe::DnsQuery(1), start);
+        }
+        assert_eq!(rtt_tracker.pending.len(), MAX_PENDING_REQUESTS);
+        assert_eq!(rtt_tracker.arrivals.len(), MAX_PENDING_REQUESTS);
+        // the oldest requests make room for the new ones
+        assert!(!rtt_tracker
+            .pending
+            .contains_key(&(query(0), RttProbe::DnsQuery(1))));
+
+        // expired requests are dropped as soon as a new one arrives
+        let later = start + ChronoDuration::seconds(REQUEST_TIMEOUT_SECONDS);
+        rtt_tracker.request(&query(0), RttProbe::DnsQuery(2), later);
+        assert_eq!(rtt_tracker.pending.len(), 1);
+        assert_eq!(
+            rtt_tracker.response(
+                &query(0).reversed(),
+                RttProbe::DnsQuery(2),
+                later + ChronoDuration::milliseconds(5)
+            ),
+            Some(Duration::from_millis(5))
+        );
+    }
+}
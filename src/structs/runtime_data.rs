@@ -4,6 +4,8 @@ use std::collections::{HashMap, VecDeque};
 
 use crate::enums::logged_notification::LoggedNotification;
 use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::country_info::CountryInfo;
+use crate::structs::host_info::HostInfo;
 use crate::structs::info_address_port_pair::InfoAddressPortPair;
 use crate::AppProtocol;
 
@@ -23,8 +25,14 @@ pub struct RunTimeData {
     pub received_packets: VecDeque<(u32, i64)>,
     /// Application protocol with the respective number of filtered packets
     pub app_protocols: HashMap<AppProtocol, u128>,
-    /// Connection entries to be displayed in report column
+    /// Connection entries to be displayed in the current page of the report table
     pub report_vec: Vec<(AddressPortPair, InfoAddressPortPair)>,
+    /// Number of connections matching the report table search (in all its pages)
+    pub report_len: usize,
+    /// Remote hosts to be displayed in the hosts page, with the traffic of all their connections
+    pub hosts_vec: Vec<HostInfo>,
+    /// Countries to be displayed in the countries page, with the traffic of all their hosts
+    pub countries_vec: Vec<CountryInfo>,
     /// Total sent bytes filtered
     pub tot_sent_bytes: u128,
     /// Total received bytes filtered
@@ -69,6 +77,9 @@ impl RunTimeData {
             received_packets: VecDeque::default(),
             app_protocols: HashMap::default(),
             report_vec: Vec::default(),
+            report_len: 0,
+            hosts_vec: Vec::default(),
+            countries_vec: Vec::default(),
             tot_sent_bytes: 0,
             tot_received_bytes: 0,
             tot_sent_packets: 0,
@@ -6,13 +6,23 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, Condvar, Mutex};
 
+use crate::enums::capture_source::CaptureSource;
+use crate::enums::country_sorting::CountrySorting;
+use crate::enums::host_sorting::HostSorting;
 use crate::enums::language::Language;
 use crate::enums::overlay::MyOverlay;
 use crate::enums::report_type::ReportType;
 use crate::enums::running_page::RunningPage;
 use crate::enums::status::Status;
+use crate::structs::capture_settings::CaptureSettings;
 use crate::structs::filters::Filters;
+use crate::structs::mmdb_settings::MmdbSettings;
 use crate::structs::notifications::Notifications;
+use crate::structs::port_mapping::{PortMapping, PortMappingDraft};
+use crate::structs::recording::Recording;
+use crate::structs::report_settings::ReportSettings;
+use crate::structs::report_table::ReportTable;
+use crate::structs::world_map::WorldMap;
 use crate::{InfoTraffic, RunTimeData, StyleType, TrafficChart};
 
 /// Struct on which the gui is based
@@ -27,12 +37,18 @@ pub struct Sniffer {
     pub status_pair: Arc<(Mutex<Status>, Condvar)>,
     /// Traffic data displayed in GUI
     pub runtime_data: Rc<RefCell<RunTimeData>>,
-    /// Network adapter to be analyzed
-    pub device: Device,
+    /// Network adapters to be analyzed (at least one)
+    pub devices: Vec<Device>,
+    /// Source of the analyzed packets (network adapter or capture file)
+    pub capture_source: CaptureSource,
+    /// Path of the capture file to be analyzed
+    pub capture_file: String,
     /// Active filters on the observed traffic
     pub filters: Filters,
     /// Signals if a pcap error occurred
     pub pcap_error: Option<String>,
+    /// Error of the BPF expression being inserted; the analysis can't be started while present
+    pub bpf_error: Option<String>,
     /// Application style (only values Day and Night are possible for this field)
     pub style: StyleType,
     /// Waiting string
@@ -41,6 +57,20 @@ pub struct Sniffer {
     pub traffic_chart: TrafficChart,
     /// Report type to be displayed
     pub report_type: ReportType,
+    /// Sorting, search and position of the connections table
+    pub report_table: ReportTable,
+    /// Adapter whose connections are displayed in the report (None means all the sniffed adapters)
+    pub report_interface: Option<String>,
+    /// Ordering of the remote hosts table
+    pub host_sorting: HostSorting,
+    /// Whether the remote hosts are aggregated by organization in the hosts table
+    pub group_by_organization: bool,
+    /// Country whose connections are displayed in the report (None means all the countries)
+    pub report_country: Option<String>,
+    /// Ordering of the countries table
+    pub country_sorting: CountrySorting,
+    /// World map displayed in the countries page
+    pub world_map: WorldMap,
     /// Currently displayed overlay; None if no overlay is displayed
     pub overlay: Option<MyOverlay>,
     /// Contains the notifications configuration set by the user
@@ -49,4 +79,16 @@ pub struct Sniffer {
     pub running_page: RunningPage,
     /// Language used in the GUI
     pub language: Language,
+    /// Contains the packets recording configuration, shared with the thread parsing packets
+    pub recording: Arc<Mutex<Recording>>,
+    /// Contains the report configuration, shared with the thread writing the report
+    pub report_settings: Arc<Mutex<ReportSettings>>,
+    /// Contains the configuration used to open the network adapters
+    pub capture_settings: CaptureSettings,
+    /// Paths of the geolocation databases set by the user
+    pub mmdb_settings: MmdbSettings,
+    /// Port-to-protocol mappings defined by the user
+    pub port_mappings: Vec<PortMapping>,
+    /// Port mapping being inserted by the user in the settings
+    pub port_mapping_draft: PortMappingDraft,
 }
@@ -0,0 +1,181 @@
+//! Module defining the `TcpInfo` struct, which keeps track of the flags and sequence numbers
+//! of the segments of a TCP connection.
+
+use crate::enums::tcp_state::TcpState;
+
+/// Relevant fields of a TCP segment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TcpSegment {
+    /// Sequence number
+    pub sequence_number: u32,
+    /// Length of the payload, as declared by the IP header
+    pub payload_len: u32,
+    pub syn: bool,
+    pub ack: bool,
+    pub fin: bool,
+    pub rst: bool,
+}
+
+/// Struct representing the TCP statistics of a connection.
+///
+/// The state is derived more accurately with bidirectional flows,
+/// where the segments of both directions are seen by the same connection.
+#[derive(Clone, Debug, Default)]
+pub struct TcpInfo {
+    /// Number of SYN segments (connection requests)
+    pub syn: u128,
+    /// Number of SYN-ACK segments (connection acceptances)
+    pub syn_ack: u128,
+    /// Number of FIN segments
+    pub fin: u128,
+    /// Number of RST segments
+    pub rst: u128,
+    /// Number of segments whose data had already been sent
+    pub retransmissions: u128,
+    /// Number of segments arrived after a gap in the sequence numbers
+    pub out_of_order: u128,
+    /// Flag indicating if an acknowledgement followed a SYN (the handshake was completed)
+    acked_after_syn: bool,
+    /// Next sequence number expected in each direction (that of the key first, then the opposite one)
+    next_sequence_numbers: [Option<u32>; 2],
+}
+
+impl TcpInfo {
+    /// Updates the statistics with a new segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - The segment exchanged.
+    ///
+    /// * `is_reversed` - Flag indicating if the segment travels in the opposite direction of the connection key.
+    pub fn update(&mut self, segment: TcpSegment, is_reversed: bool) {
+        match (segment.syn, segment.ack) {
+            (true, false) => self.syn += 1,
+            (true, true) => self.syn_ack += 1,
+            (false, true) if self.syn > 0 && !segment.rst => self.acked_after_syn = true,
+            _ => {}
+        }
+        if segment.fin {
+            self.fin += 1;
+        }
+        if segment.rst {
+            self.rst += 1;
+            // the sequence number of resets doesn't advance the stream
+            return;
+        }
+
+        // SYN and FIN flags consume a sequence number
+        let len = segment.payload_len + u32::from(segment.syn) + u32::from(segment.fin);
+        let next_sequence_number = &mut self.next_sequence_numbers[usize::from(is_reversed)];
+        match next_sequence_number {
+            None => {
+                *next_sequence_number = Some(segment.sequence_number.wrapping_add(len));
+            }
+            Some(expected) => {
+                if len == 0 {
+                    // pure acknowledgements don't carry data
+                    return;
+                }
+                // sequence numbers wrap around: compare them by their signed distance
+                let distance = segment.sequence_number.wrapping_sub(*expected) as i32;
+                if distance < 0 {
+                    self.retransmissions += 1;
+                } else {
+                    if distance > 0 {
+                        self.out_of_order += 1;
+                    }
+                    *expected = segment.sequence_number.wrapping_add(len);
+                }
+            }
+        }
+    }
+
+    /// Returns the state of the connection, derived from the flags seen so far
+    pub fn get_state(&self) -> TcpState {
+        let handshake_completed = self.syn_ack > 0 || self.acked_after_syn;
+        if self.rst > 0 {
+            if self.syn > 0 && !handshake_completed {
+                TcpState::Refused
+            } else {
+                TcpState::Reset
+            }
+        } else if self.fin > 0 {
+            TcpState::Closed
+        } else if handshake_completed {
+            TcpState::Established
+        } else if self.syn > 0 {
+            TcpState::SynSent
+        } else {
+            TcpState::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(sequence_number: u32, payload_len: u32, flags: &str) -> TcpSegment {
+        TcpSegment {
+            sequence_number,
+            payload_len,
+            syn: flags.contains('S'),
+            ack: flags.contains('A'),
+            fin: flags.contains('F'),
+            rst: flags.contains('R'),
+        }
+    }
+
+    #[test]
+    fn established_and_closed_connection() {
+        let mut tcp_info = TcpInfo::default();
+        tcp_info.update(segment(100, 0, "S"), false);
+        assert_eq!(tcp_info.get_state(), TcpState::SynSent);
+        tcp_info.update(segment(5000, 0, "SA"), true);
+        tcp_info.update(segment(101, 0, "A"), false);
+        assert_eq!(tcp_info.get_state(), TcpState::Established);
+        tcp_info.update(segment(101, 500, "A"), false);
+        tcp_info.update(segment(5001, 1000, "A"), true);
+        tcp_info.update(segment(601, 0, "FA"), false);
+        assert_eq!(tcp_info.get_state(), TcpState::Closed);
+        assert_eq!(tcp_info.retransmissions, 0);
+        assert_eq!(tcp_info.out_of_order, 0);
+    }
+
+    #[test]
+    fn refused_and_reset_connections() {
+        let mut refused = TcpInfo::default();
+        refused.update(segment(100, 0, "S"), false);
+        refused.update(segment(0, 0, "RA"), true);
+        assert_eq!(refused.get_state(), TcpState::Refused);
+
+        // only the client's direction is seen, as without bidirectional flows
+        let mut reset = TcpInfo::default();
+        reset.update(segment(100, 0, "S"), false);
+        reset.update(segment(101, 0, "A"), false);
+        reset.update(segment(101, 0, "R"), false);
+        assert_eq!(reset.get_state(), TcpState::Reset);
+
+        let mut midstream = TcpInfo::default();
+        midstream.update(segment(7000, 100, "A"), false);
+        assert_eq!(midstream.get_state(), TcpState::Unknown);
+    }
+
+    #[test]
+    fn retransmissions_and_out_of_order_segments() {
+        let mut tcp_info = TcpInfo::default();
+        tcp_info.update(segment(u32::MAX - 99, 100, "A"), false);
+        // in order, across the wrap around of sequence numbers
+        tcp_info.update(segment(0, 100, "A"), false);
+        // retransmitted
+        tcp_info.update(segment(0, 100, "A"), false);
+        // the segment starting at 200 was lost or delayed
+        tcp_info.update(segment(300, 100, "A"), false);
+        // the delayed segment can't be told apart from a retransmission
+        tcp_info.update(segment(200, 100, "A"), false);
+        // the other direction has its own sequence numbers
+        tcp_info.update(segment(50, 10, "A"), true);
+        assert_eq!(tcp_info.retransmissions, 2);
+        assert_eq!(tcp_info.out_of_order, 1);
+    }
+}
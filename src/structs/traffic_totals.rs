@@ -0,0 +1,15 @@
+//! Module defining the `TrafficTotals` struct, which represents the filtered traffic exchanged
+//! up to a given instant of a capture.
+
+/// Totals of the filtered traffic, used to close the chart intervals of capture files
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrafficTotals {
+    /// Total amount of filtered bytes sent
+    pub sent_bytes: u128,
+    /// Total amount of filtered bytes received
+    pub received_bytes: u128,
+    /// Total amount of filtered packets sent
+    pub sent_packets: u128,
+    /// Total amount of filtered packets received
+    pub received_packets: u128,
+}
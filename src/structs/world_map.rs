@@ -0,0 +1,250 @@
+//! This module defines the behavior of the `WorldMap` struct, used to display the traffic of each country in gui countries page
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use iced::event::Status;
+use iced::widget::canvas::{Cursor, Event};
+use iced::{mouse, Element, Font, Length, Rectangle};
+use plotters::style::RGBColor;
+use plotters_iced::{Chart, ChartBuilder, ChartWidget, DrawingBackend};
+
+use crate::enums::message::Message;
+use crate::structs::country_info::CountryInfo;
+use crate::structs::palette::to_rgb_color;
+use crate::utility::country_centroids::get_country_centroid;
+use crate::utility::style_constants::INCONSOLATA_BOLD;
+use crate::{get_colors, RunTimeData, StyleType};
+
+/// Longitudes displayed in the map
+const LONGITUDES: Range<f64> = -180.0..180.0;
+/// Latitudes displayed in the map (Antarctica is left out)
+const LATITUDES: Range<f64> = -60.0..85.0;
+/// Radius in pixels of the circle of the country with the least traffic
+const MIN_RADIUS: f64 = 4.0;
+/// Radius in pixels of the circle of the country with the most traffic
+const MAX_RADIUS: f64 = 30.0;
+
+/// Struct defining the world map to be displayed in gui countries page
+pub struct WorldMap {
+    runtime_data: Rc<RefCell<RunTimeData>>,
+    color_circles: RGBColor,
+    color_font: RGBColor,
+}
+
+impl WorldMap {
+    pub fn new(runtime_data: Rc<RefCell<RunTimeData>>, style: StyleType) -> Self {
+        WorldMap {
+            runtime_data,
+            color_circles: to_rgb_color(get_colors(style).outgoing),
+            color_font: to_rgb_color(get_colors(style).text_body),
+        }
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let color_font = self.color_font;
+        ChartWidget::new(self)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .resolve_font(move |_, _| match color_font {
+                RGBColor(255, 255, 255) => Font::Default, // if white non-bold
+                _ => INCONSOLATA_BOLD,
+            })
+            .into()
+    }
+
+    pub fn change_colors(&mut self, style: StyleType) {
+        self.color_font = to_rgb_color(get_colors(style).text_body);
+        self.color_circles = to_rgb_color(get_colors(style).outgoing);
+    }
+}
+
+impl Chart<Message> for WorldMap {
+    type State = ();
+
+    fn build_chart<DB: DrawingBackend>(&self, _state: &Self::State, mut chart: ChartBuilder<DB>) {
+        use plotters::prelude::*;
+
+        let mut chart = chart
+            .build_cartesian_2d(LONGITUDES, LATITUDES)
+            .expect("Error drawing map");
+
+        // meridians and parallels every 30 degrees
+        let color_grid = self.color_font.mix(0.15);
+        for lon in (-150..=150).step_by(30) {
+            let lon = f64::from(lon);
+            chart
+                .draw_series(LineSeries::new(
+                    [(lon, LATITUDES.start), (lon, LATITUDES.end)],
+                    color_grid,
+                ))
+                .expect("Error drawing map");
+        }
+        for lat in (-30..=60).step_by(30) {
+            let lat = f64::from(lat);
+            chart
+                .draw_series(LineSeries::new(
+                    [(LONGITUDES.start, lat), (LONGITUDES.end, lat)],
+                    color_grid,
+                ))
+                .expect("Error drawing map");
+        }
+
+        let runtime_data = self.runtime_data.borrow();
+        let max_bytes = get_max_bytes(&runtime_data.countries_vec);
+        let color_circles = self.color_circles;
+        let label_style = ("notosans", 12).into_font().color(&self.color_font);
+        chart
+            .draw_series(
+                runtime_data
+                    .countries_vec
+                    .iter()
+                    .filter_map(|country_info| {
+                        let centroid = get_country_centroid(&country_info.country)?;
+                        let ratio = get_traffic_ratio(country_info.get_bytes(), max_bytes);
+                        let radius = get_radius(ratio);
+                        // the more traffic, the more opaque the circle
+                        let circle_style = color_circles.mix(0.2 + 0.7 * ratio).filled();
+                        Some(
+                            EmptyElement::at(centroid)
+                                + Circle::new((0, 0), radius, circle_style)
+                                + Circle::new((0, 0), radius, color_circles)
+                                + Text::new(
+                                    country_info.country.clone(),
+                                    (radius as i32 + 2, -6),
+                                    label_style.clone(),
+                                ),
+                        )
+                    }),
+            )
+            .expect("Error drawing map");
+    }
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> (Status, Option<Message>) {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(position) = cursor.position_in(&bounds) {
+                let clicked = get_clicked_country(
+                    &self.runtime_data.borrow().countries_vec,
+                    (f64::from(position.x), f64::from(position.y)),
+                    (f64::from(bounds.width), f64::from(bounds.height)),
+                );
+                if clicked.is_some() {
+                    return (
+                        Status::Captured,
+                        Some(Message::ReportCountrySelection(clicked)),
+                    );
+                }
+            }
+        }
+        (Status::Ignored, None)
+    }
+}
+
+/// Returns the country whose circle contains the given point of the map, if any.
+///
+/// If more circles contain the point, the one with the closest centre is chosen.
+///
+/// # Arguments
+///
+/// * `countries` - The countries drawn on the map.
+///
+/// * `point` - Coordinates in pixels of the point, relative to the top left corner of the map.
+///
+/// * `size` - Width and height in pixels of the map.
+fn get_clicked_country(
+    countries: &[CountryInfo],
+    point: (f64, f64),
+    size: (f64, f64),
+) -> Option<String> {
+    let max_bytes = get_max_bytes(countries);
+    let mut clicked: Option<(&str, f64)> = None;
+    for country_info in countries {
+        if let Some((lon, lat)) = get_country_centroid(&country_info.country) {
+            let x = (lon - LONGITUDES.start) / (LONGITUDES.end - LONGITUDES.start) * size.0;
+            let y = (LATITUDES.end - lat) / (LATITUDES.end - LATITUDES.start) * size.1;
+            let distance = ((x - point.0).powi(2) + (y - point.1).powi(2)).sqrt();
+            let radius = f64::from(get_radius(get_traffic_ratio(
+                country_info.get_bytes(),
+                max_bytes,
+            )));
+            if distance <= radius && clicked.is_none_or(|(_, d)| distance < d) {
+                clicked = Some((country_info.country.as_str(), distance));
+            }
+        }
+    }
+    clicked.map(|(country, _)| country.to_string())
+}
+
+fn get_max_bytes(countries: &[CountryInfo]) -> u128 {
+    countries
+        .iter()
+        .map(CountryInfo::get_bytes)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Returns a value between 0 and 1, proportional to the square root of the traffic
+/// so that the area of the circles grows linearly with it
+fn get_traffic_ratio(bytes: u128, max_bytes: u128) -> f64 {
+    if max_bytes == 0 {
+        0.0
+    } else {
+        (bytes as f64 / max_bytes as f64).sqrt()
+    }
+}
+
+fn get_radius(ratio: f64) -> u32 {
+    (MIN_RADIUS + (MAX_RADIUS - MIN_RADIUS) * ratio).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn country(code: &str, bytes: u128) -> CountryInfo {
+        CountryInfo {
+            country: code.to_string(),
+            bytes_in: bytes,
+            bytes_out: 0,
+            packets_in: 1,
+            packets_out: 0,
+            connections: 1,
+            hosts: 1,
+        }
+    }
+
+    #[test]
+    fn click_on_country_circles() {
+        let countries = [country("IT", 1000), country("US", 10), country("//", 500)];
+        // map of 360x145 pixels: one pixel per degree
+        let size = (360.0, 145.0);
+        // centre of Italy
+        assert_eq!(
+            get_clicked_country(&countries, (192.6, 42.2), size),
+            Some("IT".to_string())
+        );
+        // inside the biggest circle, but far from the centre
+        assert_eq!(
+            get_clicked_country(&countries, (192.6 + 25.0, 42.2), size),
+            Some("IT".to_string())
+        );
+        // the circle of the United States is smaller
+        assert_eq!(
+            get_clicked_country(&countries, (81.4 + 10.0, 45.2), size),
+            None
+        );
+        assert_eq!(
+            get_clicked_country(&countries, (81.4, 45.2), size),
+            Some("US".to_string())
+        );
+        // middle of the Pacific Ocean
+        assert_eq!(get_clicked_country(&countries, (20.0, 90.0), size), None);
+    }
+}
@@ -1,40 +1,95 @@
 //! Module containing functions executed by the thread in charge of parsing sniffed packets and
 //! inserting them in the shared map.
 
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
 
+use chrono::{DateTime, Local};
 use etherparse::PacketHeaders;
-use pcap::{Active, Capture, Device};
+use pcap::{Activated, Capture, Device};
 
+use crate::enums::byte_counting::ByteCounting;
+use crate::enums::capture_source::CaptureSource;
 use crate::enums::traffic_type::TrafficType;
 use crate::structs::address_port_pair::AddressPortPair;
 use crate::structs::filters::Filters;
-use crate::utility::countries::COUNTRY_MMDB;
+use crate::structs::mmdb_readers::MmdbReaders;
+use crate::structs::packets_recorder::PacketsRecorder;
+use crate::structs::port_mapping::PortMapping;
+use crate::structs::recording::Recording;
+use crate::structs::rtt_tracker::RttTracker;
+use crate::structs::tcp_info::TcpInfo;
+use crate::thread_reverse_dns::{reverse_dns_loop, SystemResolver};
+use crate::utility::countries::{get_asn, get_country_code};
+use crate::utility::link_types::get_network_packet;
+use crate::utility::manage_filters::{
+    is_address_in_networks, is_asn_in_filter, parse_address_filter, parse_country_filter,
+    parse_organization_filter, parse_port_filter, parse_server_name_filter,
+};
 use crate::utility::manage_packets::{
-    analyze_network_header, analyze_transport_header, is_broadcast_address, is_multicast_address,
-    modify_or_insert_in_map,
+    add_rtt_measurement, analyze_arp_packet, analyze_network_header, analyze_transport_header,
+    detect_application_protocol, get_dns_answers, get_exchanged_bytes, get_flow_key,
+    get_icmp_type_code, get_packet_timestamp, get_remote_address, get_rtt_probe, get_tcp_segment,
+    is_broadcast_address, is_connection_name_in_filter, is_multicast_address,
+    modify_or_insert_in_map, ARP_ETHER_TYPE,
 };
+use crate::utility::server_name_parser::get_server_name;
 use crate::{AppProtocol, InfoTraffic, IpVersion, TransProtocol};
 
 /// The calling thread enters in a loop in which it waits for network packets, parses them according
 /// to the user specified filters, and inserts them into the shared map variable.
+///
+/// When more adapters are sniffed at once, a thread for each of them runs this loop on the same map.
+///
+/// Packets read from a capture file are parsed at full speed, closing the chart intervals
+/// according to the file's own timeline.
+///
+/// If the recording is active, packets are also saved to file in the given directory.
+///
+/// The application layer protocols are identified also by means of the port mappings defined by the user.
+///
+/// The remote addresses are looked up in the given geolocation databases.
+#[allow(clippy::too_many_arguments)]
 pub fn parse_packets_loop(
     current_capture_id: &Arc<Mutex<u16>>,
     device: Device,
-    mut cap: Capture<Active>,
+    mut cap: Capture<dyn Activated>,
+    capture_source: CaptureSource,
     filters: &Filters,
     info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    recording_mutex: &Arc<Mutex<Recording>>,
+    recording_directory: String,
+    port_mappings: &[PortMapping],
+    mmdb_readers: &MmdbReaders,
 ) {
     let capture_id = *current_capture_id.lock().unwrap();
 
+    // the adapter is recorded for each connection, since more adapters can be sniffed at once
+    let interface_name = match capture_source {
+        CaptureSource::Adapter => Some(device.name.clone()),
+        CaptureSource::File => None,
+    };
     let mut my_interface_addresses = Vec::new();
-    for address in device.addresses {
-        my_interface_addresses.push(address.addr.to_string());
+    if capture_source.eq(&CaptureSource::Adapter) {
+        for address in device.addresses {
+            my_interface_addresses.push(address.addr.to_string());
+        }
     }
 
+    // first packet timestamp and number of chart intervals closed (used for capture files)
+    let mut first_timestamp: Option<DateTime<Local>> = None;
+    let mut closed_intervals: u32 = 0;
+
     let network_layer_filter = filters.ip;
     let transport_layer_filter = filters.transport;
-    let app_layer_filter = filters.application;
+    let app_layer_filter = &filters.application;
+    // textual filters have already been validated before starting the analysis
+    let address_filter = parse_address_filter(&filters.addresses).unwrap_or_default();
+    let port_filter = parse_port_filter(&filters.ports).unwrap_or_default();
+    let country_filter = parse_country_filter(&filters.countries).unwrap_or_default();
+    let traffic_type_filter = &filters.traffic_types;
+    let server_name_filter = parse_server_name_filter(&filters.server_names);
+    let organization_filter = parse_organization_filter(&filters.organizations);
 
     let mut port1 = 0;
     let mut port2 = 0;
@@ -46,21 +101,86 @@ pub fn parse_packets_loop(
     let mut skip_packet;
     let mut reported_packet;
 
-    let country_db_reader = maxminddb::Reader::from_source(COUNTRY_MMDB).unwrap();
+    // counting and aggregation modes are fixed for the whole capture, to keep totals consistent
+    let (byte_counting, bidirectional_flows) = {
+        let info_traffic = info_traffic_mutex.lock().unwrap();
+        (info_traffic.byte_counting, info_traffic.bidirectional_flows)
+    };
+
+    // requests waiting for a response, to measure round trip times
+    let mut rtt_tracker = RttTracker::default();
+
+    // packets are decoded according to the link type of the capture
+    let linktype = cap.get_datalink();
+    let mut packets_recorder =
+        PacketsRecorder::new(linktype, recording_directory, interface_name.as_deref());
+
+    // host names are resolved by another thread, ending when the sender is dropped
+    let (reverse_dns_sender, reverse_dns_receiver) = mpsc::channel();
+    let info_traffic_mutex2 = info_traffic_mutex.clone();
+    let current_capture_id2 = current_capture_id.clone();
+    thread::Builder::new()
+        .name("thread_reverse_dns".to_string())
+        .spawn(move || {
+            reverse_dns_loop(
+                &SystemResolver,
+                &reverse_dns_receiver,
+                &info_traffic_mutex2,
+                &current_capture_id2,
+                capture_id,
+            );
+        })
+        .unwrap();
 
     loop {
         match cap.next_packet() {
+            Err(pcap::Error::NoMorePackets) => {
+                // the whole capture file has been parsed
+                if *current_capture_id.lock().unwrap() == capture_id {
+                    close_last_file_interval(info_traffic_mutex, closed_intervals, None);
+                }
+                return;
+            }
+            Err(error) if capture_source.eq(&CaptureSource::File) => {
+                // reading the file again would fail in the same way
+                if *current_capture_id.lock().unwrap() == capture_id {
+                    close_last_file_interval(info_traffic_mutex, closed_intervals, Some(error));
+                }
+                return;
+            }
             Err(_) => {
                 if *current_capture_id.lock().unwrap() != capture_id {
                     return;
                 }
+                if !recording_mutex.lock().unwrap().active {
+                    packets_recorder.stop();
+                }
                 continue;
             }
             Ok(packet) => {
                 if *current_capture_id.lock().unwrap() != capture_id {
                     return;
                 }
-                match PacketHeaders::from_ethernet_slice(&packet) {
+                let timestamp = get_packet_timestamp(packet.header);
+                if capture_source.eq(&CaptureSource::File) {
+                    close_file_intervals(
+                        info_traffic_mutex,
+                        *first_timestamp.get_or_insert(timestamp),
+                        &mut closed_intervals,
+                        timestamp,
+                    );
+                }
+                let recording = *recording_mutex.lock().unwrap();
+                if !recording.active {
+                    packets_recorder.stop();
+                } else if !recording.only_filtered {
+                    packets_recorder.record(&packet, recording);
+                }
+                let (ether_type, network_packet) = match get_network_packet(linktype, &packet) {
+                    Some(ether_type_packet) => ether_type_packet,
+                    None => continue,
+                };
+                match PacketHeaders::from_ether_type(ether_type, network_packet) {
                     Err(_) => {
                         continue;
                     }
@@ -74,30 +194,75 @@ pub fn parse_packets_loop(
                         skip_packet = false;
                         reported_packet = false;
 
-                        analyze_network_header(
-                            value.ip,
-                            &mut exchanged_bytes,
-                            &mut network_protocol,
-                            &mut address1,
-                            &mut address2,
-                            &mut skip_packet,
-                        );
-                        if skip_packet {
-                            continue;
-                        }
+                        // ICMP messages are counted for each type and code
+                        let icmp_type = get_icmp_type_code(&value.transport);
+                        // TCP flags and sequence numbers are tracked for each connection
+                        let tcp_segment = get_tcp_segment(&value.ip, &value.transport);
 
-                        analyze_transport_header(
-                            value.transport,
-                            &mut port1,
-                            &mut port2,
-                            &mut application_protocol,
-                            &mut transport_protocol,
-                            &mut skip_packet,
-                        );
+                        if ether_type == ARP_ETHER_TYPE {
+                            analyze_arp_packet(
+                                value.payload,
+                                &mut exchanged_bytes,
+                                &mut network_protocol,
+                                &mut address1,
+                                &mut address2,
+                                &mut transport_protocol,
+                                &mut skip_packet,
+                            );
+                            port1 = 0;
+                            port2 = 0;
+                            if byte_counting.eq(&ByteCounting::Frame) {
+                                exchanged_bytes = u128::from(packet.header.len);
+                            }
+                        } else {
+                            exchanged_bytes = get_exchanged_bytes(
+                                byte_counting,
+                                packet.header.len,
+                                &value.ip,
+                                &value.transport,
+                            );
+                            analyze_network_header(
+                                value.ip,
+                                &mut network_protocol,
+                                &mut address1,
+                                &mut address2,
+                                &mut skip_packet,
+                            );
+                            if !skip_packet {
+                                analyze_transport_header(
+                                    value.transport,
+                                    port_mappings,
+                                    &mut port1,
+                                    &mut port2,
+                                    &mut application_protocol,
+                                    &mut transport_protocol,
+                                );
+                            }
+                        }
                         if skip_packet {
+                            // packets of other network layer protocols are just counted
+                            let mut info_traffic = info_traffic_mutex
+                                .lock()
+                                .expect("Error acquiring mutex\n\r");
+                            info_traffic.all_packets += 1;
+                            info_traffic.all_bytes += if byte_counting.eq(&ByteCounting::Frame) {
+                                u128::from(packet.header.len)
+                            } else {
+                                value.payload.len() as u128
+                            };
                             continue;
                         }
 
+                        // DNS responses are inspected regardless of the filters,
+                        // to label the connections with the domain names queried by the user
+                        let dns_answers = get_dns_answers(value.payload, port1, transport_protocol);
+                        // the name of the contacted server is looked for in the client's requests
+                        let server_name = if transport_protocol.eq(&TransProtocol::TCP) {
+                            get_server_name(value.payload)
+                        } else {
+                            None
+                        };
+
                         if my_interface_addresses.contains(&address1) {
                             traffic_type = TrafficType::Outgoing;
                         } else if my_interface_addresses.contains(&address2) {
@@ -108,7 +273,7 @@ pub fn parse_packets_loop(
                             traffic_type = TrafficType::Broadcast;
                         }
 
-                        let key: AddressPortPair = AddressPortPair::new(
+                        let packet_key = AddressPortPair::new(
                             address1,
                             port1,
                             address2,
@@ -116,25 +281,102 @@ pub fn parse_packets_loop(
                             transport_protocol,
                         );
 
+                        // requests and responses are matched regardless of the filters
+                        let rtt = match get_rtt_probe(
+                            value.payload,
+                            port1,
+                            port2,
+                            transport_protocol,
+                            tcp_segment,
+                        ) {
+                            Some((probe, false)) => {
+                                rtt_tracker.request(&packet_key, probe, timestamp);
+                                None
+                            }
+                            Some((probe, true)) => {
+                                rtt_tracker.response(&packet_key, probe, timestamp)
+                            }
+                            None => None,
+                        };
+
+                        let (key, is_reversed) =
+                            get_flow_key(info_traffic_mutex, packet_key, bidirectional_flows);
+                        // traffic type referred to the orientation of the key
+                        let key_traffic_type = if is_reversed {
+                            traffic_type.reversed()
+                        } else {
+                            traffic_type
+                        };
+
+                        // the protocol of each connection is decided at its first packet
+                        let (application_protocol, detection_method) = detect_application_protocol(
+                            info_traffic_mutex,
+                            &key,
+                            value.payload,
+                            application_protocol,
+                        );
+
                         if (network_layer_filter.eq(&IpVersion::Other)
                             || network_layer_filter.eq(&network_protocol))
                             && (transport_layer_filter.eq(&TransProtocol::Other)
                                 || transport_layer_filter.eq(&transport_protocol))
                             && (app_layer_filter.eq(&AppProtocol::Other)
                                 || app_layer_filter.eq(&application_protocol))
+                            && (address_filter.is_empty()
+                                || is_address_in_networks(&key.address1, &address_filter)
+                                || is_address_in_networks(&key.address2, &address_filter))
+                            && (port_filter.is_empty()
+                                || port_filter
+                                    .iter()
+                                    .any(|range| range.contains(&port1) || range.contains(&port2)))
+                            && (traffic_type_filter.is_empty()
+                                || traffic_type_filter.contains(&traffic_type))
+                            && (country_filter.is_empty()
+                                || country_filter.contains(&get_country_code(
+                                    key_traffic_type,
+                                    &key,
+                                    &mmdb_readers.country,
+                                )))
+                            && (organization_filter.is_empty()
+                                || is_asn_in_filter(
+                                    mmdb_readers
+                                        .asn
+                                        .as_ref()
+                                        .and_then(|reader| get_asn(key_traffic_type, &key, reader))
+                                        .as_ref(),
+                                    &organization_filter,
+                                ))
+                            && (server_name_filter.is_empty()
+                                || is_connection_name_in_filter(
+                                    info_traffic_mutex,
+                                    &key,
+                                    key_traffic_type,
+                                    server_name.as_deref(),
+                                    &server_name_filter,
+                                ))
                         {
-                            // if (port1 >= lowest_port && port1 <= highest_port)
-                            //     || (port2 >= lowest_port && port2 <= highest_port) {
-                            modify_or_insert_in_map(
+                            let new_connection = modify_or_insert_in_map(
                                 info_traffic_mutex,
-                                key,
+                                &key,
                                 exchanged_bytes,
-                                traffic_type,
-                                application_protocol,
-                                &country_db_reader,
+                                key_traffic_type,
+                                (application_protocol.clone(), detection_method),
+                                mmdb_readers,
+                                timestamp,
                             );
+                            if let Some(index) = new_connection {
+                                // the worker is never blocked, so the send can only fail if it ended
+                                reverse_dns_sender
+                                    .send((
+                                        get_remote_address(key_traffic_type, &key).to_string(),
+                                        index,
+                                    ))
+                                    .unwrap_or(());
+                            }
                             reported_packet = true;
-                            // }
+                            if recording.active && recording.only_filtered {
+                                packets_recorder.record(&packet, recording);
+                            }
                         }
 
                         let mut info_traffic = info_traffic_mutex
@@ -143,8 +385,45 @@ pub fn parse_packets_loop(
                         //increment number of sniffed packets and bytes
                         info_traffic.all_packets += 1;
                         info_traffic.all_bytes += exchanged_bytes;
+                        info_traffic.add_dns_answers(dns_answers);
+                        if let Some(rtt) = rtt {
+                            add_rtt_measurement(&mut info_traffic, &key, rtt);
+                            // the request belongs to the connection in the opposite direction
+                            if !bidirectional_flows {
+                                add_rtt_measurement(&mut info_traffic, &key.reversed(), rtt);
+                            }
+                        }
 
                         if reported_packet {
+                            if let Some(info) = info_traffic.map.get_mut(&key) {
+                                if is_reversed {
+                                    info.received_packets += 1;
+                                    info.received_bytes += exchanged_bytes;
+                                } else {
+                                    info.sent_packets += 1;
+                                    info.sent_bytes += exchanged_bytes;
+                                }
+                                if let Some(server_name) = server_name {
+                                    info.server_name.get_or_insert(server_name);
+                                }
+                                if let Some(tcp_segment) = tcp_segment {
+                                    info.tcp_info
+                                        .get_or_insert_with(TcpInfo::default)
+                                        .update(tcp_segment, is_reversed);
+                                }
+                                if let Some(icmp_type) = icmp_type {
+                                    info.icmp_types
+                                        .entry(icmp_type)
+                                        .and_modify(|n| *n += 1)
+                                        .or_insert(1);
+                                }
+                                if let Some(interface_name) = &interface_name {
+                                    if !info.interfaces.contains(interface_name) {
+                                        info.interfaces.push(interface_name.clone());
+                                    }
+                                }
+                            }
+
                             //increment the packet count for the sniffed app protocol
                             info_traffic
                                 .app_protocols
@@ -168,3 +447,40 @@ pub fn parse_packets_loop(
         }
     }
 }
+
+/// Closes the chart intervals of a capture file ended before the given packet timestamp.
+///
+/// The charts thus follow the file's own timeline, even if it's parsed at full speed.
+fn close_file_intervals(
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    first_timestamp: DateTime<Local>,
+    closed_intervals: &mut u32,
+    timestamp: DateTime<Local>,
+) {
+    let elapsed = u32::try_from((timestamp - first_timestamp).num_seconds()).unwrap_or(0);
+    if elapsed <= *closed_intervals {
+        return;
+    }
+    let mut info_traffic = info_traffic_mutex.lock().unwrap();
+    let totals = info_traffic.get_totals();
+    // intervals older than the 30 displayed in the charts are skipped
+    let first_displayed = elapsed.saturating_sub(30);
+    for second in (*closed_intervals).max(first_displayed)..elapsed {
+        info_traffic.file_intervals.push_back((second, totals));
+    }
+    *closed_intervals = elapsed;
+}
+
+/// Closes the last chart interval of a capture file, recording the error that stopped its reading (if any).
+fn close_last_file_interval(
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    closed_intervals: u32,
+    error: Option<pcap::Error>,
+) {
+    let mut info_traffic = info_traffic_mutex.lock().unwrap();
+    let totals = info_traffic.get_totals();
+    info_traffic
+        .file_intervals
+        .push_back((closed_intervals, totals));
+    info_traffic.capture_error = error.map(|error| error.to_string());
+}
@@ -0,0 +1,184 @@
+//! Module containing functions executed by the thread in charge of resolving the host names
+//! of the remote addresses, without blocking the thread parsing packets.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+use crate::InfoTraffic;
+
+/// Resolves IP addresses to host names.
+///
+/// It permits to replace the system resolver with a stub in tests.
+pub trait ReverseResolver {
+    /// Returns the host name associated to the given address, if any
+    fn lookup(&self, address: &IpAddr) -> Option<String>;
+}
+
+/// Resolver using the reverse DNS lookup of the operating system
+pub struct SystemResolver;
+
+impl ReverseResolver for SystemResolver {
+    fn lookup(&self, address: &IpAddr) -> Option<String> {
+        dns_lookup::lookup_addr(address)
+            .ok()
+            // the numeric address is returned when no name is found
+            .filter(|hostname| hostname.ne(&address.to_string()))
+    }
+}
+
+/// The calling thread enters in a loop in which it receives the remote addresses of the new connections
+/// (together with their index in the connections map), resolves them, and stores the host names in the map.
+///
+/// Each address is resolved only once. The loop ends when the thread parsing packets drops its sender
+/// or when a new capture is started.
+pub fn reverse_dns_loop(
+    resolver: &impl ReverseResolver,
+    receiver: &Receiver<(String, usize)>,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    current_capture_id: &Arc<Mutex<u16>>,
+    capture_id: u16,
+) {
+    // host names already resolved (None means that no name was found)
+    let mut cache: HashMap<String, Option<String>> = HashMap::new();
+
+    while let Ok((address, index)) = receiver.recv() {
+        if *current_capture_id.lock().unwrap() != capture_id {
+            return;
+        }
+        let hostname = cache
+            .entry(address)
+            .or_insert_with_key(|address| {
+                address
+                    .parse()
+                    .ok()
+                    .and_then(|address| resolver.lookup(&address))
+            })
+            .clone();
+        if hostname.is_none() {
+            continue;
+        }
+        let mut info_traffic = info_traffic_mutex.lock().unwrap();
+        // the map could have been replaced by that of a new capture in the meantime
+        if *current_capture_id.lock().unwrap() != capture_id {
+            return;
+        }
+        if let Some((_, info)) = info_traffic.map.get_index_mut(index) {
+            info.hostname = hostname;
+            info_traffic.addresses_last_interval.insert(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::sync::mpsc;
+
+    use crate::enums::traffic_type::TrafficType;
+    use crate::structs::address_port_pair::AddressPortPair;
+    use crate::structs::info_address_port_pair::InfoAddressPortPair;
+    use crate::{AppProtocol, TransProtocol};
+
+    use super::*;
+
+    /// Resolver knowing a single address, counting the lookups it performs
+    struct StubResolver {
+        lookups: Cell<usize>,
+    }
+
+    impl ReverseResolver for StubResolver {
+        fn lookup(&self, address: &IpAddr) -> Option<String> {
+            self.lookups.set(self.lookups.get() + 1);
+            if address.to_string().eq("8.8.8.8") {
+                Some("dns.google".to_string())
+            } else {
+                None
+            }
+        }
+    }
+
+    fn info_traffic_with_connections(remote_addresses: &[&str]) -> InfoTraffic {
+        let mut info_traffic = InfoTraffic::new();
+        for (index, address) in remote_addresses.iter().enumerate() {
+            let key = AddressPortPair::new(
+                (*address).to_string(),
+                53,
+                "192.168.1.2".to_string(),
+                u16::try_from(50000 + index).unwrap(),
+                TransProtocol::UDP,
+            );
+            let info =
+                InfoAddressPortPair::new_for_tests(AppProtocol::DNS, TrafficType::Incoming, index);
+            info_traffic.map.insert(key, info);
+        }
+        info_traffic
+    }
+
+    #[test]
+    fn hostnames_are_resolved_once_and_stored() {
+        let info_traffic_mutex = Arc::new(Mutex::new(info_traffic_with_connections(&[
+            "8.8.8.8", "8.8.8.8", "1.1.1.1",
+        ])));
+        let current_capture_id = Arc::new(Mutex::new(0));
+        let resolver = StubResolver {
+            lookups: Cell::new(0),
+        };
+        let (sender, receiver) = mpsc::channel();
+        sender.send(("8.8.8.8".to_string(), 0)).unwrap();
+        sender.send(("8.8.8.8".to_string(), 1)).unwrap();
+        sender.send(("1.1.1.1".to_string(), 2)).unwrap();
+        drop(sender);
+
+        reverse_dns_loop(
+            &resolver,
+            &receiver,
+            &info_traffic_mutex,
+            &current_capture_id,
+            0,
+        );
+
+        let info_traffic = info_traffic_mutex.lock().unwrap();
+        let hostnames: Vec<Option<String>> = info_traffic
+            .map
+            .values()
+            .map(|info| info.hostname.clone())
+            .collect();
+        assert_eq!(
+            hostnames,
+            vec![
+                Some("dns.google".to_string()),
+                Some("dns.google".to_string()),
+                None
+            ]
+        );
+        assert_eq!(resolver.lookups.get(), 2);
+        assert!(info_traffic.addresses_last_interval.contains(&0));
+        assert!(info_traffic.addresses_last_interval.contains(&1));
+    }
+
+    #[test]
+    fn nothing_is_resolved_after_a_new_capture_is_started() {
+        let info_traffic_mutex = Arc::new(Mutex::new(info_traffic_with_connections(&["8.8.8.8"])));
+        let current_capture_id = Arc::new(Mutex::new(1));
+        let resolver = StubResolver {
+            lookups: Cell::new(0),
+        };
+        let (sender, receiver) = mpsc::channel();
+        sender.send(("8.8.8.8".to_string(), 0)).unwrap();
+        drop(sender);
+
+        reverse_dns_loop(
+            &resolver,
+            &receiver,
+            &info_traffic_mutex,
+            &current_capture_id,
+            0,
+        );
+
+        assert_eq!(resolver.lookups.get(), 0);
+        let info_traffic = info_traffic_mutex.lock().unwrap();
+        assert!(info_traffic.map[0].hostname.is_none());
+    }
+}
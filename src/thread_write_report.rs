@@ -1,79 +1,116 @@
 //! Module containing functions executed by the thread in charge of updating the output report every 1 second
 
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufWriter, Seek, SeekFrom, Write};
-use std::sync::{Arc, Condvar, Mutex};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, ErrorKind, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 use std::time::Duration;
 use std::{fs, thread};
 
+use chrono::Local;
+
 use crate::enums::status::Status;
+use crate::structs::report_entry::{ReportEntry, CSV_HEADER};
+use crate::structs::report_settings::ReportSettings;
 use crate::InfoTraffic;
 
+/// Prefix of the names of the report files, followed by the session start time
+/// (and by a counter if more sessions started in the same second)
+const REPORT_PREFIX: &str = "sniffnet_report_";
+
+/// Files of the report of a capture session
+struct ReportSession {
+    /// Path of the textual report
+    path_txt: PathBuf,
+    /// Textual report, updated by seeking to the lines of the modified connections
+    output: BufWriter<File>,
+    /// Path of the CSV report
+    path_csv: PathBuf,
+    /// Path of the JSON lines report
+    path_json: PathBuf,
+}
+
 /// The calling thread enters in a loop in which it sleeps for 1 second and then
 /// updates the output report containing detailed traffic information
+///
+/// Each capture session has its own timestamped report inside the directory specified in the report settings;
+/// depending on the same settings, it's also exported in CSV and JSON lines formats.
+///
+/// When `stop` is set, the report is updated one last time and the loop ends.
 pub fn sleep_and_write_report_loop(
     current_capture_id: &Arc<Mutex<u16>>,
     info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
     status_pair: &Arc<(Mutex<Status>, Condvar)>,
+    report_settings_mutex: &Arc<Mutex<ReportSettings>>,
+    stop: &AtomicBool,
 ) {
     let cvar = &status_pair.1;
 
-    #[cfg(target_os = "macos")]
-    std::env::set_current_dir(std::env::var("HOME").unwrap()).unwrap();
-
-    if fs::create_dir("./sniffnet_report").is_err() {
-        fs::remove_dir_all("./sniffnet_report").unwrap();
-        fs::create_dir("./sniffnet_report").unwrap();
-    }
-
-    let path_report = "./sniffnet_report/report.txt".to_string();
-
-    let mut capture_id = *current_capture_id.lock().unwrap();
-
-    let mut output =
-        BufWriter::new(File::create(path_report.clone()).expect("Error creating output file\n\r"));
-    writeln!(output, "---------------------------------------------------------------------------------------------------------------------------------------------------------------------").expect("Error writing output file\n\r");
-    writeln!(output, "|     Src IP address      | Src port |     Dst IP address      | Dst port | Layer 4 | Layer 7 |   Packets  |   Bytes    |  Initial timestamp  |   Final timestamp   |").expect("Error writing output file\n\r");
-    writeln!(output, "---------------------------------------------------------------------------------------------------------------------------------------------------------------------").expect("Error writing output file\n\r");
+    // capture whose report is currently being written
+    let mut capture_id = None;
+    let mut session: Option<ReportSession> = None;
+    let mut last_interval = false;
 
     loop {
+        if last_interval {
+            return;
+        }
         // sleep 1 second
         thread::sleep(Duration::from_secs(1));
-
-        let current_capture_id_lock = current_capture_id.lock().unwrap();
-        if *current_capture_id_lock != capture_id {
-            capture_id = *current_capture_id_lock;
-            output = BufWriter::new(
-                File::create(path_report.clone()).expect("Error creating output file\n\r"),
-            );
-            writeln!(output, "---------------------------------------------------------------------------------------------------------------------------------------------------------------------").expect("Error writing output file\n\r");
-            writeln!(output, "|     Src IP address      | Src port |     Dst IP address      | Dst port | Layer 4 | Layer 7 |   Packets  |   Bytes    |  Initial timestamp  |   Final timestamp   |").expect("Error writing output file\n\r");
-            writeln!(output, "---------------------------------------------------------------------------------------------------------------------------------------------------------------------").expect("Error writing output file\n\r");
-        }
-        drop(current_capture_id_lock);
+        last_interval = stop.load(Ordering::SeqCst);
 
         let mut status = status_pair.0.lock().expect("Error acquiring mutex\n\r");
 
         if *status == Status::Running {
+            // read while holding the status lock, so that it surely refers to the running capture
+            let current_capture_id = *current_capture_id.lock().unwrap();
             drop(status);
 
+            let report_settings = report_settings_mutex.lock().unwrap().clone();
+
             let mut info_traffic = info_traffic_mutex
                 .lock()
                 .expect("Error acquiring mutex\n\r");
 
-            for index in &info_traffic.addresses_last_interval {
-                let key_val = info_traffic.map.get_index(*index).unwrap();
-                let seek_pos = 166 * 3 + 206 * (*index) as u64;
-                output.seek(SeekFrom::Start(seek_pos)).unwrap();
-                writeln!(output, "{}{}", key_val.0, key_val.1)
-                    .expect("Error writing output file\n\r");
+            if info_traffic.addresses_last_interval.is_empty() {
+                continue;
+            }
+
+            if capture_id != Some(current_capture_id) {
+                // first data of a new capture: its report files are created only now
+                // to avoid filling the history with empty sessions
+                capture_id = Some(current_capture_id);
+                session = match start_report_session(&report_settings) {
+                    Ok(session) => Some(session),
+                    Err(error) => {
+                        info_traffic.report_error = Some(error.to_string());
+                        None
+                    }
+                };
+                info_traffic.report_path = session
+                    .as_ref()
+                    .map(|session| session.path_txt.to_string_lossy().to_string());
             }
-            info_traffic.addresses_last_interval = HashSet::new(); // empty set
 
-            output.flush().expect("Error writing output file\n\r");
+            // the report could not be created or written
+            let current_session = match session.as_mut() {
+                None => {
+                    info_traffic.addresses_last_interval = HashSet::new();
+                    continue;
+                }
+                Some(current_session) => current_session,
+            };
 
-            drop(info_traffic);
+            if let Err(error) = update_report(current_session, info_traffic, &report_settings) {
+                // the report of this capture is no longer updated
+                session = None;
+                info_traffic_mutex
+                    .lock()
+                    .expect("Error acquiring mutex\n\r")
+                    .report_error = Some(error.to_string());
+            }
         } else {
             //status is Init
             while *status == Status::Init {
@@ -82,3 +119,189 @@ pub fn sleep_and_write_report_loop(
         }
     }
 }
+
+/// Writes the connections modified in the last interval to the report files of the session.
+///
+/// The lock on the traffic data is released before rewriting the machine-readable reports.
+fn update_report(
+    session: &mut ReportSession,
+    mut info_traffic: MutexGuard<InfoTraffic>,
+    report_settings: &ReportSettings,
+) -> io::Result<()> {
+    // machine-readable reports are entirely rewritten when something changed
+    let entries: Vec<ReportEntry> = if report_settings.csv || report_settings.json {
+        info_traffic
+            .map
+            .iter()
+            .map(|(key, val)| ReportEntry::new(key, val))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let addresses_last_interval = std::mem::take(&mut info_traffic.addresses_last_interval);
+    let output = &mut session.output;
+    for index in addresses_last_interval {
+        let key_val = info_traffic.map.get_index(index).unwrap();
+        let seek_pos = 166 * 3 + 206 * index as u64;
+        output.seek(SeekFrom::Start(seek_pos))?;
+        writeln!(output, "{}{}", key_val.0, key_val.1)?;
+    }
+    drop(info_traffic);
+    output.flush()?;
+
+    if report_settings.csv {
+        write_export_file(
+            &session.path_csv,
+            std::iter::once(CSV_HEADER.to_string())
+                .chain(entries.iter().map(ReportEntry::to_csv_line)),
+        )?;
+    }
+    if report_settings.json {
+        write_export_file(
+            &session.path_json,
+            entries
+                .iter()
+                .map(|entry| serde_json::to_string(entry).unwrap()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Creates the report files of a new capture session, deleting those of the oldest sessions.
+fn start_report_session(report_settings: &ReportSettings) -> io::Result<ReportSession> {
+    let directory = Path::new(&report_settings.directory);
+    // the directory is chosen by the user: never wipe its content
+    fs::create_dir_all(directory)?;
+
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+    let mut stem = format!("{REPORT_PREFIX}{timestamp}");
+    let mut sessions_count = 1;
+    // the file is created only if missing, so that concurrent sessions never share it
+    let (path_txt, file) = loop {
+        let path_txt = directory.join(format!("{stem}.txt"));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path_txt)
+        {
+            Ok(file) => break (path_txt, file),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                sessions_count += 1;
+                stem = format!("{REPORT_PREFIX}{timestamp}_{sessions_count}");
+            }
+            Err(err) => return Err(err),
+        }
+    };
+    let mut output = BufWriter::new(file);
+    writeln!(output, "---------------------------------------------------------------------------------------------------------------------------------------------------------------------")?;
+    writeln!(output, "|     Src IP address      | Src port |     Dst IP address      | Dst port | Layer 4 | Layer 7 |   Packets  |   Bytes    |  Initial timestamp  |   Final timestamp   |")?;
+    writeln!(output, "---------------------------------------------------------------------------------------------------------------------------------------------------------------------")?;
+
+    delete_old_sessions(directory, report_settings.max_sessions);
+
+    Ok(ReportSession {
+        path_txt,
+        output,
+        path_csv: directory.join(format!("{stem}.csv")),
+        path_json: directory.join(format!("{stem}.jsonl")),
+    })
+}
+
+/// Deletes the report files of the oldest sessions, so that at most `max_sessions` are kept in the directory
+///
+/// Packet recordings saved in the same directory are never deleted.
+fn delete_old_sessions(directory: &Path, max_sessions: u64) {
+    let mut stems: Vec<String> = match fs::read_dir(directory) {
+        Err(_) => return,
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with(REPORT_PREFIX))
+            .filter_map(|name| name.strip_suffix(".txt").map(ToString::to_string))
+            .collect(),
+    };
+    stems.sort_by(|stem1, stem2| get_session_order(stem1).cmp(&get_session_order(stem2)));
+    let max_sessions = usize::try_from(max_sessions.max(1)).unwrap_or(usize::MAX);
+    let excess = stems.len().saturating_sub(max_sessions);
+    for stem in &stems[..excess] {
+        for extension in ["txt", "csv", "jsonl"] {
+            fs::remove_file(directory.join(format!("{stem}.{extension}"))).unwrap_or(());
+        }
+    }
+}
+
+/// Returns the start time and the counter of a session from the stem of its file names,
+/// so that sessions started in the same second are sorted by their counter (e.g. `_2` before `_10`)
+fn get_session_order(stem: &str) -> (&str, u64) {
+    stem.rsplit_once('_')
+        .and_then(|(timestamp, counter)| Some((timestamp, counter.parse().ok()?)))
+        // the first session of each second has no counter
+        .unwrap_or((stem, 1))
+}
+
+/// Rewrites the whole file at the given path with the given lines.
+///
+/// Lines are written to a temporary file which is then renamed,
+/// so that external readers never find a partially written file.
+fn write_export_file(path: &Path, lines: impl Iterator<Item = String>) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let mut output = BufWriter::new(File::create(&tmp_path)?);
+    for line in lines {
+        writeln!(output, "{line}")?;
+    }
+    output.flush()?;
+    drop(output);
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_are_distinct_and_recordings_are_kept() {
+        let directory = std::env::temp_dir().join("sniffnet_report_sessions_test");
+        fs::remove_dir_all(&directory).unwrap_or(());
+        fs::create_dir_all(&directory).unwrap();
+        let recording = directory.join("capture_eth0_2023-01-01_00-00-00_1.pcap");
+        File::create(&recording).unwrap();
+        let report_settings = ReportSettings {
+            directory: directory.to_string_lossy().to_string(),
+            max_sessions: 2,
+            ..ReportSettings::default()
+        };
+        let paths: Vec<PathBuf> = (0..3)
+            .map(|_| start_report_session(&report_settings).unwrap().path_txt)
+            .collect();
+        assert_ne!(paths[0], paths[1]);
+        assert_ne!(paths[1], paths[2]);
+        // the oldest session is deleted, the recording is not
+        assert!(!paths[0].exists());
+        assert!(paths[1].exists() && paths[2].exists());
+        assert!(recording.exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn sessions_of_the_same_second_are_sorted_by_counter() {
+        let directory = std::env::temp_dir().join("sniffnet_report_counters_test");
+        fs::remove_dir_all(&directory).unwrap_or(());
+        fs::create_dir_all(&directory).unwrap();
+        for stem in [
+            "sniffnet_report_2023-01-01_00-00-00",
+            "sniffnet_report_2023-01-01_00-00-00_2",
+            "sniffnet_report_2023-01-01_00-00-00_10",
+        ] {
+            File::create(directory.join(format!("{stem}.txt"))).unwrap();
+        }
+        delete_old_sessions(&directory, 1);
+        let names: Vec<String> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(names, vec!["sniffnet_report_2023-01-01_00-00-00_10.txt"]);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -1,20 +1,21 @@
 use crate::enums::traffic_type::TrafficType;
 use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::asn::Asn;
+use crate::structs::mmdb_readers::MmdbReader;
+use crate::utility::manage_packets::get_remote_address;
 use iced::widget::{image::Handle, Image};
 use iced::Length;
-use maxminddb::{geoip2, MaxMindDBError, Reader};
+use maxminddb::{geoip2, MaxMindDBError};
+use std::net::IpAddr;
 
 pub const COUNTRY_MMDB: &[u8] = include_bytes!("../../resources/DB/GeoLite2-Country.mmdb");
 
 pub fn get_country_code(
     traffic_type: TrafficType,
     key: &AddressPortPair,
-    country_db_reader: &Reader<&[u8]>,
+    country_db_reader: &MmdbReader,
 ) -> String {
-    let address_to_lookup = match traffic_type {
-        TrafficType::Outgoing => &key.address2,
-        _ => &key.address1,
-    };
+    let address_to_lookup = get_remote_address(traffic_type, key);
 
     let country_result: Result<geoip2::Country, MaxMindDBError> =
         country_db_reader.lookup(address_to_lookup.parse().unwrap());
@@ -28,6 +29,51 @@ pub fn get_country_code(
     String::new()
 }
 
+/// Returns the autonomous system of the remote address of a connection, if found in the database
+pub fn get_asn(
+    traffic_type: TrafficType,
+    key: &AddressPortPair,
+    asn_db_reader: &MmdbReader,
+) -> Option<Asn> {
+    let address_to_lookup = get_remote_address(traffic_type, key).parse().ok()?;
+    let asn: geoip2::Asn = asn_db_reader.lookup(address_to_lookup).ok()?;
+    Some(Asn {
+        number: asn.autonomous_system_number?,
+        name: asn
+            .autonomous_system_organization
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// Returns the city and the coordinates (latitude and longitude) of the remote address of a connection.
+///
+/// The city is empty if not found in the database.
+pub fn get_city(
+    traffic_type: TrafficType,
+    key: &AddressPortPair,
+    city_db_reader: &MmdbReader,
+) -> (String, Option<(f64, f64)>) {
+    let city_result: Option<geoip2::City> = get_remote_address(traffic_type, key)
+        .parse::<IpAddr>()
+        .ok()
+        .and_then(|address| city_db_reader.lookup(address).ok());
+    match city_result {
+        None => (String::new(), None),
+        Some(res) => {
+            let city = res
+                .city
+                .and_then(|city| city.names)
+                .and_then(|names| names.get("en").map(ToString::to_string))
+                .unwrap_or_default();
+            let coordinates = res
+                .location
+                .and_then(|location| Some((location.latitude?, location.longitude?)));
+            (city, coordinates)
+        }
+    }
+}
+
 pub const FLAGS_WIDTH: u16 = 15;
 
 pub const AD: &[u8] =
@@ -0,0 +1,191 @@
+//! Module containing the approximate geographic centres of the countries, used to place them on the world map.
+//!
+//! The boundaries of the countries are not embedded in the executable: each country is drawn
+//! as a circle centred on these coordinates.
+
+/// Returns the longitude and latitude of the centre of the country with the given ISO code, if known.
+pub fn get_country_centroid(country: &str) -> Option<(f64, f64)> {
+    let centroid = match country {
+        "AD" => (1.6, 42.5),
+        "AE" => (54.0, 24.0),
+        "AF" => (66.0, 33.9),
+        "AL" => (20.0, 41.1),
+        "AM" => (45.0, 40.1),
+        "AO" => (17.9, -12.3),
+        "AR" => (-64.0, -34.0),
+        "AT" => (14.6, 47.5),
+        "AU" => (134.0, -25.7),
+        "AZ" => (47.6, 40.1),
+        "BA" => (17.8, 44.2),
+        "BD" => (90.4, 23.7),
+        "BE" => (4.5, 50.6),
+        "BF" => (-1.6, 12.3),
+        "BG" => (25.5, 42.7),
+        "BH" => (50.6, 26.0),
+        "BI" => (29.9, -3.4),
+        "BJ" => (2.3, 9.3),
+        "BN" => (114.7, 4.5),
+        "BO" => (-64.7, -16.3),
+        "BR" => (-51.9, -10.8),
+        "BS" => (-77.4, 25.0),
+        "BT" => (90.4, 27.5),
+        "BW" => (24.7, -22.3),
+        "BY" => (28.0, 53.7),
+        "BZ" => (-88.5, 17.2),
+        "CA" => (-106.3, 56.1),
+        "CD" => (23.7, -2.9),
+        "CF" => (20.9, 6.6),
+        "CG" => (15.8, -0.8),
+        "CH" => (8.2, 46.8),
+        "CI" => (-5.5, 7.5),
+        "CL" => (-71.5, -35.7),
+        "CM" => (12.4, 5.7),
+        "CN" => (103.8, 35.9),
+        "CO" => (-73.1, 4.0),
+        "CR" => (-84.2, 9.9),
+        "CU" => (-79.0, 21.6),
+        "CY" => (33.4, 35.1),
+        "CZ" => (15.3, 49.8),
+        "DE" => (10.4, 51.2),
+        "DJ" => (42.6, 11.8),
+        "DK" => (9.5, 56.2),
+        "DO" => (-70.5, 18.9),
+        "DZ" => (2.6, 28.0),
+        "EC" => (-78.2, -1.8),
+        "EE" => (25.0, 58.6),
+        "EG" => (30.8, 26.8),
+        "ER" => (39.8, 15.2),
+        "ES" => (-3.7, 40.2),
+        "ET" => (39.6, 8.6),
+        "FI" => (26.0, 64.0),
+        "FJ" => (178.1, -17.7),
+        "FR" => (2.4, 46.6),
+        "GA" => (11.6, -0.6),
+        "GB" => (-2.9, 54.2),
+        "GE" => (43.4, 42.3),
+        "GH" => (-1.0, 7.9),
+        "GL" => (-41.3, 74.7),
+        "GM" => (-15.4, 13.4),
+        "GN" => (-10.9, 10.4),
+        "GQ" => (10.3, 1.6),
+        "GR" => (22.0, 39.1),
+        "GT" => (-90.4, 15.7),
+        "GW" => (-15.2, 12.0),
+        "GY" => (-58.9, 4.8),
+        "HK" => (114.2, 22.3),
+        "HN" => (-86.6, 14.8),
+        "HR" => (16.4, 45.1),
+        "HT" => (-72.7, 19.0),
+        "HU" => (19.4, 47.2),
+        "ID" => (117.3, -2.5),
+        "IE" => (-8.1, 53.2),
+        "IL" => (35.0, 31.4),
+        "IN" => (79.6, 22.9),
+        "IQ" => (43.7, 33.0),
+        "IR" => (53.7, 32.6),
+        "IS" => (-18.6, 65.0),
+        "IT" => (12.6, 42.8),
+        "JM" => (-77.3, 18.1),
+        "JO" => (36.8, 31.2),
+        "JP" => (138.0, 36.9),
+        "KE" => (37.9, 0.5),
+        "KG" => (74.6, 41.5),
+        "KH" => (104.9, 12.7),
+        "KP" => (127.2, 40.2),
+        "KR" => (127.8, 36.4),
+        "KW" => (47.6, 29.3),
+        "KZ" => (67.3, 48.2),
+        "LA" => (103.8, 18.5),
+        "LB" => (35.9, 33.9),
+        "LI" => (9.6, 47.2),
+        "LK" => (80.7, 7.6),
+        "LR" => (-9.4, 6.4),
+        "LS" => (28.2, -29.6),
+        "LT" => (23.9, 55.3),
+        "LU" => (6.1, 49.8),
+        "LV" => (24.9, 56.9),
+        "LY" => (17.2, 27.0),
+        "MA" => (-6.3, 31.8),
+        "MC" => (7.4, 43.7),
+        "MD" => (28.5, 47.2),
+        "ME" => (19.3, 42.8),
+        "MG" => (46.7, -19.4),
+        "MK" => (21.7, 41.6),
+        "ML" => (-3.5, 17.3),
+        "MM" => (96.5, 21.2),
+        "MN" => (103.1, 46.8),
+        "MO" => (113.5, 22.2),
+        "MR" => (-10.3, 20.3),
+        "MT" => (14.4, 35.9),
+        "MU" => (57.6, -20.3),
+        "MV" => (73.2, 3.2),
+        "MW" => (34.3, -13.2),
+        "MX" => (-102.5, 23.9),
+        "MY" => (102.0, 4.2),
+        "MZ" => (35.5, -17.3),
+        "NA" => (17.2, -22.1),
+        "NE" => (9.4, 17.4),
+        "NG" => (8.1, 9.6),
+        "NI" => (-85.0, 12.9),
+        "NL" => (5.3, 52.1),
+        "NO" => (9.0, 61.4),
+        "NP" => (84.0, 28.3),
+        "NZ" => (172.0, -41.8),
+        "OM" => (56.1, 20.6),
+        "PA" => (-80.1, 8.5),
+        "PE" => (-74.4, -9.2),
+        "PG" => (145.2, -6.5),
+        "PH" => (122.9, 11.8),
+        "PK" => (69.4, 29.9),
+        "PL" => (19.4, 52.1),
+        "PR" => (-66.5, 18.2),
+        "PS" => (35.2, 31.9),
+        "PT" => (-8.2, 39.6),
+        "PY" => (-58.4, -23.2),
+        "QA" => (51.2, 25.3),
+        "RO" => (25.0, 45.9),
+        "RS" => (20.8, 44.2),
+        "RU" => (96.7, 61.9),
+        "RW" => (29.9, -2.0),
+        "SA" => (44.5, 24.1),
+        "SB" => (159.6, -9.6),
+        "SD" => (29.9, 16.0),
+        "SE" => (16.7, 62.8),
+        "SG" => (103.8, 1.4),
+        "SI" => (14.8, 46.1),
+        "SK" => (19.5, 48.7),
+        "SL" => (-11.8, 8.6),
+        "SN" => (-14.5, 14.4),
+        "SO" => (45.7, 4.8),
+        "SR" => (-55.9, 4.1),
+        "SS" => (30.2, 7.3),
+        "SV" => (-88.9, 13.7),
+        "SY" => (38.5, 35.0),
+        "SZ" => (31.5, -26.5),
+        "TD" => (18.7, 15.3),
+        "TG" => (0.9, 8.5),
+        "TH" => (101.0, 15.1),
+        "TJ" => (71.0, 38.5),
+        "TL" => (125.8, -8.8),
+        "TM" => (59.4, 39.1),
+        "TN" => (9.6, 34.1),
+        "TR" => (35.2, 39.1),
+        "TT" => (-61.2, 10.5),
+        "TW" => (121.0, 23.7),
+        "TZ" => (34.8, -6.3),
+        "UA" => (31.3, 49.0),
+        "UG" => (32.4, 1.3),
+        "US" => (-98.6, 39.8),
+        "UY" => (-56.0, -32.8),
+        "UZ" => (63.1, 41.8),
+        "VA" => (12.5, 41.9),
+        "VE" => (-66.2, 7.1),
+        "VN" => (106.3, 16.7),
+        "YE" => (47.6, 15.9),
+        "ZA" => (25.1, -29.0),
+        "ZM" => (27.8, -13.5),
+        "ZW" => (29.9, -19.0),
+        _ => return None,
+    };
+    Some(centroid)
+}
@@ -0,0 +1,358 @@
+//! Module containing a minimal parser of DNS messages, used to recognize the DNS traffic and
+//! to learn the domain names associated to the IP addresses from the sniffed responses (passive DNS).
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Length of the DNS message header
+const HEADER_LEN: usize = 12;
+/// Maximum number of compression pointers followed while reading a name (avoids loops)
+const MAX_POINTERS: usize = 16;
+/// Record type A (IPv4 address)
+const TYPE_A: u16 = 1;
+/// Record type CNAME (canonical name)
+const TYPE_CNAME: u16 = 5;
+/// Record type AAAA (IPv6 address)
+const TYPE_AAAA: u16 = 28;
+/// Record class IN (Internet)
+const CLASS_IN: u16 = 1;
+
+/// Determines if the input is a well-formed standard query or response of the DNS (or mDNS) protocol,
+/// checking its header and its first record.
+pub fn is_dns_message(message: &[u8]) -> bool {
+    let (flags, questions, answers) = match (
+        read_u16(message, 2),
+        read_u16(message, 4),
+        read_u16(message, 6),
+    ) {
+        (Some(flags), Some(questions), Some(answers)) => (flags, questions, answers),
+        _ => return false,
+    };
+    let opcode = (flags >> 11) & 0x000F;
+    if opcode != 0 || questions > 4 || (questions == 0 && answers == 0) {
+        return false;
+    }
+    // the first record is a question, or an answer of responses without questions (as in mDNS)
+    match read_name(message, HEADER_LEN) {
+        // the most significant bit of the class is used by mDNS
+        Some((_, next)) => {
+            matches!(read_u16(message, next + 2), Some(class) if class & 0x7FFF == CLASS_IN)
+        }
+        None => false,
+    }
+}
+
+/// Returns the transaction ID of a DNS message, together with a flag indicating if it's a response.
+///
+/// Returns `None` if the input is not a DNS message.
+pub fn get_dns_id(message: &[u8]) -> Option<(u16, bool)> {
+    if !is_dns_message(message) {
+        return None;
+    }
+    let flags = read_u16(message, 2)?;
+    Some((read_u16(message, 0)?, flags & 0x8000 != 0))
+}
+
+/// Parses a DNS response message, returning the addresses it contains, each one associated
+/// to the domain name that was originally queried (i.e. following back the CNAME chains).
+///
+/// Messages that are not successful responses return an empty vector;
+/// truncated messages return the addresses found before the truncation point.
+/// Names that are not valid host names (e.g. containing spaces or control characters) are discarded.
+///
+/// # Arguments
+///
+/// * `message` - The DNS message, as carried by UDP (for TCP, the 2 bytes length prefix must be removed).
+pub fn parse_dns_response(message: &[u8]) -> Vec<(IpAddr, String)> {
+    let mut ret_val = Vec::new();
+    if message.len() < HEADER_LEN {
+        return ret_val;
+    }
+    let flags = read_u16(message, 2).unwrap_or(0);
+    let is_response = flags & 0x8000 != 0;
+    let response_code = flags & 0x000F;
+    if !is_response || response_code != 0 {
+        return ret_val;
+    }
+    let questions = read_u16(message, 4).unwrap_or(0);
+    let answers = read_u16(message, 6).unwrap_or(0);
+
+    // skip the question section
+    let mut offset = HEADER_LEN;
+    for _ in 0..questions {
+        match read_name(message, offset) {
+            Some((_, next)) => offset = next + 4,
+            None => return ret_val,
+        }
+    }
+
+    // alias => canonical name
+    let mut cnames: HashMap<String, String> = HashMap::new();
+    // owner name of each address record
+    let mut addresses: Vec<(IpAddr, String)> = Vec::new();
+    for _ in 0..answers {
+        let (name, next) = match read_name(message, offset) {
+            Some(name_next) => name_next,
+            None => break,
+        };
+        let (record_type, data_len) = match (read_u16(message, next), read_u16(message, next + 8)) {
+            (Some(record_type), Some(data_len)) => (record_type, usize::from(data_len)),
+            _ => break,
+        };
+        let data_start = next + 10;
+        let data = match message.get(data_start..data_start + data_len) {
+            Some(data) => data,
+            None => break,
+        };
+        match (record_type, data.len()) {
+            (TYPE_A, 4) => {
+                let octets: [u8; 4] = data.try_into().unwrap();
+                addresses.push((IpAddr::V4(Ipv4Addr::from(octets)), name));
+            }
+            (TYPE_AAAA, 16) => {
+                let octets: [u8; 16] = data.try_into().unwrap();
+                addresses.push((IpAddr::V6(Ipv6Addr::from(octets)), name));
+            }
+            (TYPE_CNAME, _) => {
+                if let Some((canonical, _)) = read_name(message, data_start) {
+                    cnames.insert(canonical, name);
+                }
+            }
+            _ => {}
+        }
+        offset = data_start + data_len;
+    }
+
+    for (address, mut name) in addresses {
+        // walk the CNAME chain back to the queried name
+        for _ in 0..cnames.len() {
+            match cnames.get(&name) {
+                Some(alias) => name = alias.clone(),
+                None => break,
+            }
+        }
+        // names of other records (e.g. mDNS service instances) can contain any character,
+        // so they are only checked here, before being shown in the GUI and in the reports
+        if is_hostname(&name) {
+            ret_val.push((address, name));
+        }
+    }
+    ret_val
+}
+
+/// Determines if all the labels of a name only contain letters, digits, hyphens and underscores
+fn is_hostname(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// Reads a big endian u16 at the given offset
+fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    let bytes = message.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// Reads the (possibly compressed) domain name starting at the given offset.
+///
+/// Returns the lowercase name and the offset following it in the message.
+fn read_name(message: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    // offset following the name, known once the first pointer is found
+    let mut next = None;
+    let mut pointers = 0;
+    loop {
+        let len = *message.get(offset)?;
+        match len & 0xC0 {
+            0x00 => {
+                if len == 0 {
+                    let next = next.unwrap_or(offset + 1);
+                    return Some((labels.join("."), next));
+                }
+                let label = message.get(offset + 1..offset + 1 + usize::from(len))?;
+                labels.push(String::from_utf8_lossy(label).to_lowercase());
+                offset += 1 + usize::from(len);
+            }
+            0xC0 => {
+                pointers += 1;
+                if pointers > MAX_POINTERS {
+                    return None;
+                }
+                let pointer = usize::from(read_u16(message, offset)? & 0x3FFF);
+                next.get_or_insert(offset + 2);
+                offset = pointer;
+            }
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a domain name without compression
+    fn encode_name(name: &str) -> Vec<u8> {
+        let mut ret_val = Vec::new();
+        for label in name.split('.') {
+            ret_val.push(u8::try_from(label.len()).unwrap());
+            ret_val.extend_from_slice(label.as_bytes());
+        }
+        ret_val.push(0);
+        ret_val
+    }
+
+    /// Encodes a resource record of class IN with the given owner name (already encoded)
+    fn encode_record(owner: &[u8], record_type: u16, data: &[u8]) -> Vec<u8> {
+        let mut ret_val = owner.to_vec();
+        ret_val.extend_from_slice(&record_type.to_be_bytes());
+        ret_val.extend_from_slice(&1_u16.to_be_bytes());
+        ret_val.extend_from_slice(&300_u32.to_be_bytes());
+        ret_val.extend_from_slice(&u16::try_from(data.len()).unwrap().to_be_bytes());
+        ret_val.extend_from_slice(data);
+        ret_val
+    }
+
+    fn encode_header(flags: u16, questions: u16, answers: u16) -> Vec<u8> {
+        let mut ret_val = vec![0x12, 0x34];
+        ret_val.extend_from_slice(&flags.to_be_bytes());
+        ret_val.extend_from_slice(&questions.to_be_bytes());
+        ret_val.extend_from_slice(&answers.to_be_bytes());
+        ret_val.extend_from_slice(&[0, 0, 0, 0]);
+        ret_val
+    }
+
+    fn encode_question(name: &str, record_type: u16) -> Vec<u8> {
+        let mut ret_val = encode_name(name);
+        ret_val.extend_from_slice(&record_type.to_be_bytes());
+        ret_val.extend_from_slice(&1_u16.to_be_bytes());
+        ret_val
+    }
+
+    #[test]
+    fn simple_a_response_with_compression() {
+        let mut message = encode_header(0x8180, 1, 1);
+        message.extend(encode_question("Example.COM", TYPE_A));
+        // pointer to the question name, placed right after the header
+        message.extend(encode_record(&[0xC0, 12], TYPE_A, &[93, 184, 216, 34]));
+        assert_eq!(
+            parse_dns_response(&message),
+            vec![(
+                IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)),
+                "example.com".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn cname_chain_is_followed_back_to_the_queried_name() {
+        let mut message = encode_header(0x8180, 1, 3);
+        message.extend(encode_question("www.example.com", TYPE_AAAA));
+        message.extend(encode_record(
+            &encode_name("www.example.com"),
+            TYPE_CNAME,
+            &encode_name("www.example.com-v1.edgesuite.net"),
+        ));
+        message.extend(encode_record(
+            &encode_name("www.example.com-v1.edgesuite.net"),
+            TYPE_CNAME,
+            &encode_name("a1234.dscb.akamai.net"),
+        ));
+        let address = Ipv6Addr::new(0x2a02, 0x26f0, 0, 0, 0, 0, 0, 0x1);
+        message.extend(encode_record(
+            &encode_name("a1234.dscb.akamai.net"),
+            TYPE_AAAA,
+            &address.octets(),
+        ));
+        assert_eq!(
+            parse_dns_response(&message),
+            vec![(IpAddr::V6(address), "www.example.com".to_string())]
+        );
+    }
+
+    #[test]
+    fn queries_and_errors_are_ignored() {
+        let mut query = encode_header(0x0100, 1, 0);
+        query.extend(encode_question("example.com", TYPE_A));
+        assert!(parse_dns_response(&query).is_empty());
+
+        // NXDOMAIN
+        let mut error = encode_header(0x8183, 1, 1);
+        error.extend(encode_question("example.com", TYPE_A));
+        error.extend(encode_record(&[0xC0, 12], TYPE_A, &[1, 2, 3, 4]));
+        assert!(parse_dns_response(&error).is_empty());
+    }
+
+    #[test]
+    fn truncated_message_keeps_complete_answers() {
+        let mut message = encode_header(0x8180, 1, 2);
+        message.extend(encode_question("example.com", TYPE_A));
+        message.extend(encode_record(&[0xC0, 12], TYPE_A, &[1, 2, 3, 4]));
+        message.extend(encode_record(&[0xC0, 12], TYPE_A, &[5, 6, 7, 8]));
+        message.truncate(message.len() - 2);
+        assert_eq!(
+            parse_dns_response(&message),
+            vec![(
+                IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)),
+                "example.com".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn dns_messages_are_recognized() {
+        let mut query = encode_header(0x0100, 1, 0);
+        query.extend(encode_question("example.com", TYPE_A));
+        assert!(is_dns_message(&query));
+        let mut mdns_response = encode_header(0x8400, 0, 1);
+        mdns_response.extend(encode_record(
+            &encode_name("printer.local"),
+            TYPE_A,
+            &[192, 168, 1, 10],
+        ));
+        assert!(is_dns_message(&mdns_response));
+        assert!(!is_dns_message(b"GET / HTTP/1.1\r\n\r\n"));
+        assert!(!is_dns_message(&encode_header(0x0100, 0, 0)));
+    }
+
+    #[test]
+    fn names_with_invalid_characters_are_discarded() {
+        for name in [
+            "bad,name.com",
+            "evil\nname.com",
+            "quote\".com",
+            "caf\u{e9}.com",
+        ] {
+            let mut message = encode_header(0x8180, 1, 1);
+            message.extend(encode_question(name, TYPE_A));
+            message.extend(encode_record(&[0xC0, 12], TYPE_A, &[1, 2, 3, 4]));
+            assert!(parse_dns_response(&message).is_empty(), "{name}");
+        }
+    }
+
+    #[test]
+    fn pointer_loops_are_rejected() {
+        let mut message = encode_header(0x8180, 1, 1);
+        // the question name points to itself
+        message.extend_from_slice(&[0xC0, 12, 0, 1, 0, 1]);
+        message.extend(encode_record(&[0xC0, 12], TYPE_A, &[1, 2, 3, 4]));
+        assert!(parse_dns_response(&message).is_empty());
+    }
+
+    #[test]
+    fn transaction_id_of_queries_and_responses() {
+        let mut query = encode_header(0x0100, 1, 0);
+        query.extend(encode_name("example.com"));
+        query.extend_from_slice(&[0, 1, 0, 1]);
+        assert_eq!(get_dns_id(&query), Some((0x1234, false)));
+        let mut response = encode_header(0x8180, 1, 0);
+        response.extend(encode_name("example.com"));
+        response.extend_from_slice(&[0, 1, 0, 1]);
+        assert_eq!(get_dns_id(&response), Some((0x1234, true)));
+        assert_eq!(get_dns_id(b"not a dns message"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use iced::Color;
 use thousands::Separable;
@@ -23,10 +24,8 @@ pub fn get_percentage_string(observed: u128, filtered: u128) -> String {
 
 /// Computes the String representing the active filters
 pub fn get_active_filters_string(filters: &Filters, language: Language) -> String {
-    if filters.ip.eq(&IpVersion::Other)
-        && filters.application.eq(&AppProtocol::Other)
-        && filters.transport.eq(&TransProtocol::Other)
-    {
+    let active_filters = get_active_filters_list(filters);
+    if active_filters.is_empty() {
         format!(
             "{}\n   {}",
             active_filters_translation(language),
@@ -34,14 +33,8 @@ pub fn get_active_filters_string(filters: &Filters, language: Language) -> Strin
         )
     } else {
         let mut ret_val = active_filters_translation(language).to_string();
-        if filters.ip.ne(&IpVersion::Other) {
-            ret_val.push_str(&format!("\n   {}", filters.ip));
-        }
-        if filters.transport.ne(&TransProtocol::Other) {
-            ret_val.push_str(&format!("\n   {}", filters.transport));
-        }
-        if filters.application.ne(&AppProtocol::Other) {
-            ret_val.push_str(&format!("\n   {}", filters.application));
+        for filter in active_filters {
+            ret_val.push_str(&format!("\n   {filter}"));
         }
         ret_val
     }
@@ -50,14 +43,44 @@ pub fn get_active_filters_string(filters: &Filters, language: Language) -> Strin
 /// Computes the String representing the active filters, without line breaks
 pub fn get_active_filters_string_nobr(filters: &Filters, language: Language) -> String {
     let mut ret_val = active_filters_translation(language).to_string();
+    for filter in get_active_filters_list(filters) {
+        ret_val.push_str(&format!(" {filter}"));
+    }
+    ret_val
+}
+
+/// Returns a description of each of the filters different from their default value
+fn get_active_filters_list(filters: &Filters) -> Vec<String> {
+    let mut ret_val = Vec::new();
     if filters.ip.ne(&IpVersion::Other) {
-        ret_val.push_str(&format!(" {}", filters.ip));
+        ret_val.push(filters.ip.to_string());
     }
     if filters.transport.ne(&TransProtocol::Other) {
-        ret_val.push_str(&format!(" {}", filters.transport));
+        ret_val.push(filters.transport.to_string());
     }
     if filters.application.ne(&AppProtocol::Other) {
-        ret_val.push_str(&format!(" {}", filters.application));
+        ret_val.push(filters.application.to_string());
+    }
+    if !filters.bpf.trim().is_empty() {
+        ret_val.push(format!("BPF: {}", filters.bpf.trim()));
+    }
+    if !filters.addresses.trim().is_empty() {
+        ret_val.push(format!("IP: {}", filters.addresses.trim()));
+    }
+    if !filters.ports.trim().is_empty() {
+        ret_val.push(format!("Port: {}", filters.ports.trim()));
+    }
+    if !filters.countries.trim().is_empty() {
+        ret_val.push(filters.countries.trim().to_uppercase());
+    }
+    if !filters.server_names.trim().is_empty() {
+        ret_val.push(format!("Name: {}", filters.server_names.trim()));
+    }
+    if !filters.organizations.trim().is_empty() {
+        ret_val.push(format!("AS: {}", filters.organizations.trim()));
+    }
+    for traffic_type in &filters.traffic_types {
+        ret_val.push(traffic_type.to_string());
     }
     ret_val
 }
@@ -115,7 +138,7 @@ pub fn get_app_count_string(app_count: &HashMap<AppProtocol, u128>, tot_packets:
     }
 
     for entry in sorted_app_count {
-        let app_proto_string = format!("{:?}", entry.0);
+        let app_proto_string = String::from(entry.0.clone());
 
         let num_string = entry.1.separate_with_spaces().to_string();
 
@@ -139,6 +162,11 @@ pub fn get_app_count_string(app_count: &HashMap<AppProtocol, u128>, tot_packets:
     ret_val
 }
 
+/// Returns a String representing a round trip time in milliseconds
+pub fn get_formatted_rtt_string(rtt: Duration) -> String {
+    format!("{:.1} ms", rtt.as_secs_f64() * 1000.0)
+}
+
 /// Returns a String representing a quantity of bytes with their proper multiple (KB, MB, GB, TB)
 pub fn get_formatted_bytes_string(bytes: u128) -> String {
     let mut multiple_transmitted = String::new();
@@ -0,0 +1,60 @@
+//! Module containing the names of the ICMP and ICMPv6 message types, shown in the connection details.
+
+/// Returns the name of an ICMP message type, given its type number.
+///
+/// # Arguments
+///
+/// * `is_icmpv6` - Flag indicating if the message is an ICMPv6 one (carried by IPv6).
+///
+/// * `icmp_type` - The type number of the message.
+pub fn get_icmp_type_name(is_icmpv6: bool, icmp_type: u8) -> &'static str {
+    if is_icmpv6 {
+        match icmp_type {
+            1 => "Destination unreachable",
+            2 => "Packet too big",
+            3 => "Time exceeded",
+            4 => "Parameter problem",
+            128 => "Echo request",
+            129 => "Echo reply",
+            130 => "Multicast listener query",
+            131 | 143 => "Multicast listener report",
+            132 => "Multicast listener done",
+            133 => "Router solicitation",
+            134 => "Router advertisement",
+            135 => "Neighbor solicitation",
+            136 => "Neighbor advertisement",
+            137 => "Redirect",
+            _ => "Unknown",
+        }
+    } else {
+        match icmp_type {
+            0 => "Echo reply",
+            3 => "Destination unreachable",
+            4 => "Source quench",
+            5 => "Redirect",
+            8 => "Echo request",
+            9 => "Router advertisement",
+            10 => "Router solicitation",
+            11 => "Time exceeded",
+            12 => "Parameter problem",
+            13 => "Timestamp",
+            14 => "Timestamp reply",
+            _ => "Unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icmp_and_icmpv6_types_differ() {
+        assert_eq!(get_icmp_type_name(false, 8), "Echo request");
+        assert_eq!(get_icmp_type_name(true, 128), "Echo request");
+        assert_eq!(get_icmp_type_name(false, 3), "Destination unreachable");
+        assert_eq!(get_icmp_type_name(true, 3), "Time exceeded");
+        assert_eq!(get_icmp_type_name(true, 135), "Neighbor solicitation");
+        assert_eq!(get_icmp_type_name(false, 135), "Unknown");
+    }
+}
@@ -0,0 +1,319 @@
+//! Module containing the decoders of the link layer headers of the supported capture link types:
+//! Ethernet (with VLAN tags), BSD loopback, raw IP, Linux cooked captures and 802.11 with radiotap.
+
+use pcap::Linktype;
+
+/// EtherType of IPv4 packets
+const ETHER_TYPE_IPV4: u16 = 0x0800;
+/// EtherType of IPv6 packets
+const ETHER_TYPE_IPV6: u16 = 0x86DD;
+/// EtherTypes of the 802.1Q and 802.1ad VLAN tags
+const ETHER_TYPES_VLAN: [u16; 2] = [0x8100, 0x88A8];
+/// Address families used for IPv6 by the loopback headers of the different operating systems
+/// (Linux, FreeBSD, macOS and OpenBSD/NetBSD)
+const AF_INET6: [u32; 4] = [10, 28, 30, 24];
+/// Address family of IPv4
+const AF_INET: u32 = 2;
+/// Length of the header of Linux cooked captures (v1)
+const LINUX_SLL_LEN: usize = 16;
+/// Length of the header of Linux cooked captures (v2)
+const LINUX_SLL2_LEN: usize = 20;
+/// Logical Link Control header with SNAP extension, followed by the EtherType in 802.11 data frames
+const LLC_SNAP: [u8; 6] = [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x00];
+
+/// Decodes the link layer header of a packet captured with the given link type.
+///
+/// Returns the EtherType of the network layer protocol carried by the packet,
+/// together with the network layer packet itself;
+/// returns None for unsupported link types, truncated headers and frames not carrying any data
+/// (e.g. 802.11 management or encrypted frames).
+pub fn get_network_packet(linktype: Linktype, packet: &[u8]) -> Option<(u16, &[u8])> {
+    match linktype {
+        Linktype::ETHERNET => parse_ethernet(packet),
+        Linktype::NULL | Linktype::LOOP => parse_loopback(packet),
+        Linktype::RAW | Linktype::IPV4 | Linktype::IPV6 => parse_raw_ip(packet),
+        Linktype::LINUX_SLL => {
+            let ether_type = read_u16(packet, 14)?;
+            Some((ether_type, packet.get(LINUX_SLL_LEN..)?))
+        }
+        Linktype::LINUX_SLL2 => {
+            let ether_type = read_u16(packet, 0)?;
+            Some((ether_type, packet.get(LINUX_SLL2_LEN..)?))
+        }
+        Linktype::IEEE802_11 => parse_ieee802_11(packet),
+        Linktype::IEEE802_11_RADIOTAP => {
+            // the radiotap header length is little endian
+            let len = usize::from(u16::from_le_bytes([*packet.get(2)?, *packet.get(3)?]));
+            parse_ieee802_11(packet.get(len..)?)
+        }
+        _ => None,
+    }
+}
+
+/// Decodes an Ethernet frame, skipping its VLAN tags
+fn parse_ethernet(packet: &[u8]) -> Option<(u16, &[u8])> {
+    let mut offset = 12;
+    let mut ether_type = read_u16(packet, offset)?;
+    while ETHER_TYPES_VLAN.contains(&ether_type) {
+        offset += 4;
+        ether_type = read_u16(packet, offset)?;
+    }
+    Some((ether_type, packet.get(offset + 2..)?))
+}
+
+/// Decodes a loopback packet, whose header contains the address family
+/// (in the byte order of the capturing host for DLT_NULL, and in network byte order for DLT_LOOP)
+fn parse_loopback(packet: &[u8]) -> Option<(u16, &[u8])> {
+    let bytes: [u8; 4] = packet.get(0..4)?.try_into().ok()?;
+    let mut family = u32::from_be_bytes(bytes);
+    if family > 0xFFFF {
+        family = family.swap_bytes();
+    }
+    let ether_type = if family == AF_INET {
+        ETHER_TYPE_IPV4
+    } else if AF_INET6.contains(&family) {
+        ETHER_TYPE_IPV6
+    } else {
+        return None;
+    };
+    Some((ether_type, &packet[4..]))
+}
+
+/// Decodes a raw IP packet, whose version is found in its first nibble
+fn parse_raw_ip(packet: &[u8]) -> Option<(u16, &[u8])> {
+    match packet.first()? >> 4 {
+        4 => Some((ETHER_TYPE_IPV4, packet)),
+        6 => Some((ETHER_TYPE_IPV6, packet)),
+        _ => None,
+    }
+}
+
+/// Decodes an unencrypted 802.11 data frame carrying a LLC/SNAP header
+fn parse_ieee802_11(frame: &[u8]) -> Option<(u16, &[u8])> {
+    let (frame_control, flags) = (*frame.first()?, *frame.get(1)?);
+    let frame_type = (frame_control >> 2) & 0x03;
+    let subtype = frame_control >> 4;
+    // only data frames (type 2) carry packets, and their "null" subtypes have no body
+    if frame_type != 2 || subtype & 0x04 != 0 || flags & 0x40 != 0 {
+        return None;
+    }
+    let mut header_len = 24;
+    if flags & 0x03 == 0x03 {
+        // both "to DS" and "from DS": the frame carries a fourth address
+        header_len += 6;
+    }
+    if subtype & 0x08 != 0 {
+        // QoS data frames carry the QoS control field, possibly followed by the HT control field
+        header_len += 2;
+        if flags & 0x80 != 0 {
+            header_len += 4;
+        }
+    }
+    let body = frame.get(header_len..)?;
+    if !body.starts_with(&LLC_SNAP) {
+        return None;
+    }
+    let ether_type = read_u16(body, LLC_SNAP.len())?;
+    Some((ether_type, body.get(LLC_SNAP.len() + 2..)?))
+}
+
+/// Reads a big endian u16 at the given offset
+fn read_u16(packet: &[u8], offset: usize) -> Option<u16> {
+    let bytes = packet.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use etherparse::{IpHeader, PacketHeaders, TransportHeader};
+
+    use super::*;
+
+    /// ICMP echo request from 127.0.0.1 to 127.0.0.1
+    const IPV4_PING: [u8; 28] = [
+        0x45, 0x00, 0x00, 0x1c, 0x9e, 0x2b, 0x00, 0x00, 0x40, 0x01, 0xde, 0xb3, 0x7f, 0x00, 0x00,
+        0x01, 0x7f, 0x00, 0x00, 0x01, 0x08, 0x00, 0xf2, 0xd5, 0x05, 0x2a, 0x00, 0x00,
+    ];
+
+    /// UDP datagram from [fe80::1]:54321 to [ff02::fb]:5353, with an empty payload
+    const IPV6_UDP: [u8; 48] = [
+        0x60, 0x0c, 0x3a, 0x1f, 0x00, 0x08, 0x11, 0xff, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xff, 0x02, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xfb, 0xd4, 0x31, 0x14, 0xe9, 0x00,
+        0x08, 0x6a, 0x4c,
+    ];
+
+    /// ARP request for 192.168.1.1, sent by 192.168.1.5
+    const ARP_REQUEST: [u8; 28] = [
+        0x00, 0x01, 0x08, 0x00, 0x06, 0x04, 0x00, 0x01, 0x3c, 0x22, 0xfb, 0x9a, 0x10, 0x4e, 0xc0,
+        0xa8, 0x01, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0xa8, 0x01, 0x01,
+    ];
+
+    fn with_header(header: &[u8], packet: &[u8]) -> Vec<u8> {
+        let mut ret_val = header.to_vec();
+        ret_val.extend_from_slice(packet);
+        ret_val
+    }
+
+    /// Decodes the network and transport layers, checking they're those of the ping fixture
+    fn assert_ipv4_ping(ether_type: u16, network_packet: &[u8]) {
+        assert_eq!(ether_type, ETHER_TYPE_IPV4);
+        let headers = PacketHeaders::from_ether_type(ether_type, network_packet).unwrap();
+        match headers.ip {
+            Some(IpHeader::Version4(ipv4_header, _)) => {
+                assert_eq!(ipv4_header.source, [127, 0, 0, 1]);
+                assert_eq!(ipv4_header.destination, [127, 0, 0, 1]);
+            }
+            _ => panic!("IPv4 header expected"),
+        }
+        assert!(matches!(
+            headers.transport,
+            Some(TransportHeader::Icmpv4(_))
+        ));
+    }
+
+    /// Decodes the network and transport layers, checking they're those of the UDP fixture
+    fn assert_ipv6_udp(ether_type: u16, network_packet: &[u8]) {
+        assert_eq!(ether_type, ETHER_TYPE_IPV6);
+        let headers = PacketHeaders::from_ether_type(ether_type, network_packet).unwrap();
+        assert!(matches!(headers.ip, Some(IpHeader::Version6(_, _))));
+        match headers.transport {
+            Some(TransportHeader::Udp(udp_header)) => {
+                assert_eq!(udp_header.source_port, 54321);
+                assert_eq!(udp_header.destination_port, 5353);
+            }
+            _ => panic!("UDP header expected"),
+        }
+    }
+
+    #[test]
+    fn ethernet_with_vlan_tag() {
+        let header = [
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3c, 0x22, 0xfb, 0x9a, 0x10, 0x4e, 0x81, 0x00,
+            0x00, 0x64, 0x08, 0x06,
+        ];
+        let frame = with_header(&header, &ARP_REQUEST);
+        assert_eq!(
+            get_network_packet(Linktype::ETHERNET, &frame),
+            Some((0x0806, &ARP_REQUEST[..]))
+        );
+    }
+
+    #[test]
+    fn bsd_loopback() {
+        // DLT_NULL captured on a little endian host (AF_INET)
+        let packet = with_header(&[0x02, 0x00, 0x00, 0x00], &IPV4_PING);
+        let (ether_type, network_packet) = get_network_packet(Linktype::NULL, &packet).unwrap();
+        assert_ipv4_ping(ether_type, network_packet);
+        // DLT_NULL captured on macOS (AF_INET6 is 30)
+        let packet = with_header(&[0x1e, 0x00, 0x00, 0x00], &IPV6_UDP);
+        let (ether_type, network_packet) = get_network_packet(Linktype::NULL, &packet).unwrap();
+        assert_ipv6_udp(ether_type, network_packet);
+        // DLT_LOOP is in network byte order
+        let packet = with_header(&[0x00, 0x00, 0x00, 0x18], &IPV6_UDP);
+        let (ether_type, network_packet) = get_network_packet(Linktype::LOOP, &packet).unwrap();
+        assert_ipv6_udp(ether_type, network_packet);
+        // unknown address family
+        let packet = with_header(&[0x07, 0x00, 0x00, 0x00], &IPV4_PING);
+        assert_eq!(get_network_packet(Linktype::NULL, &packet), None);
+    }
+
+    #[test]
+    fn raw_ip() {
+        let (ether_type, network_packet) = get_network_packet(Linktype::RAW, &IPV4_PING).unwrap();
+        assert_ipv4_ping(ether_type, network_packet);
+        let (ether_type, network_packet) = get_network_packet(Linktype::RAW, &IPV6_UDP).unwrap();
+        assert_ipv6_udp(ether_type, network_packet);
+        assert_eq!(get_network_packet(Linktype::RAW, &[]), None);
+    }
+
+    #[test]
+    fn linux_cooked_capture() {
+        // incoming packet, ARPHRD_ETHER, 6 bytes address padded to 8
+        let header = [
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x06, 0x3c, 0x22, 0xfb, 0x9a, 0x10, 0x4e, 0x00, 0x00,
+            0x08, 0x00,
+        ];
+        let packet = with_header(&header, &IPV4_PING);
+        let (ether_type, network_packet) =
+            get_network_packet(Linktype::LINUX_SLL, &packet).unwrap();
+        assert_ipv4_ping(ether_type, network_packet);
+    }
+
+    #[test]
+    fn linux_cooked_capture_v2() {
+        // protocol, reserved, interface index 2, ARPHRD_ETHER, outgoing packet, address
+        let header = [
+            0x86, 0xdd, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x04, 0x06, 0x3c, 0x22,
+            0xfb, 0x9a, 0x10, 0x4e, 0x00, 0x00,
+        ];
+        let packet = with_header(&header, &IPV6_UDP);
+        let (ether_type, network_packet) =
+            get_network_packet(Linktype::LINUX_SLL2, &packet).unwrap();
+        assert_ipv6_udp(ether_type, network_packet);
+    }
+
+    #[test]
+    fn radiotap_data_frames() {
+        // radiotap header with flags, rate and antenna signal fields
+        let radiotap = [
+            0x00, 0x00, 0x0b, 0x00, 0x26, 0x00, 0x00, 0x00, 0x00, 0x02, 0xc4,
+        ];
+        // data frame from the access point ("from DS")
+        let data_header = [
+            0x08, 0x02, 0x2c, 0x00, 0x3c, 0x22, 0xfb, 0x9a, 0x10, 0x4e, 0xa4, 0x2b, 0xb0, 0xfe,
+            0x31, 0x9c, 0xa4, 0x2b, 0xb0, 0xfe, 0x31, 0x9c, 0x50, 0x3e,
+        ];
+        let mut frame = with_header(&radiotap, &data_header);
+        frame.extend_from_slice(&LLC_SNAP);
+        frame.extend_from_slice(&[0x08, 0x00]);
+        frame.extend_from_slice(&IPV4_PING);
+        let (ether_type, network_packet) =
+            get_network_packet(Linktype::IEEE802_11_RADIOTAP, &frame).unwrap();
+        assert_ipv4_ping(ether_type, network_packet);
+
+        // QoS data frame to the access point ("to DS")
+        let qos_data_header = [
+            0x88, 0x01, 0x2c, 0x00, 0xa4, 0x2b, 0xb0, 0xfe, 0x31, 0x9c, 0x3c, 0x22, 0xfb, 0x9a,
+            0x10, 0x4e, 0x33, 0x33, 0x00, 0x00, 0x00, 0xfb, 0x60, 0x3e, 0x00, 0x00,
+        ];
+        let mut frame = with_header(&radiotap, &qos_data_header);
+        frame.extend_from_slice(&LLC_SNAP);
+        frame.extend_from_slice(&[0x86, 0xdd]);
+        frame.extend_from_slice(&IPV6_UDP);
+        let (ether_type, network_packet) =
+            get_network_packet(Linktype::IEEE802_11_RADIOTAP, &frame).unwrap();
+        assert_ipv6_udp(ether_type, network_packet);
+    }
+
+    #[test]
+    fn radiotap_frames_without_packets() {
+        let radiotap = [0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00];
+        // beacon (management frame)
+        let mut beacon = with_header(&radiotap, &[0x80, 0x00, 0x00, 0x00]);
+        beacon.extend_from_slice(&[0xff; 20]);
+        assert_eq!(
+            get_network_packet(Linktype::IEEE802_11_RADIOTAP, &beacon),
+            None
+        );
+        // protected data frame
+        let mut protected = with_header(&radiotap, &[0x08, 0x41, 0x2c, 0x00]);
+        protected.extend_from_slice(&[0x00; 20]);
+        protected.extend_from_slice(&LLC_SNAP);
+        protected.extend_from_slice(&[0x08, 0x00]);
+        assert_eq!(
+            get_network_packet(Linktype::IEEE802_11_RADIOTAP, &protected),
+            None
+        );
+        // truncated radiotap header
+        assert_eq!(
+            get_network_packet(Linktype::IEEE802_11_RADIOTAP, &radiotap[..3]),
+            None
+        );
+    }
+
+    #[test]
+    fn unsupported_link_type() {
+        assert_eq!(get_network_packet(Linktype(147), &IPV4_PING), None);
+    }
+}
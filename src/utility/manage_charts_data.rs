@@ -1,12 +1,51 @@
 use std::cell::RefMut;
 use std::collections::VecDeque;
 
+use crate::structs::traffic_totals::TrafficTotals;
 use crate::RunTimeData;
 
 /// This function is invoked every second by the application subscription
 ///
 /// It updates data (packets and bytes per second) to be displayed in the charts of gui run page
 pub fn update_charts_data(mut charts_data: RefMut<RunTimeData>) {
+    push_charts_data(&mut charts_data);
+}
+
+/// Updates the charts of a capture file with the intervals closed on the file's own timeline,
+/// instead of the ones elapsed since the file is being parsed
+///
+/// # Arguments
+///
+/// * `intervals` - Closed intervals, each with its second on the file's timeline and the traffic totals at its end.
+pub fn update_file_charts_data(
+    mut charts_data: RefMut<RunTimeData>,
+    intervals: impl IntoIterator<Item = (u32, TrafficTotals)>,
+) {
+    // the displayed totals are the current ones, not the ones at the end of the last interval
+    let current_totals = (
+        charts_data.tot_sent_bytes,
+        charts_data.tot_received_bytes,
+        charts_data.tot_sent_packets,
+        charts_data.tot_received_packets,
+    );
+    for (second, totals) in intervals {
+        charts_data.ticks = second;
+        charts_data.tot_sent_bytes = totals.sent_bytes;
+        charts_data.tot_received_bytes = totals.received_bytes;
+        charts_data.tot_sent_packets = totals.sent_packets;
+        charts_data.tot_received_packets = totals.received_packets;
+        push_charts_data(&mut charts_data);
+    }
+    (
+        charts_data.tot_sent_bytes,
+        charts_data.tot_received_bytes,
+        charts_data.tot_sent_packets,
+        charts_data.tot_received_packets,
+    ) = current_totals;
+}
+
+/// Adds to the charts the traffic exchanged since the previous point
+fn push_charts_data(charts_data: &mut RunTimeData) {
     let tot_seconds = charts_data.ticks;
     charts_data.ticks += 1;
 
@@ -78,3 +117,32 @@ fn get_max(deque: &VecDeque<(u32, i64)>) -> i64 {
     }
     max
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[test]
+    fn file_charts_follow_the_file_timeline() {
+        let charts_data = RefCell::new(RunTimeData::new());
+        charts_data.borrow_mut().tot_sent_bytes = 900;
+        let totals = |sent_bytes| TrafficTotals {
+            sent_bytes,
+            ..TrafficTotals::default()
+        };
+        update_file_charts_data(
+            charts_data.borrow_mut(),
+            [(0, totals(100)), (1, totals(100)), (40, totals(700))],
+        );
+        let charts_data = charts_data.borrow();
+        assert_eq!(
+            charts_data.sent_bytes,
+            VecDeque::from([(0, -100), (1, 0), (40, -600)])
+        );
+        assert_eq!(charts_data.ticks, 41);
+        assert_eq!(charts_data.tot_sent_bytes, 900);
+        assert_eq!(charts_data.tot_sent_bytes_prev, 700);
+    }
+}
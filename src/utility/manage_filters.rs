@@ -0,0 +1,252 @@
+//! Module containing functions to parse and apply the textual filters inserted by the user
+//! (IP addresses/networks, ports, countries, server names and organizations).
+
+use std::net::IpAddr;
+use std::ops::RangeInclusive;
+
+use pcap::{Capture, Linktype};
+
+use crate::structs::asn::Asn;
+
+/// Checks that the given BPF expression compiles, so that it's rejected before any capture is opened.
+///
+/// The expression is compiled for Ethernet frames; pcap compiles it again for the actual link type
+/// of each opened capture.
+pub fn validate_bpf_filter(filter: &str) -> Result<(), String> {
+    if filter.trim().is_empty() {
+        return Ok(());
+    }
+    if filter.contains('\0') {
+        return Err("invalid BPF expression: unexpected NUL character".to_string());
+    }
+    Capture::dead(Linktype::ETHERNET)
+        .and_then(|cap| cap.compile(filter, true))
+        .map(|_| ())
+        .map_err(|err| format!("invalid BPF expression: {err}"))
+}
+
+/// Parses a comma separated list of IP addresses or networks in CIDR notation
+/// (e.g. `192.168.1.1, 10.0.0.0/8, fe80::/10`), returning each one as a (network address, prefix length) pair.
+pub fn parse_address_filter(filter: &str) -> Result<Vec<(IpAddr, u8)>, String> {
+    let mut ret_val = Vec::new();
+    for item in split_filter(filter) {
+        let (address, prefix) = match item.split_once('/') {
+            None => (item, None),
+            Some((address, prefix)) => (address, Some(prefix)),
+        };
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| format!("invalid IP address: {item}"))?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            None => max_prefix,
+            Some(prefix) => match prefix.parse::<u8>() {
+                Ok(prefix) if prefix <= max_prefix => prefix,
+                _ => return Err(format!("invalid network prefix: {item}")),
+            },
+        };
+        ret_val.push((address, prefix));
+    }
+    Ok(ret_val)
+}
+
+/// Parses a comma separated list of ports or port ranges (e.g. `53, 8000-8100`).
+pub fn parse_port_filter(filter: &str) -> Result<Vec<RangeInclusive<u16>>, String> {
+    let mut ret_val = Vec::new();
+    for item in split_filter(filter) {
+        let (lowest, highest) = item.split_once('-').unwrap_or((item, item));
+        match (lowest.trim().parse::<u16>(), highest.trim().parse::<u16>()) {
+            (Ok(lowest), Ok(highest)) if lowest <= highest => ret_val.push(lowest..=highest),
+            _ => return Err(format!("invalid port range: {item}")),
+        }
+    }
+    Ok(ret_val)
+}
+
+/// Parses a comma separated list of ISO 3166-1 alpha-2 country codes (e.g. `IT, us`),
+/// returning them in upper case.
+pub fn parse_country_filter(filter: &str) -> Result<Vec<String>, String> {
+    let mut ret_val = Vec::new();
+    for item in split_filter(filter) {
+        if item.len() != 2 || !item.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("invalid country code: {item}"));
+        }
+        ret_val.push(item.to_ascii_uppercase());
+    }
+    Ok(ret_val)
+}
+
+/// Parses a comma separated list of (parts of) server names (e.g. `google, github.com`),
+/// returning them in lower case.
+pub fn parse_server_name_filter(filter: &str) -> Vec<String> {
+    split_filter(filter).map(str::to_lowercase).collect()
+}
+
+/// Determines if the input name contains at least one of the given (lower case) names
+pub fn is_name_in_filter(name: &str, names: &[String]) -> bool {
+    let name = name.to_lowercase();
+    names.iter().any(|filter| name.contains(filter.as_str()))
+}
+
+/// Parses a comma separated list of AS numbers or (parts of) organization names (e.g. `AS13335, amazon`),
+/// returning them in lower case.
+pub fn parse_organization_filter(filter: &str) -> Vec<String> {
+    split_filter(filter).map(str::to_lowercase).collect()
+}
+
+/// Determines if the input autonomous system has one of the given (lower case) AS numbers
+/// or its organization name contains one of the given names
+pub fn is_asn_in_filter(asn: Option<&Asn>, organizations: &[String]) -> bool {
+    asn.is_some_and(|asn| {
+        let number = format!("as{}", asn.number);
+        let name = asn.name.to_lowercase();
+        organizations
+            .iter()
+            .any(|filter| filter.eq(&number) || name.contains(filter.as_str()))
+    })
+}
+
+/// Determines if the input address belongs to at least one of the given networks.
+///
+/// # Arguments
+///
+/// * `address` - string representing an IPv4 or IPv6 network address.
+///
+/// * `networks` - (network address, prefix length) pairs, as returned by `parse_address_filter`.
+pub fn is_address_in_networks(address: &str, networks: &[(IpAddr, u8)]) -> bool {
+    let address = match address.parse::<IpAddr>() {
+        Ok(address) => address,
+        Err(_) => return false,
+    };
+    networks
+        .iter()
+        .any(|(network, prefix)| match (address, network) {
+            (IpAddr::V4(address), IpAddr::V4(network)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(*prefix)).unwrap_or(0);
+                u32::from(address) & mask == u32::from(*network) & mask
+            }
+            (IpAddr::V6(address), IpAddr::V6(network)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(*prefix)).unwrap_or(0);
+                u128::from(address) & mask == u128::from(*network) & mask
+            }
+            _ => false,
+        })
+}
+
+/// Splits a comma separated filter in its trimmed, non-empty items
+fn split_filter(filter: &str) -> impl Iterator<Item = &str> {
+    filter
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filters_are_valid() {
+        assert_eq!(parse_address_filter(" "), Ok(vec![]));
+        assert_eq!(parse_port_filter(""), Ok(vec![]));
+        assert_eq!(parse_country_filter(" , "), Ok(vec![]));
+        assert_eq!(validate_bpf_filter("  "), Ok(()));
+    }
+
+    #[test]
+    fn valid_bpf_expression() {
+        assert_eq!(
+            validate_bpf_filter("net 192.168.1.0/24 and (tcp port 443 or udp port 53)"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn invalid_bpf_expression() {
+        assert!(validate_bpf_filter("port 443 and").is_err());
+        assert!(validate_bpf_filter("net 10.0.0.0/33").is_err());
+        assert!(validate_bpf_filter("port 443\0").is_err());
+    }
+
+    #[test]
+    fn addresses_and_networks() {
+        let networks = parse_address_filter("192.168.1.0/24, 8.8.8.8, fe80::/10").unwrap();
+        assert!(is_address_in_networks("192.168.1.77", &networks));
+        assert!(!is_address_in_networks("192.168.2.1", &networks));
+        assert!(is_address_in_networks("8.8.8.8", &networks));
+        assert!(!is_address_in_networks("8.8.4.4", &networks));
+        assert!(is_address_in_networks("fe80::1c04:41c", &networks));
+        assert!(!is_address_in_networks("ff02::fb", &networks));
+    }
+
+    #[test]
+    fn zero_prefix_matches_everything() {
+        let networks = parse_address_filter("0.0.0.0/0").unwrap();
+        assert!(is_address_in_networks("151.101.1.69", &networks));
+        assert!(!is_address_in_networks("::1", &networks));
+    }
+
+    #[test]
+    fn invalid_addresses() {
+        assert!(parse_address_filter("192.168.1").is_err());
+        assert!(parse_address_filter("10.0.0.0/33").is_err());
+        assert!(parse_address_filter("::/129").is_err());
+    }
+
+    #[test]
+    fn ports_and_ranges() {
+        assert_eq!(
+            parse_port_filter("53, 8000 - 8100"),
+            Ok(vec![53..=53, 8000..=8100])
+        );
+        assert!(parse_port_filter("100-10").is_err());
+        assert!(parse_port_filter("65536").is_err());
+    }
+
+    #[test]
+    fn country_codes() {
+        assert_eq!(
+            parse_country_filter("it,Us"),
+            Ok(vec!["IT".to_string(), "US".to_string()])
+        );
+        assert!(parse_country_filter("ITA").is_err());
+    }
+
+    #[test]
+    fn server_names() {
+        let names = parse_server_name_filter("Google, github.com,");
+        assert_eq!(names, vec!["google".to_string(), "github.com".to_string()]);
+        assert!(is_name_in_filter("www.google.it", &names));
+        assert!(is_name_in_filter("API.GitHub.com", &names));
+        assert!(!is_name_in_filter("github.io", &names));
+    }
+
+    #[test]
+    fn organizations() {
+        let organizations = parse_organization_filter("AS13335, Amazon");
+        let cloudflare = Asn {
+            number: 13335,
+            name: "CLOUDFLARENET".to_string(),
+        };
+        let amazon = Asn {
+            number: 16509,
+            name: "AMAZON-02".to_string(),
+        };
+        let google = Asn {
+            number: 15169,
+            name: "GOOGLE".to_string(),
+        };
+        assert!(is_asn_in_filter(Some(&cloudflare), &organizations));
+        assert!(is_asn_in_filter(Some(&amazon), &organizations));
+        assert!(!is_asn_in_filter(Some(&google), &organizations));
+        assert!(!is_asn_in_filter(None, &organizations));
+        // the number must match exactly
+        assert!(!is_asn_in_filter(
+            Some(&Asn {
+                number: 133350,
+                name: String::new()
+            }),
+            &organizations
+        ));
+    }
+}
@@ -81,12 +81,12 @@ pub fn notify_and_log(mut runtime_data: RefMut<RunTimeData>, notifications: Noti
             .clone();
         runtime_data
             .logged_notifications
-            .push_front(LoggedNotification::FavoriteTransmitted(
+            .push_front(LoggedNotification::FavoriteTransmitted(Box::new(
                 FavoriteTransmitted {
                     connection: favorite_featured,
                     timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
                 },
-            ));
+            )));
         if !already_emitted_sound && notifications.favorite_notification.sound.ne(&Sound::None) {
             // emit sound
             play_sound(
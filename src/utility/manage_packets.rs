@@ -1,22 +1,39 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use chrono::Local;
+use chrono::{DateTime, Local, TimeZone};
 use etherparse::{IpHeader, TransportHeader};
-use maxminddb::Reader;
-use pcap::{Active, Capture, Device};
+use pcap::{Activated, Capture, Device, PacketHeader};
 
 use crate::enums::app_protocol::from_port_to_application_protocol;
+use crate::enums::byte_counting::ByteCounting;
+use crate::enums::capture_source::CaptureSource;
+use crate::enums::detection_method::DetectionMethod;
+use crate::enums::rtt_probe::RttProbe;
 use crate::enums::traffic_type::TrafficType;
 use crate::structs::address_port_pair::AddressPortPair;
 use crate::structs::info_address_port_pair::InfoAddressPortPair;
-use crate::utility::countries::get_country_code;
+use crate::structs::mmdb_readers::MmdbReaders;
+use crate::structs::port_mapping::PortMapping;
+use crate::structs::rtt_stats::RttStats;
+use crate::structs::tcp_info::TcpSegment;
+use crate::utility::countries::{get_asn, get_city, get_country_code};
+use crate::utility::dns_parser::{get_dns_id, parse_dns_response};
+use crate::utility::manage_filters::is_name_in_filter;
+use crate::utility::payload_signatures::get_payload_protocol;
 use crate::{AppProtocol, InfoTraffic, IpVersion, TransProtocol};
 
+/// EtherType of ARP packets
+pub const ARP_ETHER_TYPE: u16 = 0x0806;
+/// ARP protocol type of IPv4 addresses
+const ARP_IPV4_PROTOCOL_TYPE: u16 = 0x0800;
+
 /// This function analyzes the network layer header passed as parameter and updates variables
 /// passed by reference on the basis of the packet header content.
 pub fn analyze_network_header(
     network_header: Option<IpHeader>,
-    exchanged_bytes: &mut u128,
     network_protocol: &mut IpVersion,
     address1: &mut String,
     address2: &mut String,
@@ -35,13 +52,11 @@ pub fn analyze_network_header(
                 .replace(']', "")
                 .replace(',', ".")
                 .replace(' ', "");
-            *exchanged_bytes = u128::from(ipv4header.payload_len);
         }
         Some(IpHeader::Version6(ipv6header, _)) => {
             *network_protocol = IpVersion::IPv6;
             *address1 = ipv6_from_long_dec_to_short_hex(ipv6header.source);
             *address2 = ipv6_from_long_dec_to_short_hex(ipv6header.destination);
-            *exchanged_bytes = u128::from(ipv6header.payload_length);
         }
         _ => {
             *skip_packet = true;
@@ -49,63 +64,331 @@ pub fn analyze_network_header(
     }
 }
 
+/// Returns the number of bytes of an IP packet to be counted, according to the selected counting mode.
+///
+/// Lengths are taken from the headers, so that they're correct even if the packet was truncated
+/// by the snapshot length.
+///
+/// # Arguments
+///
+/// * `byte_counting` - How the bytes of the packets are counted.
+///
+/// * `frame_len` - The length of the whole frame on the wire, as reported by pcap.
+///
+/// * `network_header` - The IP header of the packet, with its extensions.
+///
+/// * `transport_header` - The transport layer header of the packet.
+pub fn get_exchanged_bytes(
+    byte_counting: ByteCounting,
+    frame_len: u32,
+    network_header: &Option<IpHeader>,
+    transport_header: &Option<TransportHeader>,
+) -> u128 {
+    // total length of the IP packet and length of the IP payload (extension headers excluded)
+    let (network_len, network_payload_len) = match network_header {
+        Some(IpHeader::Version4(ipv4header, extensions)) => (
+            ipv4header.header_len() + usize::from(ipv4header.payload_len),
+            usize::from(ipv4header.payload_len).saturating_sub(extensions.header_len()),
+        ),
+        Some(IpHeader::Version6(ipv6header, extensions)) => (
+            ipv6header.header_len() + usize::from(ipv6header.payload_length),
+            usize::from(ipv6header.payload_length).saturating_sub(extensions.header_len()),
+        ),
+        None => return u128::from(frame_len),
+    };
+    match byte_counting {
+        ByteCounting::Frame => u128::from(frame_len),
+        ByteCounting::Network => network_len as u128,
+        ByteCounting::Payload => network_payload_len.saturating_sub(
+            transport_header
+                .as_ref()
+                .map_or(0, TransportHeader::header_len),
+        ) as u128,
+    }
+}
+
+/// This function analyzes the payload of ARP packets (resolving IPv4 addresses) and updates
+/// variables passed by reference with the sender and target protocol addresses.
+pub fn analyze_arp_packet(
+    payload: &[u8],
+    exchanged_bytes: &mut u128,
+    network_protocol: &mut IpVersion,
+    address1: &mut String,
+    address2: &mut String,
+    transport_protocol: &mut TransProtocol,
+    skip_packet: &mut bool,
+) {
+    match parse_arp_addresses(payload) {
+        Some((sender, target, len)) => {
+            *network_protocol = IpVersion::IPv4;
+            *address1 = sender.to_string();
+            *address2 = target.to_string();
+            *exchanged_bytes = len as u128;
+            *transport_protocol = TransProtocol::ARP;
+        }
+        None => {
+            *skip_packet = true;
+        }
+    }
+}
+
+/// Parses an ARP packet for IPv4 addresses, returning the sender and target protocol addresses
+/// together with the length of the packet
+fn parse_arp_addresses(payload: &[u8]) -> Option<(Ipv4Addr, Ipv4Addr, usize)> {
+    // hardware type, protocol type, hardware address length, protocol address length, operation
+    let (protocol_type, hardware_len, protocol_len) = match payload {
+        [_, _, p0, p1, hardware_len, protocol_len, _, _, ..] => (
+            u16::from_be_bytes([*p0, *p1]),
+            usize::from(*hardware_len),
+            usize::from(*protocol_len),
+        ),
+        _ => return None,
+    };
+    if protocol_type != ARP_IPV4_PROTOCOL_TYPE || protocol_len != 4 {
+        return None;
+    }
+    let sender_start = 8 + hardware_len;
+    let target_start = sender_start + 4 + hardware_len;
+    let sender: [u8; 4] = payload
+        .get(sender_start..sender_start + 4)?
+        .try_into()
+        .ok()?;
+    let target: [u8; 4] = payload
+        .get(target_start..target_start + 4)?
+        .try_into()
+        .ok()?;
+    Some((
+        Ipv4Addr::from(sender),
+        Ipv4Addr::from(target),
+        target_start + 4,
+    ))
+}
+
 /// This function analyzes the transport layer header passed as parameter and updates variables
 /// passed by reference on the basis of the packet header content.
+///
+/// Protocols without ports (ICMP and the ones not identified) get the port number 0.
+///
+/// The port mappings defined by the user are consulted before the well-known ports.
 pub fn analyze_transport_header(
     transport_header: Option<TransportHeader>,
+    port_mappings: &[PortMapping],
     port1: &mut u16,
     port2: &mut u16,
     application_protocol: &mut AppProtocol,
     transport_protocol: &mut TransProtocol,
-    skip_packet: &mut bool,
 ) {
     match transport_header {
         Some(TransportHeader::Udp(udp_header)) => {
             *port1 = udp_header.source_port;
             *port2 = udp_header.destination_port;
             *transport_protocol = TransProtocol::UDP;
-            *application_protocol = from_port_to_application_protocol(*port1);
-            if (*application_protocol).eq(&AppProtocol::Other) {
-                *application_protocol = from_port_to_application_protocol(*port2);
-            }
         }
         Some(TransportHeader::Tcp(tcp_header)) => {
             *port1 = tcp_header.source_port;
             *port2 = tcp_header.destination_port;
             *transport_protocol = TransProtocol::TCP;
-            *application_protocol = from_port_to_application_protocol(*port1);
-            if (*application_protocol).eq(&AppProtocol::Other) {
-                *application_protocol = from_port_to_application_protocol(*port2);
-            }
         }
-        _ => {
-            *skip_packet = true;
+        Some(TransportHeader::Icmpv4(_) | TransportHeader::Icmpv6(_)) => {
+            *port1 = 0;
+            *port2 = 0;
+            *transport_protocol = TransProtocol::ICMP;
+            return;
+        }
+        None => {
+            *port1 = 0;
+            *port2 = 0;
+            *transport_protocol = TransProtocol::Other;
+            return;
         }
     }
+    *application_protocol = port_mappings
+        .iter()
+        .find_map(|mapping| {
+            mapping
+                .get_app_protocol(*port1, *transport_protocol)
+                .or_else(|| mapping.get_app_protocol(*port2, *transport_protocol))
+        })
+        .unwrap_or_else(|| match from_port_to_application_protocol(*port1) {
+            AppProtocol::Other => from_port_to_application_protocol(*port2),
+            app_protocol => app_protocol,
+        });
+}
+
+/// Returns the type and code of ICMP and ICMPv6 messages
+pub fn get_icmp_type_code(transport_header: &Option<TransportHeader>) -> Option<(u8, u8)> {
+    match transport_header {
+        // type and code are the first two bytes of the header
+        Some(TransportHeader::Icmpv4(icmp_header)) => {
+            let bytes = icmp_header.to_bytes();
+            Some((bytes[0], bytes[1]))
+        }
+        Some(TransportHeader::Icmpv6(icmp_header)) => {
+            let bytes = icmp_header.to_bytes();
+            Some((bytes[0], bytes[1]))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the sequence number, payload length and flags of TCP segments
+pub fn get_tcp_segment(
+    network_header: &Option<IpHeader>,
+    transport_header: &Option<TransportHeader>,
+) -> Option<TcpSegment> {
+    match transport_header {
+        Some(TransportHeader::Tcp(tcp_header)) => Some(TcpSegment {
+            sequence_number: tcp_header.sequence_number,
+            // lengths declared by the headers are not affected by the snapshot length
+            payload_len: u32::try_from(get_exchanged_bytes(
+                ByteCounting::Payload,
+                0,
+                network_header,
+                transport_header,
+            ))
+            .unwrap_or(u32::MAX),
+            syn: tcp_header.syn,
+            ack: tcp_header.ack,
+            fin: tcp_header.fin,
+            rst: tcp_header.rst,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the kind of request or response carried by a packet, used to measure round trip times,
+/// together with a flag indicating if it's a response.
+///
+/// # Arguments
+///
+/// * `payload` - The transport layer payload of the packet.
+///
+/// * `source_port` - The transport layer source port of the packet.
+///
+/// * `destination_port` - The transport layer destination port of the packet.
+///
+/// * `transport_protocol` - The transport layer protocol of the packet.
+///
+/// * `tcp_segment` - The relevant fields of the TCP header, if any.
+pub fn get_rtt_probe(
+    payload: &[u8],
+    source_port: u16,
+    destination_port: u16,
+    transport_protocol: TransProtocol,
+    tcp_segment: Option<TcpSegment>,
+) -> Option<(RttProbe, bool)> {
+    if let Some(segment) = tcp_segment {
+        if segment.syn && !segment.rst {
+            return Some((RttProbe::TcpHandshake, segment.ack));
+        }
+    }
+    if source_port != 53 && destination_port != 53 {
+        return None;
+    }
+    let message = match transport_protocol {
+        TransProtocol::UDP => payload,
+        // DNS messages over TCP are prefixed by their length
+        TransProtocol::TCP if payload.len() > 2 => &payload[2..],
+        _ => return None,
+    };
+    let (id, is_response) = get_dns_id(message)?;
+    Some((RttProbe::DnsQuery(id), is_response))
+}
+
+/// Adds a round trip time measurement to the connection with the given key, if present in the map
+pub fn add_rtt_measurement(info_traffic: &mut InfoTraffic, key: &AddressPortPair, rtt: Duration) {
+    if let Some(info) = info_traffic.map.get_mut(key) {
+        match info.rtt.as_mut() {
+            Some(rtt_stats) => rtt_stats.add(rtt),
+            None => info.rtt = Some(RttStats::new(rtt)),
+        }
+    }
+}
+
+/// Parses the payload of packets sent by DNS servers, returning the addresses
+/// contained in the response together with the domain names queried to obtain them.
+pub fn get_dns_answers(
+    payload: &[u8],
+    source_port: u16,
+    transport_protocol: TransProtocol,
+) -> Vec<(IpAddr, String)> {
+    if source_port != 53 {
+        return Vec::new();
+    }
+    match transport_protocol {
+        TransProtocol::UDP => parse_dns_response(payload),
+        // DNS messages over TCP are prefixed by their length
+        TransProtocol::TCP if payload.len() > 2 => parse_dns_response(&payload[2..]),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the application layer protocol of a connection and the method used to detect it.
+///
+/// The protocol is decided at the first packet of each connection and kept for its whole duration:
+/// the labels of the user's port mappings come first, then the signatures found in the payload,
+/// then the guess based on the well-known ports.
+pub fn detect_application_protocol(
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    key: &AddressPortPair,
+    payload: &[u8],
+    port_guess: AppProtocol,
+) -> (AppProtocol, DetectionMethod) {
+    if let Some(info) = info_traffic_mutex
+        .lock()
+        .expect("Error acquiring mutex\n\r")
+        .map
+        .get(key)
+    {
+        return (info.app_protocol.clone(), info.detection_method);
+    }
+    match get_payload_protocol(payload, key.trans_protocol, port_guess.clone()) {
+        Some(app_protocol) => (app_protocol, DetectionMethod::Signature),
+        None => (port_guess, DetectionMethod::Port),
+    }
 }
 
 /// Function to insert the source and destination of a packet into the shared map containing the analyzed traffic.
+///
+/// Returns the index of the connection if it's a new one.
 pub fn modify_or_insert_in_map(
     info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
-    key: AddressPortPair,
+    key: &AddressPortPair,
     exchanged_bytes: u128,
     traffic_type: TrafficType,
-    application_protocol: AppProtocol,
-    country_db_reader: &Reader<&[u8]>,
-) {
-    let now = Local::now();
+    (application_protocol, detection_method): (AppProtocol, DetectionMethod),
+    mmdb_readers: &MmdbReaders,
+    timestamp: DateTime<Local>,
+) -> Option<usize> {
     let very_long_address = key.address1.len() > 25 || key.address2.len() > 25;
     let mut info_traffic = info_traffic_mutex
         .lock()
         .expect("Error acquiring mutex\n\r");
     let len = info_traffic.map.len();
-    let index = info_traffic.map.get_index_of(&key).unwrap_or(len);
-    let country = if index == len {
-        // first occurrence of key => retrieve country code
-        get_country_code(traffic_type, &key, country_db_reader)
+    let index = info_traffic.map.get_index_of(key).unwrap_or(len);
+    let (country, asn, (city, coordinates), domain) = if index == len {
+        // first occurrence of key => retrieve country code, autonomous system, city and domain name
+        let domain = get_remote_address(traffic_type, key)
+            .parse::<IpAddr>()
+            .ok()
+            .and_then(|address| info_traffic.dns_table.get(&address).cloned());
+        (
+            get_country_code(traffic_type, key, &mmdb_readers.country),
+            mmdb_readers
+                .asn
+                .as_ref()
+                .and_then(|reader| get_asn(traffic_type, key, reader)),
+            mmdb_readers
+                .city
+                .as_ref()
+                .map_or((String::new(), None), |reader| {
+                    get_city(traffic_type, key, reader)
+                }),
+            domain,
+        )
     } else {
         // this key already occurred
-        String::new()
+        (String::new(), None, (String::new(), None), None)
     };
     let is_already_featured = info_traffic.favorite_featured_last_interval.is_some();
     let mut update_favorite_featured = false;
@@ -115,7 +398,7 @@ pub fn modify_or_insert_in_map(
         .and_modify(|info| {
             info.transmitted_bytes += exchanged_bytes;
             info.transmitted_packets += 1;
-            info.final_timestamp = now;
+            info.final_timestamp = timestamp;
             if info.is_favorite && !is_already_featured {
                 update_favorite_featured = true;
             }
@@ -123,19 +406,100 @@ pub fn modify_or_insert_in_map(
         .or_insert(InfoAddressPortPair {
             transmitted_bytes: exchanged_bytes,
             transmitted_packets: 1,
-            initial_timestamp: now,
-            final_timestamp: now,
+            // the direction is accounted by the caller
+            sent_bytes: 0,
+            sent_packets: 0,
+            received_bytes: 0,
+            received_packets: 0,
+            initial_timestamp: timestamp,
+            final_timestamp: timestamp,
             app_protocol: application_protocol,
+            detection_method,
             very_long_address,
             traffic_type,
             country,
+            asn,
+            city,
+            coordinates,
+            hostname: None,
+            domain,
+            server_name: None,
+            icmp_types: HashMap::new(),
+            interfaces: Vec::new(),
+            tcp_info: None,
+            rtt: None,
             index,
             is_favorite: false,
         });
     info_traffic.addresses_last_interval.insert(index);
     if update_favorite_featured {
-        let info = info_traffic.map.get(&key).unwrap().clone();
-        info_traffic.favorite_featured_last_interval = Some((key, info));
+        let info = info_traffic.map.get(key).unwrap().clone();
+        info_traffic.favorite_featured_last_interval = Some((key.clone(), info));
+    }
+    if index == len {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// Determines if a connection is known with a name matching the server names filter.
+///
+/// The considered names are the server name found in the current packet and the names
+/// already associated to the connection or to its remote address.
+pub fn is_connection_name_in_filter(
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    key: &AddressPortPair,
+    traffic_type: TrafficType,
+    server_name: Option<&str>,
+    names: &[String],
+) -> bool {
+    let info_traffic = info_traffic_mutex
+        .lock()
+        .expect("Error acquiring mutex\n\r");
+    let info = info_traffic.map.get(key);
+    let domain = get_remote_address(traffic_type, key)
+        .parse::<IpAddr>()
+        .ok()
+        .and_then(|address| info_traffic.dns_table.get(&address));
+    server_name
+        .into_iter()
+        .chain(info.and_then(|info| info.server_name.as_deref()))
+        .chain(info.and_then(|info| info.domain.as_deref()))
+        .chain(info.and_then(|info| info.hostname.as_deref()))
+        .chain(domain.map(String::as_str))
+        .any(|name| is_name_in_filter(name, names))
+}
+
+/// Returns the key under which a packet is inserted in the map, together with a flag
+/// indicating if the packet travels in the opposite direction of the key.
+///
+/// For bidirectional flows, the packets of both directions of a conversation share the key
+/// oriented as the first packet seen (usually sent by the client).
+pub fn get_flow_key(
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    key: AddressPortPair,
+    bidirectional_flows: bool,
+) -> (AddressPortPair, bool) {
+    if bidirectional_flows {
+        let reversed_key = key.reversed();
+        if info_traffic_mutex
+            .lock()
+            .expect("Error acquiring mutex\n\r")
+            .map
+            .contains_key(&reversed_key)
+        {
+            return (reversed_key, true);
+        }
+    }
+    (key, false)
+}
+
+/// Returns the address of the remote host of a connection, given its traffic type
+pub fn get_remote_address(traffic_type: TrafficType, key: &AddressPortPair) -> &str {
+    match traffic_type {
+        TrafficType::Outgoing => &key.address2,
+        _ => &key.address1,
     }
 }
 
@@ -193,19 +557,97 @@ pub fn is_broadcast_address(address: &str) -> bool {
 }
 
 /// Determines if the capture opening resolves into an Error
-pub fn get_capture_result(device: &Device) -> (Option<String>, Option<Capture<Active>>) {
-    let cap_result = Capture::from_device(&*device.name)
-        .expect("Capture initialization error\n\r")
-        .promisc(true)
-        .snaplen(256) //limit stored packets slice dimension (to keep more in the buffer)
-        .immediate_mode(true) //parse packets ASAP!
-        .open();
-    if cap_result.is_err() {
-        let err_string = cap_result.err().unwrap().to_string();
-        (Some(err_string), None)
-    } else {
-        (None, cap_result.ok())
+///
+/// Depending on the selected capture source, packets are sniffed live from the given network adapter
+/// or read from the given pcap/pcapng capture file.
+pub fn get_capture_result(
+    device: &Device,
+    capture_source: CaptureSource,
+    capture_file: &str,
+    bpf: &str,
+    snaplen: i32,
+) -> (Option<String>, Option<Capture<dyn Activated>>) {
+    let cap_result: Result<Capture<dyn Activated>, pcap::Error> = match capture_source {
+        CaptureSource::Adapter => Capture::from_device(&*device.name)
+            .expect("Capture initialization error\n\r")
+            .promisc(true)
+            .snaplen(snaplen) //limit stored packets slice dimension (to keep more in the buffer)
+            .immediate_mode(true) //parse packets ASAP!
+            .open()
+            .map(Into::into),
+        CaptureSource::File => Capture::from_file(capture_file).map(Into::into),
+    };
+    match cap_result {
+        Err(err) => (Some(err.to_string()), None),
+        Ok(mut cap) => {
+            // an invalid BPF expression is reported like any other pcap error
+            if !bpf.trim().is_empty() {
+                if let Err(err) = cap.filter(bpf, true) {
+                    return (Some(err.to_string()), None);
+                }
+            }
+            (None, Some(cap))
+        }
+    }
+}
+
+/// Opened captures, each one paired with the network adapter it sniffs
+pub type AdapterCaptures = Vec<(Device, Capture<dyn Activated>)>;
+
+/// Opens a capture for each of the given network adapters (or a single capture of the given file).
+///
+/// Returns the first error found, preceded by the name of the adapter if more of them are sniffed.
+pub fn get_captures_result(
+    devices: &[Device],
+    capture_source: CaptureSource,
+    capture_file: &str,
+    bpf: &str,
+    snaplen: i32,
+) -> Result<AdapterCaptures, String> {
+    // the adapter is not used when reading a file, which is thus paired with a placeholder if none is given
+    let file_device;
+    let devices = match capture_source {
+        CaptureSource::Adapter => devices,
+        CaptureSource::File => {
+            file_device = [devices
+                .first()
+                .cloned()
+                .unwrap_or_else(|| Device::from(capture_file))];
+            &file_device[..]
+        }
+    };
+    if devices.is_empty() {
+        return Err("no network adapter selected".to_string());
+    }
+    let mut captures = Vec::new();
+    for device in devices {
+        match get_capture_result(device, capture_source, capture_file, bpf, snaplen) {
+            (Some(error), _) if devices.len() > 1 => {
+                return Err(format!("{}: {error}", device.name));
+            }
+            (Some(error), _) => return Err(error),
+            (None, Some(cap)) => captures.push((device.clone(), cap)),
+            (None, None) => {}
+        }
+    }
+    if captures.is_empty() {
+        return Err("no capture opened".to_string());
     }
+    Ok(captures)
+}
+
+/// Returns the time instant at which a packet was captured, as reported in its pcap header.
+///
+/// Packets read from a capture file are thus placed on the file's own timeline.
+///
+/// Malformed headers (e.g. with more than a second of microseconds) are timestamped with the current time.
+pub fn get_packet_timestamp(header: &PacketHeader) -> DateTime<Local> {
+    let secs = header.ts.tv_sec;
+    u32::try_from(header.ts.tv_usec)
+        .ok()
+        .filter(|micros| *micros < 1_000_000)
+        .and_then(|micros| Local.timestamp_opt(secs, micros * 1000).single())
+        .unwrap_or_else(Local::now)
 }
 
 // Test for this function at the end of this file (run with cargo test)
@@ -303,7 +745,24 @@ pub fn ipv6_from_long_dec_to_short_hex(ipv6_long: [u8; 16]) -> String {
 
 #[cfg(test)]
 mod test {
-    use crate::utility::manage_packets::ipv6_from_long_dec_to_short_hex;
+    use std::net::Ipv4Addr;
+    use std::sync::{Arc, Mutex};
+
+    use chrono::Local;
+    use etherparse::icmpv6::DestUnreachableCode;
+    use etherparse::{Icmpv6Type, PacketBuilder, PacketHeaders};
+    use pcap::PacketHeader;
+
+    use crate::enums::byte_counting::ByteCounting;
+    use crate::enums::traffic_type::TrafficType;
+    use crate::structs::address_port_pair::AddressPortPair;
+    use crate::structs::info_address_port_pair::InfoAddressPortPair;
+    use crate::utility::manage_packets::{
+        get_captures_result, get_exchanged_bytes, get_flow_key, get_icmp_type_code,
+        get_packet_timestamp, ipv6_from_long_dec_to_short_hex, parse_arp_addresses,
+    };
+    use crate::CaptureSource;
+    use crate::{AppProtocol, InfoTraffic, TransProtocol};
 
     #[test]
     fn ipv6_simple_test() {
@@ -404,4 +863,148 @@ mod test {
             ipv6_from_long_dec_to_short_hex([0, 16, 16, 0, 0, 1, 7, 0, 0, 2, 216, 0, 1, 0, 0, 1]);
         assert_eq!(result, "10:1000:1:700:2:d800:100:1".to_string());
     }
+
+    #[test]
+    fn arp_request_addresses() {
+        // Ethernet, IPv4, request
+        let mut request = vec![0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x01];
+        request.extend_from_slice(&[0x11; 6]);
+        request.extend_from_slice(&[192, 168, 1, 5]);
+        request.extend_from_slice(&[0x00; 6]);
+        request.extend_from_slice(&[192, 168, 1, 1]);
+        // Ethernet padding
+        request.extend_from_slice(&[0x00; 18]);
+        assert_eq!(
+            parse_arp_addresses(&request),
+            Some((
+                Ipv4Addr::new(192, 168, 1, 5),
+                Ipv4Addr::new(192, 168, 1, 1),
+                28
+            ))
+        );
+    }
+
+    #[test]
+    fn arp_for_other_protocols_or_truncated() {
+        // protocol type different from IPv4
+        let mut packet = vec![0x00, 0x01, 0x86, 0xdd, 6, 4, 0x00, 0x01];
+        packet.extend_from_slice(&[0x00; 20]);
+        assert_eq!(parse_arp_addresses(&packet), None);
+        let truncated = [0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x02, 0x11, 0x11];
+        assert_eq!(parse_arp_addresses(&truncated), None);
+    }
+
+    #[test]
+    fn exchanged_bytes_counting_modes() {
+        let builder = PacketBuilder::ipv4([192, 168, 1, 10], [192, 168, 1, 1], 64).udp(5000, 53);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &[0; 10]).unwrap();
+        // truncated by the snapshot length: lengths still come from the headers
+        let headers = PacketHeaders::from_ip_slice(&packet[..30]).unwrap();
+        let count =
+            |byte_counting| get_exchanged_bytes(byte_counting, 52, &headers.ip, &headers.transport);
+        assert_eq!(count(ByteCounting::Frame), 52);
+        assert_eq!(count(ByteCounting::Network), 38);
+        assert_eq!(count(ByteCounting::Payload), 10);
+    }
+
+    #[test]
+    fn icmp_types_and_codes() {
+        let mut packet = Vec::new();
+        PacketBuilder::ipv4([192, 168, 1, 10], [1, 1, 1, 1], 64)
+            .icmpv4_echo_request(1, 1)
+            .write(&mut packet, &[])
+            .unwrap();
+        let headers = PacketHeaders::from_ip_slice(&packet).unwrap();
+        assert_eq!(get_icmp_type_code(&headers.transport), Some((8, 0)));
+
+        let mut packet = Vec::new();
+        PacketBuilder::ipv6([0; 16], [1; 16], 64)
+            .icmpv6(Icmpv6Type::DestinationUnreachable(
+                DestUnreachableCode::Port,
+            ))
+            .write(&mut packet, &[])
+            .unwrap();
+        let headers = PacketHeaders::from_ip_slice(&packet).unwrap();
+        assert_eq!(get_icmp_type_code(&headers.transport), Some((1, 4)));
+
+        let mut packet = Vec::new();
+        PacketBuilder::ipv4([192, 168, 1, 10], [1, 1, 1, 1], 64)
+            .udp(5000, 53)
+            .write(&mut packet, &[])
+            .unwrap();
+        let headers = PacketHeaders::from_ip_slice(&packet).unwrap();
+        assert_eq!(get_icmp_type_code(&headers.transport), None);
+    }
+
+    #[test]
+    fn flow_keys_of_both_directions() {
+        let request = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            50000,
+            "1.1.1.1".to_string(),
+            443,
+            TransProtocol::TCP,
+        );
+        let response = AddressPortPair::new(
+            "1.1.1.1".to_string(),
+            443,
+            "192.168.1.10".to_string(),
+            50000,
+            TransProtocol::TCP,
+        );
+        let info_traffic_mutex = Arc::new(Mutex::new(InfoTraffic::new()));
+        // the first packet seen defines the orientation of the flow
+        assert_eq!(
+            get_flow_key(&info_traffic_mutex, request.clone(), true),
+            (request.clone(), false)
+        );
+        let info = InfoAddressPortPair {
+            transmitted_bytes: 60,
+            transmitted_packets: 1,
+            sent_bytes: 60,
+            sent_packets: 1,
+            ..InfoAddressPortPair::new_for_tests(AppProtocol::HTTPS, TrafficType::Outgoing, 0)
+        };
+        info_traffic_mutex
+            .lock()
+            .unwrap()
+            .map
+            .insert(request.clone(), info);
+        assert_eq!(
+            get_flow_key(&info_traffic_mutex, response.clone(), true),
+            (request, true)
+        );
+        // directional mode
+        assert_eq!(
+            get_flow_key(&info_traffic_mutex, response.clone(), false),
+            (response, false)
+        );
+    }
+
+    #[test]
+    fn no_adapter_is_an_error() {
+        assert_eq!(
+            get_captures_result(&[], CaptureSource::Adapter, "", "", 256).err(),
+            Some("no network adapter selected".to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_timestamps_are_rejected() {
+        // the header has no public constructor, and all zeros is a valid one
+        let mut header: PacketHeader = unsafe { std::mem::zeroed() };
+        header.ts.tv_sec = 1_600_000_000;
+        header.ts.tv_usec = 999_999;
+        assert_eq!(
+            get_packet_timestamp(&header).timestamp_micros(),
+            1_600_000_000_999_999
+        );
+
+        let before = Local::now();
+        for tv_usec in [1_000_000, 5_000_000, -1] {
+            header.ts.tv_usec = tv_usec;
+            assert!(get_packet_timestamp(&header) >= before);
+        }
+    }
 }
@@ -1,51 +1,247 @@
+use crate::enums::country_sorting::CountrySorting;
+use crate::enums::host_sorting::HostSorting;
+use crate::enums::report_column::ReportColumn;
 use crate::structs::address_port_pair::AddressPortPair;
+use crate::structs::country_info::CountryInfo;
+use crate::structs::host_info::HostInfo;
 use crate::structs::info_address_port_pair::InfoAddressPortPair;
+use crate::structs::report_table::{get_pages_number, ReportTable, CONNECTIONS_PER_PAGE};
+use crate::utility::manage_packets::get_remote_address;
 use crate::{InfoTraffic, ReportType, RunTimeData};
 use std::cell::RefMut;
-use std::cmp::min;
+use std::cmp::{min, Ordering, Reverse};
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 
+/// Updates the connections displayed in the current page of the overview table,
+/// searched and sorted according to the table state.
+///
+/// If an interface is given, only the connections seen on it are displayed.
+/// If a country is given, only the connections with its hosts are displayed.
+/// The displayed page is moved back if it no longer contains any connection.
 pub fn update_report_data(
     mut runtime_data: RefMut<RunTimeData>,
     info_traffic: &Arc<Mutex<InfoTraffic>>,
     report_type: ReportType,
+    interface: Option<&str>,
+    country: Option<&str>,
+    report_table: &mut ReportTable,
 ) {
+    let search = report_table.search.trim().to_lowercase();
+    let is_shown = |key: &AddressPortPair, info: &InfoAddressPortPair| {
+        interface.is_none_or(|name| info.interfaces.iter().any(|i| i.eq(name)))
+            && country.is_none_or(|code| info.country.eq(code))
+            && matches_search(key, info, &search)
+    };
     let info_traffic_lock = info_traffic.lock().unwrap();
-    runtime_data.report_vec = Vec::default();
 
-    if report_type.ne(&ReportType::Favorites) {
-        let mut sorted_vec: Vec<(&AddressPortPair, &InfoAddressPortPair)> =
-            info_traffic_lock.map.iter().collect();
+    let mut sorted_vec: Vec<(&AddressPortPair, &InfoAddressPortPair)> =
+        if report_type.eq(&ReportType::Favorites) {
+            info_traffic_lock
+                .favorite_connections
+                .iter()
+                .filter_map(|index| info_traffic_lock.map.get_index(*index))
+                .filter(|&(key, info)| is_shown(key, info))
+                .collect()
+        } else {
+            info_traffic_lock
+                .map
+                .iter()
+                .filter(|&(key, info)| is_shown(key, info))
+                .collect()
+        };
+    sorted_vec.sort_by(|a, b| {
+        compare_connections(report_table.sort_column, report_table.descending, *a, *b)
+    });
 
-        match report_type {
-            ReportType::MostRecent => {
-                sorted_vec.sort_by(|&(_, a), &(_, b)| b.final_timestamp.cmp(&a.final_timestamp));
-            }
-            ReportType::MostPackets => {
-                sorted_vec
-                    .sort_by(|&(_, a), &(_, b)| b.transmitted_packets.cmp(&a.transmitted_packets));
+    report_table.page = min(report_table.page, get_pages_number(sorted_vec.len()) - 1);
+    runtime_data.report_len = sorted_vec.len();
+    runtime_data.report_vec = sorted_vec
+        .into_iter()
+        .skip(report_table.page * CONNECTIONS_PER_PAGE)
+        .take(CONNECTIONS_PER_PAGE)
+        .map(|(key, info)| (key.clone(), info.clone()))
+        .collect();
+}
+
+/// Determines if a connection contains the searched text (already in lowercase) in its addresses, ports,
+/// country, protocols or host names
+fn matches_search(key: &AddressPortPair, info: &InfoAddressPortPair, search: &str) -> bool {
+    if search.is_empty() {
+        return true;
+    }
+    let fields = [
+        key.address1.clone(),
+        key.address2.clone(),
+        key.port1.to_string(),
+        key.port2.to_string(),
+        info.country.clone(),
+        key.trans_protocol.to_string(),
+        info.app_protocol.to_string(),
+    ];
+    fields
+        .iter()
+        .map(String::as_str)
+        .chain(info.hostname.as_deref())
+        .chain(info.domain.as_deref())
+        .chain(info.server_name.as_deref())
+        .any(|field| field.to_lowercase().contains(search))
+}
+
+/// Compares two connections according to the given column of the overview table.
+///
+/// The connections whose latency was not measured are always placed after the others.
+fn compare_connections(
+    column: ReportColumn,
+    descending: bool,
+    (key_a, info_a): (&AddressPortPair, &InfoAddressPortPair),
+    (key_b, info_b): (&AddressPortPair, &InfoAddressPortPair),
+) -> Ordering {
+    // addresses are compared numerically when possible
+    let compare_addresses = |a: &str, b: &str| match (a.parse::<IpAddr>(), b.parse::<IpAddr>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    };
+    let ordering = match column {
+        ReportColumn::SrcAddress => compare_addresses(&key_a.address1, &key_b.address1),
+        ReportColumn::SrcPort => key_a.port1.cmp(&key_b.port1),
+        ReportColumn::DstAddress => compare_addresses(&key_a.address2, &key_b.address2),
+        ReportColumn::DstPort => key_a.port2.cmp(&key_b.port2),
+        ReportColumn::TransProtocol => key_a
+            .trans_protocol
+            .to_string()
+            .cmp(&key_b.trans_protocol.to_string()),
+        ReportColumn::AppProtocol => info_a
+            .app_protocol
+            .to_string()
+            .cmp(&info_b.app_protocol.to_string()),
+        ReportColumn::Packets => info_a.transmitted_packets.cmp(&info_b.transmitted_packets),
+        ReportColumn::Bytes => info_a.transmitted_bytes.cmp(&info_b.transmitted_bytes),
+        ReportColumn::LastSeen => info_a.final_timestamp.cmp(&info_b.final_timestamp),
+        ReportColumn::Latency => match (info_a.rtt, info_b.rtt) {
+            (Some(rtt_a), Some(rtt_b)) => rtt_a.avg().cmp(&rtt_b.avg()),
+            (rtt_a, rtt_b) => {
+                return rtt_b.is_some().cmp(&rtt_a.is_some());
             }
-            ReportType::MostBytes => {
-                sorted_vec
-                    .sort_by(|&(_, a), &(_, b)| b.transmitted_bytes.cmp(&a.transmitted_bytes));
+        },
+        ReportColumn::Country => info_a.country.cmp(&info_b.country),
+    };
+    if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+/// Updates the remote hosts displayed in the hosts page, aggregating the connections by remote address
+/// and sorting them according to the given criterion.
+///
+/// If requested, the hosts belonging to the same autonomous system are aggregated as well.
+pub fn update_hosts_data(
+    mut runtime_data: RefMut<RunTimeData>,
+    info_traffic: &Arc<Mutex<InfoTraffic>>,
+    host_sorting: HostSorting,
+    group_by_organization: bool,
+) {
+    let mut hosts_vec = if group_by_organization {
+        get_organizations(get_hosts(info_traffic))
+    } else {
+        get_hosts(info_traffic)
+    };
+
+    match host_sorting {
+        HostSorting::Recent => {
+            hosts_vec.sort_by_key(|host| Reverse(host.last_seen));
+        }
+        HostSorting::Packets => {
+            hosts_vec.sort_by_key(|host| Reverse(host.packets_in + host.packets_out));
+        }
+        HostSorting::Bytes => {
+            hosts_vec.sort_by_key(|host| Reverse(host.bytes_in + host.bytes_out));
+        }
+        HostSorting::Connections => {
+            hosts_vec.sort_by_key(|host| Reverse(host.connections));
+        }
+    }
+    runtime_data.hosts_vec = hosts_vec;
+}
+
+/// Updates the countries displayed in the countries page, aggregating the remote hosts by country
+/// and sorting them according to the given criterion.
+pub fn update_countries_data(
+    mut runtime_data: RefMut<RunTimeData>,
+    info_traffic: &Arc<Mutex<InfoTraffic>>,
+    country_sorting: CountrySorting,
+) {
+    let mut countries: HashMap<String, CountryInfo> = HashMap::new();
+    for host_info in get_hosts(info_traffic) {
+        match countries.get_mut(&host_info.country) {
+            Some(country_info) => country_info.add_host(&host_info),
+            None => {
+                countries.insert(host_info.country.clone(), CountryInfo::new(&host_info));
             }
-            ReportType::Favorites => {}
         }
+    }
+    let mut countries_vec: Vec<CountryInfo> = countries.into_values().collect();
 
-        let n_entry = min(sorted_vec.len(), 15);
-        for i in 0..n_entry {
-            let key_val = *sorted_vec.get(i).unwrap();
-            runtime_data
-                .report_vec
-                .push((key_val.0.clone(), key_val.1.clone()));
+    match country_sorting {
+        CountrySorting::Bytes => {
+            countries_vec.sort_by_key(|country| Reverse(country.get_bytes()));
         }
-    } else {
-        // favorites
-        for index in &info_traffic_lock.favorite_connections {
-            let key_val = info_traffic_lock.map.get_index(*index).unwrap();
-            runtime_data
-                .report_vec
-                .push((key_val.0.clone(), key_val.1.clone()));
+        CountrySorting::Packets => {
+            countries_vec.sort_by_key(|country| Reverse(country.packets_in + country.packets_out));
+        }
+        CountrySorting::Connections => {
+            countries_vec.sort_by_key(|country| Reverse(country.connections));
+        }
+        CountrySorting::Hosts => {
+            countries_vec.sort_by_key(|country| Reverse(country.hosts));
+        }
+    }
+    runtime_data.countries_vec = countries_vec;
+}
+
+/// Aggregates the connections by remote address
+fn get_hosts(info_traffic: &Arc<Mutex<InfoTraffic>>) -> Vec<HostInfo> {
+    let info_traffic_lock = info_traffic.lock().unwrap();
+    let mut hosts: HashMap<&str, HostInfo> = HashMap::new();
+    for (key, info) in &info_traffic_lock.map {
+        let address = get_remote_address(info.traffic_type, key);
+        match hosts.get_mut(address) {
+            Some(host_info) => host_info.add_connection(key, info),
+            None => {
+                hosts.insert(address, HostInfo::new(key, info));
+            }
+        }
+    }
+    hosts.into_values().collect()
+}
+
+/// Aggregates the remote hosts by autonomous system (hosts without one are left as they are)
+fn get_organizations(hosts: Vec<HostInfo>) -> Vec<HostInfo> {
+    let mut organizations: HashMap<u32, HostInfo> = HashMap::new();
+    let mut ret_val = Vec::new();
+    for host_info in hosts {
+        match host_info.asn.clone() {
+            None => ret_val.push(host_info),
+            Some(asn) => match organizations.get_mut(&asn.number) {
+                Some(organization) => organization.add_host(&host_info),
+                None => {
+                    // the organization is displayed in place of the host name
+                    organizations.insert(
+                        asn.number,
+                        HostInfo {
+                            address: format!("AS{}", asn.number),
+                            name: Some(asn.name),
+                            asn: None,
+                            ..host_info
+                        },
+                    );
+                }
+            },
         }
     }
+    ret_val.extend(organizations.into_values());
+    ret_val
 }
@@ -1,8 +1,15 @@
 pub mod countries;
+pub mod country_centroids;
+pub mod dns_parser;
 pub mod get_formatted_strings;
+pub mod icmp_types;
+pub mod link_types;
 pub mod manage_charts_data;
+pub mod manage_filters;
 pub mod manage_notifications;
 pub mod manage_packets;
 pub mod manage_report_data;
+pub mod payload_signatures;
+pub mod server_name_parser;
 pub mod style_constants;
 pub mod translations;
@@ -0,0 +1,253 @@
+//! Module containing the signatures used to recognize the application layer protocols
+//! from the payload of the packets, independently of the transport layer ports.
+
+use crate::utility::dns_parser::is_dns_message;
+use crate::{AppProtocol, TransProtocol};
+
+/// Prefix of the messages starting an HTTP/1.x request or response
+const HTTP_PREFIXES: [&[u8]; 10] = [
+    b"GET ",
+    b"POST ",
+    b"HEAD ",
+    b"PUT ",
+    b"DELETE ",
+    b"OPTIONS ",
+    b"PATCH ",
+    b"CONNECT ",
+    b"TRACE ",
+    b"HTTP/1.",
+];
+/// Handshake sent by BitTorrent peers over TCP
+const BITTORRENT_HANDSHAKE: &[u8] = b"\x13BitTorrent protocol";
+/// QUIC versions recognized in long header packets (v1, v2 and the IETF drafts prefix)
+const QUIC_VERSIONS: [[u8; 4]; 2] = [[0x00, 0x00, 0x00, 0x01], [0x6b, 0x33, 0x43, 0xcf]];
+
+/// Returns the application layer protocol whose signature is found in the payload, if any.
+///
+/// When the signature is that of a family of protocols (e.g. TLS or DNS), the protocol guessed from
+/// the ports is kept if it belongs to the same family (e.g. IMAPS or mDNS).
+/// The labels of the user's port mappings are never overridden.
+///
+/// # Arguments
+///
+/// * `payload` - The transport layer payload of a packet.
+///
+/// * `transport_protocol` - The transport layer protocol carrying the payload.
+///
+/// * `port_guess` - The application layer protocol guessed from the well-known ports.
+pub fn get_payload_protocol(
+    payload: &[u8],
+    transport_protocol: TransProtocol,
+    port_guess: AppProtocol,
+) -> Option<AppProtocol> {
+    if matches!(port_guess, AppProtocol::Custom(_)) {
+        return None;
+    }
+    let detected = match transport_protocol {
+        TransProtocol::TCP => get_tcp_payload_protocol(payload),
+        TransProtocol::UDP => get_udp_payload_protocol(payload),
+        _ => None,
+    }?;
+    let same_family = match detected {
+        AppProtocol::TLS => matches!(
+            port_guess,
+            AppProtocol::HTTPS
+                | AppProtocol::IMAPS
+                | AppProtocol::POP3S
+                | AppProtocol::LDAPS
+                | AppProtocol::FTPS
+                | AppProtocol::SMTPS
+        ),
+        AppProtocol::DNS => matches!(port_guess, AppProtocol::mDNS | AppProtocol::LLMNR),
+        _ => false,
+    };
+    if same_family {
+        Some(port_guess)
+    } else {
+        Some(detected)
+    }
+}
+
+fn get_tcp_payload_protocol(payload: &[u8]) -> Option<AppProtocol> {
+    if is_tls_record(payload) {
+        Some(AppProtocol::TLS)
+    } else if HTTP_PREFIXES
+        .iter()
+        .any(|prefix| payload.starts_with(prefix))
+    {
+        Some(AppProtocol::HTTP)
+    } else if payload.starts_with(b"SSH-") {
+        Some(AppProtocol::SSH)
+    } else if payload.starts_with(BITTORRENT_HANDSHAKE) {
+        Some(AppProtocol::BitTorrent)
+    } else if payload.starts_with(b"+OK") {
+        Some(AppProtocol::POP)
+    } else if payload.starts_with(b"* OK") {
+        Some(AppProtocol::IMAP)
+    } else if payload.len() > 2 && is_dns_message(&payload[2..]) {
+        // DNS messages over TCP are prefixed by their length
+        let len = usize::from(u16::from_be_bytes([payload[0], payload[1]]));
+        (len == payload.len() - 2).then_some(AppProtocol::DNS)
+    } else {
+        None
+    }
+}
+
+fn get_udp_payload_protocol(payload: &[u8]) -> Option<AppProtocol> {
+    if is_quic_long_header(payload) {
+        Some(AppProtocol::QUIC)
+    } else if is_dht_message(payload) {
+        Some(AppProtocol::BitTorrent)
+    } else if is_dns_message(payload) {
+        Some(AppProtocol::DNS)
+    } else {
+        None
+    }
+}
+
+/// Checks the header of a TLS record: content type, protocol version and length
+fn is_tls_record(payload: &[u8]) -> bool {
+    match payload {
+        [content_type, 0x03, minor_version, len_high, len_low, ..] => {
+            (0x14..=0x17).contains(content_type)
+                && *minor_version <= 0x04
+                && u16::from_be_bytes([*len_high, *len_low]) <= 16384 + 2048
+        }
+        _ => false,
+    }
+}
+
+/// Checks the header form and fixed bits of QUIC long header packets (sent during the handshake),
+/// together with the protocol version
+fn is_quic_long_header(payload: &[u8]) -> bool {
+    match payload {
+        [first, v0, v1, v2, v3, ..] => {
+            first & 0xC0 == 0xC0
+                && (QUIC_VERSIONS.contains(&[*v0, *v1, *v2, *v3]) || (*v0 == 0xff && *v1 == 0x00))
+        }
+        _ => false,
+    }
+}
+
+/// Checks if the payload is a bencoded query or response of the BitTorrent distributed hash table
+fn is_dht_message(payload: &[u8]) -> bool {
+    (payload.starts_with(b"d1:ad2:id20:") || payload.starts_with(b"d1:rd2:id20:"))
+        && payload.ends_with(b"e")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tls_on_any_port() {
+        let client_hello = [0x16, 0x03, 0x01, 0x02, 0x00, 0x01, 0x00, 0x01, 0xfc];
+        assert_eq!(
+            get_payload_protocol(&client_hello, TransProtocol::TCP, AppProtocol::Other),
+            Some(AppProtocol::TLS)
+        );
+        assert_eq!(
+            get_payload_protocol(&client_hello, TransProtocol::TCP, AppProtocol::IMAPS),
+            Some(AppProtocol::IMAPS)
+        );
+        // TLS found on the HTTP port
+        assert_eq!(
+            get_payload_protocol(&client_hello, TransProtocol::TCP, AppProtocol::HTTP),
+            Some(AppProtocol::TLS)
+        );
+    }
+
+    #[test]
+    fn port_mappings_are_not_overridden() {
+        let client_hello = [0x16, 0x03, 0x01, 0x02, 0x00, 0x01, 0x00, 0x01, 0xfc];
+        assert_eq!(
+            get_payload_protocol(
+                &client_hello,
+                TransProtocol::TCP,
+                AppProtocol::Custom("Internal".to_string())
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn http_and_ssh_on_non_standard_ports() {
+        assert_eq!(
+            get_payload_protocol(
+                b"GET / HTTP/1.1\r\nHost: localhost:8081\r\n\r\n",
+                TransProtocol::TCP,
+                AppProtocol::Other
+            ),
+            Some(AppProtocol::HTTP)
+        );
+        assert_eq!(
+            get_payload_protocol(
+                b"SSH-2.0-OpenSSH_9.0\r\n",
+                TransProtocol::TCP,
+                AppProtocol::Other
+            ),
+            Some(AppProtocol::SSH)
+        );
+    }
+
+    #[test]
+    fn quic_initial_packet() {
+        let initial = [0xc3, 0x00, 0x00, 0x00, 0x01, 0x08, 0x83, 0x94];
+        assert_eq!(
+            get_payload_protocol(&initial, TransProtocol::UDP, AppProtocol::HTTPS),
+            Some(AppProtocol::QUIC)
+        );
+        // same bytes over TCP
+        assert_eq!(
+            get_payload_protocol(&initial, TransProtocol::TCP, AppProtocol::HTTPS),
+            None
+        );
+    }
+
+    #[test]
+    fn bittorrent_handshake_and_dht() {
+        let mut handshake = BITTORRENT_HANDSHAKE.to_vec();
+        handshake.extend_from_slice(&[0; 8]);
+        assert_eq!(
+            get_payload_protocol(&handshake, TransProtocol::TCP, AppProtocol::Other),
+            Some(AppProtocol::BitTorrent)
+        );
+        let ping = b"d1:ad2:id20:abcdefghij0123456789e1:q4:ping1:t2:aa1:y1:qe";
+        assert_eq!(
+            get_payload_protocol(ping, TransProtocol::UDP, AppProtocol::Other),
+            Some(AppProtocol::BitTorrent)
+        );
+    }
+
+    #[test]
+    fn dns_query() {
+        let mut query = vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        query.extend_from_slice(b"\x07example\x03com\x00\x00\x01\x00\x01");
+        assert_eq!(
+            get_payload_protocol(&query, TransProtocol::UDP, AppProtocol::Other),
+            Some(AppProtocol::DNS)
+        );
+        assert_eq!(
+            get_payload_protocol(&query, TransProtocol::UDP, AppProtocol::mDNS),
+            Some(AppProtocol::mDNS)
+        );
+        let mut tcp_query = u16::try_from(query.len()).unwrap().to_be_bytes().to_vec();
+        tcp_query.extend(query);
+        assert_eq!(
+            get_payload_protocol(&tcp_query, TransProtocol::TCP, AppProtocol::Other),
+            Some(AppProtocol::DNS)
+        );
+    }
+
+    #[test]
+    fn unknown_payloads() {
+        assert_eq!(
+            get_payload_protocol(b"hello world", TransProtocol::TCP, AppProtocol::Other),
+            None
+        );
+        assert_eq!(
+            get_payload_protocol(&[], TransProtocol::UDP, AppProtocol::NTP),
+            None
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Module containing functions to extract the name of the contacted server from the TCP payloads:
+//! the Server Name Indication of TLS ClientHello messages and the `Host` header of HTTP requests.
+
+/// TLS record content type of handshake messages
+const TLS_HANDSHAKE: u8 = 0x16;
+/// TLS handshake type of ClientHello messages
+const TLS_CLIENT_HELLO: u8 = 0x01;
+/// TLS extension type of the Server Name Indication
+const TLS_SERVER_NAME_EXTENSION: u16 = 0x0000;
+/// Methods starting an HTTP/1.x request
+const HTTP_METHODS: [&str; 9] = [
+    "GET", "POST", "HEAD", "PUT", "DELETE", "OPTIONS", "PATCH", "CONNECT", "TRACE",
+];
+
+/// Returns the name of the server contacted by the client sending the given TCP payload, if any.
+///
+/// Payloads truncated by the capture snapshot length could not contain the name.
+pub fn get_server_name(payload: &[u8]) -> Option<String> {
+    parse_tls_server_name(payload).or_else(|| parse_http_host(payload))
+}
+
+/// Parses a TLS ClientHello message, returning its Server Name Indication
+pub fn parse_tls_server_name(payload: &[u8]) -> Option<String> {
+    let mut reader = Reader { payload, offset: 0 };
+    // record header: content type, version, length
+    if reader.read_u8()? != TLS_HANDSHAKE || reader.read_u8()? != 0x03 {
+        return None;
+    }
+    reader.skip(3)?;
+    // handshake header: type, length (3 bytes), client version, random
+    if reader.read_u8()? != TLS_CLIENT_HELLO {
+        return None;
+    }
+    reader.skip(3 + 2 + 32)?;
+    // session id, cipher suites, compression methods
+    let session_id_len = usize::from(reader.read_u8()?);
+    reader.skip(session_id_len)?;
+    let cipher_suites_len = usize::from(reader.read_u16()?);
+    reader.skip(cipher_suites_len)?;
+    let compression_methods_len = usize::from(reader.read_u8()?);
+    reader.skip(compression_methods_len)?;
+    // extensions
+    let extensions_end = usize::from(reader.read_u16()?) + reader.offset;
+    while reader.offset + 4 <= extensions_end {
+        let extension_type = reader.read_u16()?;
+        let extension_len = usize::from(reader.read_u16()?);
+        if extension_type != TLS_SERVER_NAME_EXTENSION {
+            reader.skip(extension_len)?;
+            continue;
+        }
+        // server name list length, name type (0 = host name), name length
+        reader.skip(2)?;
+        if reader.read_u8()? != 0 {
+            return None;
+        }
+        let name_len = usize::from(reader.read_u16()?);
+        let name = reader.read_bytes(name_len)?;
+        return to_server_name(name);
+    }
+    None
+}
+
+/// Parses an HTTP/1.x request, returning the value of its `Host` header (without port)
+pub fn parse_http_host(payload: &[u8]) -> Option<String> {
+    let method = payload.split(|byte| *byte == b' ').next()?;
+    if !HTTP_METHODS.iter().any(|m| m.as_bytes().eq(method)) {
+        return None;
+    }
+    let text = String::from_utf8_lossy(payload);
+    for line in text.split("\r\n").skip(1) {
+        if line.is_empty() {
+            // end of the headers
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("host") {
+                let value = value.trim();
+                // remove the port, if any (IPv6 addresses are enclosed in brackets)
+                let host = match value.rsplit_once(':') {
+                    Some((host, port))
+                        if !port.contains(']') && port.chars().all(|c| c.is_ascii_digit()) =>
+                    {
+                        host
+                    }
+                    _ => value,
+                };
+                return to_server_name(host.as_bytes());
+            }
+        }
+    }
+    None
+}
+
+/// Validates a server name, converting it to lowercase
+fn to_server_name(name: &[u8]) -> Option<String> {
+    let name = std::str::from_utf8(name).ok()?;
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._:[]".contains(c))
+    {
+        return None;
+    }
+    Some(name.to_ascii_lowercase())
+}
+
+/// Cursor over the bytes of a payload
+struct Reader<'a> {
+    payload: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.payload.get(self.offset..self.offset + len)?;
+        self.offset += len;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        self.read_bytes(2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a TLS record containing a ClientHello with the given extensions
+    fn client_hello(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0xAB; 32]);
+        // session id
+        body.push(32);
+        body.extend_from_slice(&[0xCD; 32]);
+        // cipher suites
+        body.extend_from_slice(&[0x00, 0x04, 0x13, 0x01, 0x13, 0x02]);
+        // compression methods
+        body.extend_from_slice(&[0x01, 0x00]);
+        let mut extensions_bytes = Vec::new();
+        for (extension_type, data) in extensions {
+            extensions_bytes.extend_from_slice(&extension_type.to_be_bytes());
+            extensions_bytes.extend_from_slice(&u16::try_from(data.len()).unwrap().to_be_bytes());
+            extensions_bytes.extend_from_slice(data);
+        }
+        body.extend_from_slice(&u16::try_from(extensions_bytes.len()).unwrap().to_be_bytes());
+        body.extend(extensions_bytes);
+
+        let mut handshake = vec![TLS_CLIENT_HELLO];
+        handshake.extend_from_slice(&u32::try_from(body.len()).unwrap().to_be_bytes()[1..]);
+        handshake.extend(body);
+
+        let mut record = vec![TLS_HANDSHAKE, 0x03, 0x01];
+        record.extend_from_slice(&u16::try_from(handshake.len()).unwrap().to_be_bytes());
+        record.extend(handshake);
+        record
+    }
+
+    fn server_name_extension(name: &str) -> (u16, Vec<u8>) {
+        let mut data = (u16::try_from(name.len()).unwrap() + 3)
+            .to_be_bytes()
+            .to_vec();
+        data.push(0);
+        data.extend_from_slice(&u16::try_from(name.len()).unwrap().to_be_bytes());
+        data.extend_from_slice(name.as_bytes());
+        (TLS_SERVER_NAME_EXTENSION, data)
+    }
+
+    #[test]
+    fn tls_server_name_after_other_extensions() {
+        let payload = client_hello(&[
+            (0x0017, vec![]),
+            (0x002b, vec![0x02, 0x03, 0x04]),
+            server_name_extension("WWW.Example.com"),
+        ]);
+        assert_eq!(
+            get_server_name(&payload),
+            Some("www.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn tls_without_server_name() {
+        let payload = client_hello(&[(0x0017, vec![])]);
+        assert_eq!(parse_tls_server_name(&payload), None);
+    }
+
+    #[test]
+    fn truncated_tls_client_hello() {
+        let mut payload = client_hello(&[server_name_extension("example.com")]);
+        payload.truncate(payload.len() - 4);
+        assert_eq!(get_server_name(&payload), None);
+    }
+
+    #[test]
+    fn http_host_header() {
+        let payload = b"GET /index.html HTTP/1.1\r\nUser-Agent: curl/7.87.0\r\nHOST: Example.org:8080\r\n\r\n";
+        assert_eq!(get_server_name(payload), Some("example.org".to_string()));
+        let payload = b"POST / HTTP/1.1\r\nhost: [fe80::1]\r\nContent-Length: 0\r\n\r\n";
+        assert_eq!(get_server_name(payload), Some("[fe80::1]".to_string()));
+    }
+
+    #[test]
+    fn http_responses_and_bodies_are_ignored() {
+        let payload = b"HTTP/1.1 200 OK\r\nHost: example.org\r\n\r\n";
+        assert_eq!(get_server_name(payload), None);
+        let payload = b"GET / HTTP/1.1\r\nAccept: */*\r\n\r\nHost: example.org\r\n";
+        assert_eq!(get_server_name(payload), None);
+    }
+}
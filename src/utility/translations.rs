@@ -3,8 +3,8 @@ use iced::widget::Text;
 
 pub fn choose_adapters_translation(language: Language) -> Text<'static> {
     Text::new(match language {
-        Language::EN => "Select network adapter to inspect",
-        Language::IT => "Seleziona la scheda di rete da ispezionare",
+        Language::EN => "Select network adapters to inspect",
+        Language::IT => "Seleziona le schede di rete da ispezionare",
     })
 }
 
@@ -172,10 +172,15 @@ pub fn filtered_bytes_translation(
     language: Language,
     filtered: &str,
     percentage: &str,
+    byte_counting: &str,
 ) -> Text<'static> {
     Text::new(match language {
-        Language::EN => format!("Filtered bytes:\n   {filtered} ({percentage} of the total)"),
-        Language::IT => format!("Byte filtrati:\n   {filtered} ({percentage} del totale)"),
+        Language::EN => format!(
+            "Filtered bytes (counting the {byte_counting}):\n   {filtered} ({percentage} of the total)"
+        ),
+        Language::IT => format!(
+            "Byte filtrati (contando il {byte_counting}):\n   {filtered} ({percentage} del totale)"
+        ),
     })
 }
 
@@ -195,6 +200,32 @@ pub fn no_favorites_translation(language: Language) -> Text<'static> {
     })
 }
 
+pub fn no_search_results_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "No connection matches the search.",
+        Language::IT => "Nessuna connessione corrisponde alla ricerca.",
+    })
+}
+
+pub fn search_connections_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Search address, port, country, protocol, host name...",
+        Language::IT => "Cerca indirizzo, porta, paese, protocollo, nome host...",
+    }
+}
+
+pub fn connections_page_translation(
+    language: Language,
+    page: usize,
+    pages: usize,
+    connections: usize,
+) -> String {
+    match language {
+        Language::EN => format!("Page {page} of {pages} ({connections} connections)"),
+        Language::IT => format!("Pagina {page} di {pages} ({connections} connessioni)"),
+    }
+}
+
 pub fn error_translation(language: Language, error: &str) -> Text<'static> {
     Text::new(match language {
         Language::EN => format!(
@@ -215,6 +246,13 @@ pub fn both_translation(language: Language) -> &'static str {
     }
 }
 
+pub fn all_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "all",
+        Language::IT => "tutti",
+    }
+}
+
 // pub fn all_protocols_translation(language: Language) -> &'static str {
 //     match language {
 //         Language::EN => "All protocols",
@@ -236,6 +274,83 @@ pub fn bytes_chart_translation(language: Language) -> &'static str {
     }
 }
 
+pub fn remote_hosts_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "Remote hosts:",
+        Language::IT => "Host remoti:",
+    })
+}
+
+pub fn connections_report_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "most connections",
+        Language::IT => "più connessioni",
+    }
+}
+
+pub fn hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Hosts",
+        Language::IT => "Host",
+    }
+}
+
+pub fn no_hosts_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "No remote host to show at the moment.",
+        Language::IT => "Nessun host remoto da mostrare al momento.",
+    })
+}
+
+pub fn shown_hosts_translation(language: Language, shown: usize, total: usize) -> String {
+    match language {
+        Language::EN => format!("Showing {shown} of {total} hosts"),
+        Language::IT => format!("Mostrati {shown} host su {total}"),
+    }
+}
+
+pub fn countries_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Countries",
+        Language::IT => "Paesi",
+    }
+}
+
+pub fn countries_title_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "Countries:",
+        Language::IT => "Paesi:",
+    })
+}
+
+pub fn hosts_report_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "most hosts",
+        Language::IT => "più host",
+    }
+}
+
+pub fn no_countries_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "No country to show at the moment.",
+        Language::IT => "Nessun paese da mostrare al momento.",
+    })
+}
+
+pub fn world_map_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "Circles are placed on the countries centres and grow with the exchanged bytes.\nClick on a country to show its connections in the overview.",
+        Language::IT => "I cerchi sono posti al centro dei paesi e crescono con i byte scambiati.\nClicca su un paese per mostrarne le connessioni nella panoramica.",
+    })
+}
+
+pub fn country_connections_translation(language: Language, country: &str) -> String {
+    match language {
+        Language::EN => format!("Connections with {country}"),
+        Language::IT => format!("Connessioni con {country}"),
+    }
+}
+
 pub fn recent_report_translation(language: Language) -> &'static str {
     match language {
         Language::EN => "most recent",
@@ -257,6 +372,13 @@ pub fn bytes_report_translation(language: Language) -> &'static str {
     }
 }
 
+pub fn all_report_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "all",
+        Language::IT => "tutte",
+    }
+}
+
 pub fn favorite_report_translation(language: Language) -> &'static str {
     match language {
         Language::EN => "favorites",
@@ -439,6 +561,13 @@ pub fn open_report_translation(language: Language) -> &'static str {
     }
 }
 
+pub fn report_error_translation(language: Language, error: &str) -> String {
+    match language {
+        Language::EN => format!("The report is no longer updated: {error}"),
+        Language::IT => format!("Il report non viene più aggiornato: {error}"),
+    }
+}
+
 pub fn bytes_exceeded_translation(language: Language) -> &'static str {
     match language {
         Language::EN => "Bytes threshold exceeded!",
@@ -505,3 +634,579 @@ pub fn only_last_30_translation(language: Language) -> &'static str {
         Language::IT => "Solo le ultime 30 notifiche sono mostrate",
     }
 }
+
+pub fn network_adapter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Network adapter",
+        Language::IT => "Adattatore di rete",
+    }
+}
+
+pub fn capture_file_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Capture file (pcap/pcapng)",
+        Language::IT => "File di cattura (pcap/pcapng)",
+    }
+}
+
+pub fn capture_file_path_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Path of the capture file to analyze",
+        Language::IT => "Percorso del file di cattura da analizzare",
+    }
+}
+
+pub fn waiting_file_translation(language: Language, file: &str) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => format!(
+            "No traffic has been observed yet. Reading network packets...\n\n\
+                                 Capture file: {file}\n\n\
+                                 Are you sure the file you selected contains any packet?"
+        ),
+        Language::IT => format!(
+            "Nessun tipo di traffico è stato osservato finora. Leggo pacchetti di rete...\n\n\
+                                File di cattura: {file}\n\n\
+                                Sei sicuro che il file selezionato contenga dei pacchetti?"
+        ),
+    })
+}
+
+pub fn advanced_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Advanced",
+        Language::IT => "Avanzate",
+    }
+}
+
+pub fn advanced_title_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "Customize the analysis",
+        Language::IT => "Personalizza l'analisi",
+    })
+}
+
+pub fn recording_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Recording of sniffed packets (pcap files saved next to the reports)",
+        Language::IT => "Registrazione dei pacchetti (file pcap salvati accanto ai report)",
+    }
+}
+
+pub fn record_only_filtered_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Save only the packets selected by the filters",
+        Language::IT => "Salva solo i pacchetti selezionati dai filtri",
+    }
+}
+
+pub fn record_max_size_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Create a new file when a size is exceeded",
+        Language::IT => "Crea un nuovo file quando una dimensione è superata",
+    }
+}
+
+pub fn record_max_duration_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Create a new file when a duration is exceeded",
+        Language::IT => "Crea un nuovo file quando una durata è superata",
+    }
+}
+
+pub fn seconds_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "seconds",
+        Language::IT => "secondi",
+    }
+}
+
+pub fn start_recording_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Start saving packets to file",
+        Language::IT => "Inizia a salvare i pacchetti su file",
+    }
+}
+
+pub fn stop_recording_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Stop saving packets to file",
+        Language::IT => "Smetti di salvare i pacchetti su file",
+    }
+}
+
+pub fn bpf_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "BPF filter expression (optional)",
+        Language::IT => "Espressione di filtro BPF (opzionale)",
+    }
+}
+
+pub fn multicast_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Multicast",
+        Language::IT => "Multicast",
+    }
+}
+
+pub fn broadcast_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Broadcast",
+        Language::IT => "Broadcast",
+    }
+}
+
+pub fn duration_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Duration",
+        Language::IT => "Durata",
+    }
+}
+
+pub fn round_trip_time_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Round trip time",
+        Language::IT => "Tempo di andata e ritorno",
+    }
+}
+
+pub fn tcp_state_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "TCP state",
+        Language::IT => "Stato TCP",
+    }
+}
+
+pub fn tcp_flags_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "TCP flags",
+        Language::IT => "Flag TCP",
+    }
+}
+
+pub fn retransmissions_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Retransmissions",
+        Language::IT => "Ritrasmissioni",
+    }
+}
+
+pub fn out_of_order_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Out of order segments",
+        Language::IT => "Segmenti fuori ordine",
+    }
+}
+
+pub fn syn_sent_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Connection requested",
+        Language::IT => "Connessione richiesta",
+    }
+}
+
+pub fn established_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Established",
+        Language::IT => "Stabilita",
+    }
+}
+
+pub fn closed_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Closed",
+        Language::IT => "Chiusa",
+    }
+}
+
+pub fn reset_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Reset",
+        Language::IT => "Interrotta (reset)",
+    }
+}
+
+pub fn refused_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Refused",
+        Language::IT => "Rifiutata",
+    }
+}
+
+pub fn handshake_not_seen_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Handshake not seen",
+        Language::IT => "Handshake non osservato",
+    }
+}
+
+pub fn unknown_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Not identified",
+        Language::IT => "Non identificato",
+    }
+}
+
+pub fn traffic_type_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Traffic type (none selected means all)",
+        Language::IT => "Tipo di traffico (nessuna selezione equivale a tutti)",
+    }
+}
+
+pub fn addresses_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP addresses or networks",
+        Language::IT => "Indirizzi IP o reti",
+    }
+}
+
+pub fn ports_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Ports or port ranges",
+        Language::IT => "Porte o intervalli di porte",
+    }
+}
+
+pub fn countries_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Country codes",
+        Language::IT => "Codici dei paesi",
+    }
+}
+
+pub fn report_settings_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Reports (a textual report is always written for each session)",
+        Language::IT => "Report (per ogni sessione viene sempre scritto un report testuale)",
+    }
+}
+
+pub fn export_csv_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Also export the report as CSV",
+        Language::IT => "Esporta il report anche in formato CSV",
+    }
+}
+
+pub fn export_json_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Also export the report as JSON lines",
+        Language::IT => "Esporta il report anche in formato JSON lines",
+    }
+}
+
+pub fn report_directory_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Directory:",
+        Language::IT => "Cartella:",
+    }
+}
+
+pub fn max_sessions_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Keep the reports of the last",
+        Language::IT => "Mantieni i report delle ultime",
+    }
+}
+
+pub fn sessions_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "sessions (packet recordings are never deleted)",
+        Language::IT => "sessioni (le registrazioni dei pacchetti non vengono eliminate)",
+    }
+}
+
+pub fn server_names_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Server names",
+        Language::IT => "Nomi dei server",
+    }
+}
+
+pub fn organizations_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Organizations (an ASN database is needed)",
+        Language::IT => "Organizzazioni (serve un database ASN)",
+    }
+}
+
+pub fn mmdb_settings_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Geolocation databases (MMDB files, applied to the next analysis)",
+        Language::IT => {
+            "Database di geolocalizzazione (file MMDB, applicati alla prossima analisi)"
+        }
+    }
+}
+
+pub fn country_database_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Country database:",
+        Language::IT => "Database paesi:",
+    }
+}
+
+pub fn country_database_placeholder_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Embedded GeoLite2-Country",
+        Language::IT => "GeoLite2-Country integrato",
+    }
+}
+
+pub fn city_database_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "City database:",
+        Language::IT => "Database città:",
+    }
+}
+
+pub fn city_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "City",
+        Language::IT => "Città",
+    }
+}
+
+pub fn coordinates_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Coordinates",
+        Language::IT => "Coordinate",
+    }
+}
+
+pub fn asn_database_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "ASN database:",
+        Language::IT => "Database ASN:",
+    }
+}
+
+pub fn autonomous_system_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Autonomous system",
+        Language::IT => "Sistema autonomo",
+    }
+}
+
+pub fn group_by_organization_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Group by organization",
+        Language::IT => "Raggruppa per organizzazione",
+    }
+}
+
+pub fn capture_settings_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Capture (applied to the next analysis)",
+        Language::IT => "Cattura (applicata alla prossima analisi)",
+    }
+}
+
+pub fn snaplen_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bytes captured for each packet of a network adapter:",
+        Language::IT => "Byte catturati per ogni pacchetto di un adattatore di rete:",
+    }
+}
+
+pub fn byte_counting_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bytes counted for each packet:",
+        Language::IT => "Byte contati per ogni pacchetto:",
+    }
+}
+
+pub fn bidirectional_flows_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Show both directions of a conversation as a single connection",
+        Language::IT => {
+            "Mostra entrambe le direzioni di una conversazione come una singola connessione"
+        }
+    }
+}
+
+pub fn frame_counting_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "whole frame",
+        Language::IT => "frame intero",
+    }
+}
+
+pub fn network_counting_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP packet",
+        Language::IT => "pacchetto IP",
+    }
+}
+
+pub fn payload_counting_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "transport payload",
+        Language::IT => "payload di trasporto",
+    }
+}
+
+pub fn snaplen_hint_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => {
+            "higher values are needed to find the server names in TLS and HTTP requests"
+        }
+        Language::IT => {
+            "valori più alti servono a trovare i nomi dei server nelle richieste TLS e HTTP"
+        }
+    }
+}
+
+pub fn protocols_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Protocols",
+        Language::IT => "Protocolli",
+    }
+}
+
+pub fn protocols_title_translation(language: Language) -> Text<'static> {
+    Text::new(match language {
+        Language::EN => "Assign protocols to ports (checked before the well-known ports)",
+        Language::IT => "Assegna protocolli alle porte (verificate prima delle porte note)",
+    })
+}
+
+pub fn port_mapping_ports_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Port or range:",
+        Language::IT => "Porta o intervallo:",
+    }
+}
+
+pub fn port_mapping_label_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Protocol:",
+        Language::IT => "Protocollo:",
+    }
+}
+
+pub fn add_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Add",
+        Language::IT => "Aggiungi",
+    }
+}
+
+pub fn remove_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Remove",
+        Language::IT => "Rimuovi",
+    }
+}
+
+pub fn no_port_mappings_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "No port mapping defined yet",
+        Language::IT => "Nessuna associazione di porte ancora definita",
+    }
+}
+
+pub fn connection_details_translation(language: Language) -> String {
+    match language {
+        Language::EN => "Connection details".to_string(),
+        Language::IT => "Dettagli della connessione".to_string(),
+    }
+}
+
+pub fn show_details_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Show details",
+        Language::IT => "Mostra dettagli",
+    }
+}
+
+pub fn source_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Source",
+        Language::IT => "Sorgente",
+    }
+}
+
+pub fn destination_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Destination",
+        Language::IT => "Destinazione",
+    }
+}
+
+pub fn server_name_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Server name",
+        Language::IT => "Nome del server",
+    }
+}
+
+pub fn domain_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Domain",
+        Language::IT => "Dominio",
+    }
+}
+
+pub fn host_name_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Host name",
+        Language::IT => "Nome host",
+    }
+}
+
+pub fn country_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Country",
+        Language::IT => "Paese",
+    }
+}
+
+pub fn packets_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Packets",
+        Language::IT => "Pacchetti",
+    }
+}
+
+pub fn bytes_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bytes",
+        Language::IT => "Byte",
+    }
+}
+
+pub fn first_packet_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "First packet",
+        Language::IT => "Primo pacchetto",
+    }
+}
+
+pub fn last_packet_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Last packet",
+        Language::IT => "Ultimo pacchetto",
+    }
+}
+
+pub fn icmp_messages_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "ICMP messages",
+        Language::IT => "Messaggi ICMP",
+    }
+}
+
+pub fn connection_not_found_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "The connection is no longer available",
+        Language::IT => "La connessione non è più disponibile",
+    }
+}
+
+pub fn network_adapters_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Network adapters",
+        Language::IT => "Adattatori di rete",
+    }
+}